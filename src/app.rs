@@ -1,10 +1,20 @@
 use std::sync::Arc;
 
+use crate::io::reader::NamedFrame;
 use crate::misc::config::config;
+use crate::misc::paths::snippets_path;
 use crate::misc::remote_load::{self, RemoteLoad};
+use crate::misc::snippets::Snippet;
+use crate::misc::sql::sql;
 use crate::tui::Pane;
+use crate::tui::pane::TableDescription;
 use crate::tui::popups::download_notif::DownloadNotification;
+use crate::tui::popups::plan_viewer::PlanViewer;
+use crate::tui::popups::script_runner::ScriptRunner;
+use crate::tui::popups::snippet_runner::{self, SnippetRunner};
+use crate::tui::popups::sql_editor::SqlEditor;
 use crate::tui::popups::sql_query_picker::SqlQueryPicker;
+use crate::tui::popups::table_renamer::TableRenamer;
 use crate::tui::table::Table;
 use crate::tui::toast::Toast;
 use crate::tui::{error_popup::ErrorPopup, tabs::Tabs};
@@ -79,6 +89,60 @@ impl App {
         )));
     }
 
+    fn show_sql_editor(&mut self, query: &str) {
+        self.overlay = Some(Overlay::SqlEditor(SqlEditor::new(
+            query,
+            self.tabs
+                .selected()
+                .map(Pane::table)
+                .map(Table::data_frame)
+                .cloned(),
+        )));
+    }
+
+    /// Opens the query of the current table, if it has one, in the SQL editor to be explained.
+    fn show_explain(&mut self) {
+        let query = match self.tabs.selected().map(Pane::description) {
+            Some(TableDescription::Query(query)) => query.as_str(),
+            _ => "",
+        };
+        self.show_sql_editor(&format!("EXPLAIN {query}"));
+    }
+
+    fn show_script_runner(&mut self) {
+        self.overlay = Some(Overlay::ScriptRunner(ScriptRunner::new(
+            self.tabs
+                .selected()
+                .map(Pane::table)
+                .map(Table::data_frame)
+                .cloned(),
+        )));
+    }
+
+    fn show_snippet_runner(&mut self) {
+        match Snippet::load_all() {
+            Ok(snippets) if snippets.is_empty() => self.show_toast(format!(
+                "No snippets found in '{}'",
+                snippets_path()
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_default()
+            )),
+            Ok(snippets) => {
+                self.overlay = Some(Overlay::SnippetRunner(SnippetRunner::new(
+                    snippet_runner::State::new(
+                        snippets,
+                        self.tabs
+                            .selected()
+                            .map(Pane::table)
+                            .map(Table::data_frame)
+                            .cloned(),
+                    ),
+                )))
+            }
+            Err(err) => self.show_error(err.to_string()),
+        }
+    }
+
     fn add_download(&mut self, url: &Url, reader: Arc<dyn remote_load::Reader>) {
         self.dls.push(DownloadNotification::new(
             url.as_str().to_owned(),
@@ -86,6 +150,16 @@ impl App {
         ));
     }
 
+    fn reload_table(&mut self, name: &str) {
+        match sql().reload_source(name) {
+            Ok((source, reader)) => self.dls.push(DownloadNotification::new(
+                source.display_path().into_owned(),
+                RemoteLoad::reload(name, source, reader),
+            )),
+            Err(err) => self.show_error(err.to_string()),
+        }
+    }
+
     fn reload_app_config(&mut self) {
         if let Err(err) = config().reload() {
             self.show_error(err.to_string());
@@ -183,9 +257,20 @@ impl Component for App {
             Message::AppShowSchema => self.show_schema(),
             Message::AppShowImporter => self.show_importer(),
             Message::AppDismissSchema => self.dismiss_schema(),
+            Message::AppShowTableRenamer(name) => {
+                self.overlay = Some(Overlay::TableRenamer(TableRenamer::new(name.to_owned())))
+            }
             Message::AppShowSqlQuery => self.show_sql_query_picker(),
+            Message::AppShowSnippetRunner => self.show_snippet_runner(),
+            Message::AppShowScriptRunner => self.show_script_runner(),
+            Message::AppShowSqlEditor(query) => self.show_sql_editor(query),
+            Message::AppShowExplain => self.show_explain(),
+            Message::AppShowPlanViewer(plan) => {
+                self.overlay = Some(Overlay::PlanViewer(PlanViewer::new(plan.as_ref().clone())))
+            }
             Message::AppReloadConfig => self.reload_app_config(),
             Message::AppDownloadDataSource(url, reader) => self.add_download(url, reader.clone()),
+            Message::AppReloadTable(name) => self.reload_table(name),
             _ => (),
         };
         match (self.overlay.as_mut(), self.schema.as_mut()) {
@@ -226,13 +311,21 @@ impl Component for App {
             .rev()
             .for_each(|idx| {
                 let dl = self.dls.remove(idx).into_remote_load();
-                match dl.join() {
-                    Ok(nfs) => {
-                        for (name, df) in nfs {
+                match (dl.reloads().map(str::to_owned), dl.join()) {
+                    (Some(name), Ok(nfs)) => {
+                        for frame in nfs {
+                            match sql().set_reloaded(&name, frame) {
+                                Ok(()) => self.show_toast(format!("Table '{name}' reloaded")),
+                                Err(err) => self.show_error(err.to_string()),
+                            }
+                        }
+                    }
+                    (None, Ok(nfs)) => {
+                        for (name, df, _) in nfs.into_iter().map(NamedFrame::into_parts) {
                             Message::TabsAddNamePane(df, name).enqueue();
                         }
                     }
-                    Err(err) => self.show_error(err.to_string()),
+                    (_, Err(err)) => self.show_error(err.to_string()),
                 }
             });
         self.tabs.tick();
@@ -245,6 +338,11 @@ pub enum Overlay {
     CommandPicker(CommandPalette),
     ThemeSelector(ThemeSelector),
     SqlQueryPicker(SqlQueryPicker),
+    SnippetRunner(SnippetRunner),
+    ScriptRunner(ScriptRunner),
+    TableRenamer(TableRenamer),
+    SqlEditor(SqlEditor),
+    PlanViewer(PlanViewer),
     Import(Importer),
     Help(Help),
 }
@@ -258,6 +356,11 @@ impl Overlay {
             Overlay::Help(help) => help,
             Overlay::Import(step_by_step) => step_by_step,
             Overlay::SqlQueryPicker(sql_query_picker) => sql_query_picker,
+            Overlay::SnippetRunner(snippet_runner) => snippet_runner,
+            Overlay::ScriptRunner(script_runner) => script_runner,
+            Overlay::TableRenamer(table_renamer) => table_renamer,
+            Overlay::SqlEditor(sql_editor) => sql_editor,
+            Overlay::PlanViewer(plan_viewer) => plan_viewer,
         }
     }
 }
@@ -25,6 +25,19 @@ pub struct Args {
     #[arg(long, help = "Sets the key for sqlite (if required)", value_enum)]
     pub sqlite_key: Option<String>,
 
+    #[cfg(feature = "duckdb")]
+    #[arg(
+        long,
+        help = "Runs a query against the DuckDB file and loads its result instead of every table."
+    )]
+    pub duckdb_query: Option<String>,
+
+    #[arg(
+        long,
+        help = "Opens an older version of a Delta table or snapshot of an Iceberg table instead of the current one."
+    )]
+    pub table_version: Option<i64>,
+
     #[arg(
         long,
         help = "Specifies if the input does not contain a header row.",
@@ -32,6 +45,14 @@ pub struct Args {
     )]
     pub no_header: bool,
 
+    #[arg(
+        long,
+        help = "Specifies if the input contains a header row, instead of detecting it.",
+        default_value_t = false,
+        conflicts_with = "no_header"
+    )]
+    pub header: bool,
+
     #[arg(
         long,
         help = "Ignores parsing errors while loading.",
@@ -57,19 +78,17 @@ pub struct Args {
 
     #[arg(
         long,
-        help = "Character used as the field separator or delimiter while loading DSV files.",
-        required = false,
-        default_value_t = ','
+        help = "Character used as the field separator or delimiter while loading DSV files. Detected from the content if not set.",
+        required = false
     )]
-    pub separator: char,
+    pub separator: Option<char>,
 
     #[arg(
         long,
-        help = "Character used to quote fields while loading DSV files.",
-        required = false,
-        default_value_t = '"'
+        help = "Character used to quote fields while loading DSV files. Detected from the content if not set.",
+        required = false
     )]
-    pub quote_char: char,
+    pub quote_char: Option<char>,
 
     #[arg(
         long,
@@ -103,6 +122,52 @@ pub struct Args {
     )]
     pub truncate_ragged_lines: bool,
 
+    #[arg(
+        long,
+        help = "Number of data rows to skip at the start of CSV, JSONL, Parquet and Arrow files.",
+        required = false,
+        default_value_t = 0_usize
+    )]
+    pub skip_rows: usize,
+
+    #[arg(
+        long,
+        help = "Maximum number of rows to load from CSV, JSONL, Parquet and Arrow files.",
+        required = false
+    )]
+    pub n_rows: Option<usize>,
+
+    #[arg(
+        long,
+        help = "A comma-separated list of the columns to load from CSV, JSONL, Parquet and Arrow files.",
+        required = false,
+        value_delimiter = ','
+    )]
+    pub columns: Vec<String>,
+
+    #[arg(
+        long,
+        help = "A comma-separated list of strings to read as null, such as 'NA,\\N'.",
+        required = false,
+        value_delimiter = ','
+    )]
+    pub null_values: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Lines starting with this prefix are ignored while loading CSV and JSONL files. Detected from the content of CSV files if not set.",
+        required = false
+    )]
+    pub comment_prefix: Option<String>,
+
+    #[arg(
+        long,
+        help = "Character encoding of text-based files, such as 'windows-1252' or 'utf-16le'. Detected from the byte order mark or the content when set to 'auto'.",
+        required = false,
+        default_value = "auto"
+    )]
+    pub encoding: String,
+
     #[arg(
         long,
         help = "Specifies the types to infer for text-based files.",
@@ -118,6 +183,44 @@ pub struct Args {
         default_value_t = false
     )]
     pub no_type_inference: bool,
+
+    #[arg(
+        long,
+        help = "Replaces labeled values with their value labels while loading SPSS and Stata files.",
+        required = false,
+        default_value_t = false
+    )]
+    pub apply_value_labels: bool,
+
+    #[arg(
+        long,
+        help = "Regular expression whose named capture groups become the columns while loading with the regex format.",
+        required = false
+    )]
+    pub pattern: Option<String>,
+
+    #[arg(
+        long,
+        help = "Built-in pattern of a common log format, used when no pattern is given.",
+        required = false,
+        value_enum
+    )]
+    pub pattern_preset: Option<PatternPreset>,
+
+    #[arg(
+        long,
+        help = "Skips the lines that do not match the pattern instead of collecting them into an 'unmatched' column.",
+        required = false,
+        default_value_t = false
+    )]
+    pub skip_unmatched: bool,
+
+    #[arg(
+        long,
+        help = "Runs the statements of a SQL file once the data is loaded and opens the result of the last query as a tab.",
+        required = false
+    )]
+    pub script: Option<std::path::PathBuf>,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -131,11 +234,22 @@ pub enum Format {
     Arrow,
     Fwf,
     Sqlite,
+    #[cfg(feature = "duckdb")]
+    #[value(name = "duckdb", alias = "duck-db")]
+    DuckDb,
     Excel,
     Logfmt,
     Avro,
     Html,
     Markdown,
+    Yaml,
+    Toml,
+    Sas,
+    Spss,
+    Stata,
+    Delta,
+    Iceberg,
+    Regex,
 }
 
 #[derive(Debug, Clone)]
@@ -228,3 +342,41 @@ impl InferSchema {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PatternPreset {
+    Common,
+    Combined,
+    Syslog,
+    Rfc5424,
+}
+
+impl PatternPreset {
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            PatternPreset::Common => {
+                r#"^(?P<host>\S+) (?P<ident>\S+) (?P<user>\S+) \[(?P<time>[^\]]+)\] "(?P<request>[^"]*)" (?P<status>\d{3}) (?P<size>\S+)$"#
+            }
+            PatternPreset::Combined => {
+                r#"^(?P<host>\S+) (?P<ident>\S+) (?P<user>\S+) \[(?P<time>[^\]]+)\] "(?P<request>[^"]*)" (?P<status>\d{3}) (?P<size>\S+) "(?P<referer>[^"]*)" "(?P<user_agent>[^"]*)"$"#
+            }
+            PatternPreset::Syslog => {
+                r#"^(?:<(?P<priority>\d+)>)?(?P<time>[A-Z][a-z]{2} [ \d]\d \d{2}:\d{2}:\d{2}) (?P<host>\S+) (?P<app>[^\[:\s]+)(?:\[(?P<pid>\d+)\])?: (?P<message>.*)$"#
+            }
+            PatternPreset::Rfc5424 => {
+                r#"^<(?P<priority>\d+)>(?P<version>\d+) (?P<time>\S+) (?P<host>\S+) (?P<app>\S+) (?P<pid>\S+) (?P<msgid>\S+) (?P<structured_data>-|(?:\[.*?\])+) ?(?P<message>.*)$"#
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for PatternPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternPreset::Common => write!(f, "Common Log Format (Apache/Nginx)"),
+            PatternPreset::Combined => write!(f, "Combined Log Format (Apache/Nginx)"),
+            PatternPreset::Syslog => write!(f, "Syslog (RFC 3164)"),
+            PatternPreset::Rfc5424 => write!(f, "Syslog (RFC 5424)"),
+        }
+    }
+}
@@ -1,12 +1,20 @@
-use std::sync::{
-    Arc, LazyLock, Mutex,
-    mpsc::{Receiver, Sender, channel},
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc, LazyLock, Mutex,
+        mpsc::{Receiver, Sender, channel},
+    },
 };
 
 use polars::frame::DataFrame;
 use url::Url;
 
-use crate::{misc::remote_load::Reader, tui::pane::TableDescription};
+use crate::{
+    misc::{
+        query_job::PlannedQuery, query_plan::QueryPlan, remote_load::Reader, sql_script::SqlScript,
+    },
+    tui::pane::TableDescription,
+};
 
 #[derive(Debug)]
 pub enum Message {
@@ -18,13 +26,23 @@ pub enum Message {
     AppShowSchema,
     AppShowImporter,
     AppDismissSchema,
+    AppShowTableRenamer(String),
     AppShowSqlQuery,
+    AppShowSnippetRunner,
+    AppShowScriptRunner,
+    AppShowSqlEditor(String),
+    AppShowPlanViewer(Box<QueryPlan>),
+    AppShowExplain,
     AppDownloadDataSource(Url, Arc<dyn Reader>),
+    AppReloadTable(String),
     AppReloadConfig,
     TabsSelect(usize),
     TabsDismissSwitcher,
     TabsAddNamePane(DataFrame, String),
     TabsAddQueryPane(DataFrame, String),
+    TabsRunQuery(Box<PlannedQuery>),
+    TabsRunScript(Box<SqlScript>),
+    TabsExportXlsx(PathBuf),
     PaneEditInExternalEditor,
     PaneShowExporter,
     PaneShowFuzzySearch,
@@ -43,6 +61,10 @@ pub enum Message {
     PaneShowTableRegisterer,
     PaneShowTableInfo,
     PaneShowColumnCaster,
+    PaneShowColumnHider,
+    PaneHideColumn(String),
+    PaneUnhideColumns,
+    PaneUnmarkRows,
     Quit,
 }
 
@@ -1,24 +1,53 @@
 use std::fs::File;
 
-use polars::{io::SerReader, prelude::IpcReader};
+use polars::{
+    frame::DataFrame,
+    io::{SerReader, mmap::MmapBytesReader},
+    prelude::IpcReader,
+};
 
 use crate::{
     AppResult,
-    io::reader::{DataFrameReader, NamedFrames, ReaderSource},
+    args::Args,
+    io::reader::{DataFrameReader, NamedFrame, NamedFrames, ReadOptions, ReaderSource},
     misc::stdin::stdin,
 };
 
-#[derive(Debug)]
-pub struct ArrowIpcToDataFrame;
+#[derive(Debug, Default)]
+pub struct ArrowIpcToDataFrame {
+    read_options: ReadOptions,
+}
+
+impl ArrowIpcToDataFrame {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            read_options: ReadOptions::from_args(args),
+        }
+    }
+
+    pub fn with_read_options(mut self, read_options: ReadOptions) -> Self {
+        self.read_options = read_options;
+        self
+    }
+
+    fn try_into_frame(&self, reader: impl MmapBytesReader) -> AppResult<DataFrame> {
+        let df = IpcReader::new(reader)
+            .with_n_rows(self.read_options.rows_to_read())
+            .with_columns(self.read_options.columns().map(<[_]>::to_vec))
+            .set_rechunk(true)
+            .finish()?;
+        let df = self.read_options.slice_rows(df);
+        self.read_options
+            .replace_null_values(self.read_options.select_columns(df)?)
+    }
+}
 
 impl DataFrameReader for ArrowIpcToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
         let df = match &input {
-            ReaderSource::File(path) => IpcReader::new(File::open(path)?)
-                .set_rechunk(true)
-                .finish()?,
-            ReaderSource::Stdin => IpcReader::new(stdin()).set_rechunk(true).finish()?,
+            ReaderSource::File(path) => self.try_into_frame(File::open(path)?)?,
+            ReaderSource::Stdin => self.try_into_frame(stdin())?,
         };
-        Ok([(input.table_name(), df)].into())
+        Ok([NamedFrame::new(input.table_name(), df)].into())
     }
 }
@@ -4,7 +4,7 @@ use polars::io::{SerReader, avro::AvroReader};
 
 use crate::{
     AppResult,
-    io::reader::{DataFrameReader, NamedFrames, ReaderSource},
+    io::reader::{DataFrameReader, NamedFrame, NamedFrames, ReaderSource},
     misc::stdin::stdin,
 };
 
@@ -19,6 +19,6 @@ impl DataFrameReader for AvroToDataFrame {
                 .finish()?,
             ReaderSource::Stdin => AvroReader::new(stdin()).set_rechunk(true).finish()?,
         };
-        Ok([(input.table_name(), df)].into())
+        Ok([NamedFrame::new(input.table_name(), df)].into())
     }
 }
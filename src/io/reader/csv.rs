@@ -1,27 +1,36 @@
-use std::fs::File;
+use std::io::Cursor;
 
 use anyhow::anyhow;
 use polars::{
     frame::DataFrame,
     io::{SerReader, mmap::MmapBytesReader},
-    prelude::{CsvParseOptions, CsvReadOptions},
+    prelude::{CsvParseOptions, CsvReadOptions, NullValues, PlSmallStr},
 };
 
 use crate::{
     AppResult,
     args::{Args, InferSchema},
-    io::reader::{DataFrameReader, NamedFrames, ReaderSource},
-    misc::{stdin::stdin, type_ext::ToAscii},
+    io::reader::{
+        DataFrameReader, NamedFrame, NamedFrames, ReadOptions, ReaderSource,
+        csv_dialect::{CsvDialect, SNIFF_BYTES},
+        source::Utf8Input,
+    },
+    misc::{encoding::TextEncoding, type_ext::ToAscii},
 };
 
+/// Reads delimiter-separated files. The dialect settings left unset are sniffed from the input.
 #[derive(Debug)]
 pub struct CsvToDataFrame {
     infer_schema: InferSchema,
-    quote_char: char,
-    separator_char: char,
-    no_header: bool,
+    quote_char: Option<char>,
+    separator_char: Option<char>,
+    has_header: Option<bool>,
+    skip_lines: Option<usize>,
+    comment_prefix: Option<Option<String>>,
     ignore_errors: bool,
     truncate_ragged_lines: bool,
+    encoding: TextEncoding,
+    read_options: ReadOptions,
 }
 
 impl CsvToDataFrame {
@@ -30,38 +39,115 @@ impl CsvToDataFrame {
             infer_schema: args.infer_schema,
             quote_char: args.quote_char,
             separator_char: args.separator,
-            no_header: args.no_header,
+            has_header: if args.no_header {
+                Some(false)
+            } else {
+                args.header.then_some(true)
+            },
+            skip_lines: None,
+            comment_prefix: args.comment_prefix.clone().map(Some),
             ignore_errors: args.ignore_errors,
             truncate_ragged_lines: args.truncate_ragged_lines,
+            encoding: TextEncoding::from_args(args),
+            read_options: ReadOptions::from_args(args),
         }
     }
 
     pub fn with_separator(mut self, c: char) -> Self {
-        self.separator_char = c;
+        self.separator_char = Some(c);
         self
     }
 
     pub fn with_no_header(mut self, no_header: bool) -> Self {
-        self.no_header = no_header;
+        self.has_header = Some(!no_header);
         self
     }
 
     pub fn with_quote_char(mut self, c: char) -> Self {
-        self.quote_char = c;
+        self.quote_char = Some(c);
         self
     }
 
-    fn try_into_frame(&self, reader: impl MmapBytesReader) -> AppResult<DataFrame> {
+    pub fn with_skip_lines(mut self, skip_lines: usize) -> Self {
+        self.skip_lines = Some(skip_lines);
+        self
+    }
+
+    pub fn with_comment_prefix(mut self, comment_prefix: Option<String>) -> Self {
+        self.comment_prefix = Some(comment_prefix);
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: TextEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn with_read_options(mut self, read_options: ReadOptions) -> Self {
+        self.read_options = read_options;
+        self
+    }
+
+    /// The explicit settings, with the others sniffed from the start of the input.
+    fn dialect(&self, bytes: &[u8]) -> CsvDialect {
+        let sniffed = if self.separator_char.is_some()
+            && self.quote_char.is_some()
+            && self.has_header.is_some()
+            && self.skip_lines.is_some()
+            && self.comment_prefix.is_some()
+        {
+            CsvDialect::default()
+        } else {
+            CsvDialect::sniff(&String::from_utf8_lossy(
+                &bytes[..bytes.len().min(SNIFF_BYTES)],
+            ))
+        };
+        CsvDialect {
+            separator: self.separator_char.unwrap_or(sniffed.separator),
+            quote_char: self.quote_char.unwrap_or(sniffed.quote_char),
+            has_header: self.has_header.unwrap_or(sniffed.has_header),
+            skip_lines: self.skip_lines.unwrap_or(sniffed.skip_lines),
+            comment_prefix: self
+                .comment_prefix
+                .clone()
+                .unwrap_or(sniffed.comment_prefix),
+        }
+    }
+
+    fn try_into_frame(
+        &self,
+        reader: impl MmapBytesReader,
+        dialect: CsvDialect,
+    ) -> AppResult<DataFrame> {
         let df = CsvReadOptions::default()
             .with_ignore_errors(self.ignore_errors)
             .with_infer_schema_length(self.infer_schema.to_csv_infer_schema_length())
-            .with_has_header(!self.no_header)
+            .with_has_header(dialect.has_header)
+            .with_skip_lines(dialect.skip_lines)
+            .with_skip_rows_after_header(self.read_options.skip_rows())
+            .with_n_rows(self.read_options.n_rows())
+            .with_columns(
+                self.read_options
+                    .columns()
+                    .map(|columns| columns.iter().map(PlSmallStr::from).collect()),
+            )
             .with_parse_options(
                 CsvParseOptions::default()
                     .with_truncate_ragged_lines(self.truncate_ragged_lines)
-                    .with_quote_char(self.quote_char.to_ascii())
+                    .with_quote_char(dialect.quote_char.to_ascii())
+                    .with_comment_prefix(dialect.comment_prefix.as_deref())
+                    .with_null_values((!self.read_options.null_values().is_empty()).then(|| {
+                        NullValues::AllColumns(
+                            self.read_options
+                                .null_values()
+                                .iter()
+                                .map(PlSmallStr::from)
+                                .collect(),
+                        )
+                    }))
                     .with_separator(
-                        self.separator_char
+                        dialect
+                            .separator
                             .to_ascii()
                             .ok_or(anyhow!("non-ASCII separator character"))?,
                     ),
@@ -77,21 +163,28 @@ impl Default for CsvToDataFrame {
     fn default() -> Self {
         Self {
             infer_schema: InferSchema::Safe,
-            quote_char: '"',
-            separator_char: ',',
-            no_header: false,
+            quote_char: None,
+            separator_char: None,
+            has_header: None,
+            skip_lines: None,
+            comment_prefix: None,
             ignore_errors: true,
             truncate_ragged_lines: false,
+            encoding: Default::default(),
+            read_options: Default::default(),
         }
     }
 }
 
 impl DataFrameReader for CsvToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
-        let df = match &input {
-            ReaderSource::File(path) => self.try_into_frame(File::open(path)?),
-            ReaderSource::Stdin => self.try_into_frame(stdin()),
-        }?;
-        Ok([(input.table_name(), df)].into())
+        let utf8 = input.open_utf8(self.encoding)?;
+        let dialect = self.dialect(utf8.head());
+        let df = match utf8 {
+            Utf8Input::File(file, _) => self.try_into_frame(file, dialect)?,
+            Utf8Input::Bytes(bytes) => self.try_into_frame(Cursor::new(bytes), dialect)?,
+        };
+        let df = self.read_options.select_columns(df)?;
+        Ok([NamedFrame::new(input.table_name(), df)].into())
     }
 }
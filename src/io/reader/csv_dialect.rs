@@ -0,0 +1,289 @@
+use std::{collections::HashMap, fs::File, io::Read};
+
+use crate::{
+    AppResult,
+    misc::{encoding::TextEncoding, stdin::stdin},
+};
+
+use super::ReaderSource;
+
+/// Bytes inspected at the start of the input.
+pub const SNIFF_BYTES: usize = 1 << 16;
+const SNIFF_LINES: usize = 200;
+const SEPARATORS: [char; 4] = [',', ';', '\t', '|'];
+const COMMENT_PREFIXES: [&str; 2] = ["#", "//"];
+
+/// Layout of a delimiter-separated file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvDialect {
+    pub separator: char,
+    pub quote_char: char,
+    pub has_header: bool,
+    /// Lines before the table, such as titles or export notes.
+    pub skip_lines: usize,
+    pub comment_prefix: Option<String>,
+}
+
+impl CsvDialect {
+    /// Guesses the dialect from the start of the source.
+    pub fn sniff_source(source: &ReaderSource, encoding: TextEncoding) -> AppResult<Self> {
+        let mut bytes = Vec::new();
+        match source {
+            ReaderSource::File(path) => {
+                File::open(path)?
+                    .take(SNIFF_BYTES as u64)
+                    .read_to_end(&mut bytes)?;
+            }
+            ReaderSource::Stdin => {
+                stdin().take(SNIFF_BYTES as u64).read_to_end(&mut bytes)?;
+            }
+        }
+        Ok(Self::sniff(&String::from_utf8_lossy(
+            &encoding.to_utf8(bytes),
+        )))
+    }
+
+    /// Guesses the dialect from a sample of the first lines.
+    pub fn sniff(sample: &str) -> Self {
+        let mut lines = sample.lines().take(SNIFF_LINES + 1).collect::<Vec<_>>();
+        if lines.len() > 1 && (lines.len() > SNIFF_LINES || !sample.ends_with('\n')) {
+            // the last line may be cut off
+            lines.pop();
+        }
+
+        let Some((separator, fields)) = sniff_separator(&lines, '"') else {
+            return Self::default();
+        };
+        let quote_char = sniff_quote(&lines, separator);
+        let (separator, fields) = match quote_char {
+            '"' => (separator, fields),
+            quote_char => sniff_separator(&lines, quote_char).unwrap_or((separator, fields)),
+        };
+
+        let counts = lines
+            .iter()
+            .map(|line| split_fields(line, separator, quote_char).len())
+            .collect::<Vec<_>>();
+        let is_comment = |idx: usize| {
+            counts[idx] != fields
+                && COMMENT_PREFIXES
+                    .iter()
+                    .any(|prefix| lines[idx].starts_with(prefix))
+        };
+        let skip_lines = counts
+            .iter()
+            .position(|count| *count == fields)
+            .unwrap_or_default();
+        let comment_prefix = (skip_lines..lines.len())
+            .find(|idx| is_comment(*idx))
+            .and_then(|idx| {
+                COMMENT_PREFIXES
+                    .iter()
+                    .find(|prefix| lines[idx].starts_with(*prefix))
+            })
+            .map(|prefix| prefix.to_string());
+
+        let rows = (skip_lines..lines.len())
+            .filter(|idx| !lines[*idx].trim().is_empty() && !is_comment(*idx))
+            .map(|idx| split_fields(lines[idx], separator, quote_char))
+            .collect::<Vec<_>>();
+
+        Self {
+            separator,
+            quote_char,
+            has_header: sniff_header(&rows),
+            skip_lines,
+            comment_prefix,
+        }
+    }
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        Self {
+            separator: ',',
+            quote_char: '"',
+            has_header: true,
+            skip_lines: 0,
+            comment_prefix: None,
+        }
+    }
+}
+
+/// Single quotes when they wrap more fields than double quotes and every field opening one
+/// closes it, which rules out the leading apostrophe spreadsheets put before `'0123`.
+fn sniff_quote(lines: &[&str], separator: char) -> char {
+    let opened = |quote_char: char| {
+        lines
+            .iter()
+            .flat_map(|line| split_raw(line, separator, quote_char))
+            .filter(|field| field.starts_with(quote_char))
+            .collect::<Vec<_>>()
+    };
+    let single = opened('\'');
+    let balanced = single
+        .iter()
+        .all(|field| field.len() > 1 && field.ends_with('\''));
+    if balanced && single.len() > opened('"').len() {
+        '\''
+    } else {
+        '"'
+    }
+}
+
+/// The separator splitting the most lines into the same number of fields, along with that
+/// number. Earlier separators win ties.
+fn sniff_separator(lines: &[&str], quote_char: char) -> Option<(char, usize)> {
+    SEPARATORS
+        .into_iter()
+        .filter_map(|separator| {
+            let mut frequencies = HashMap::<usize, usize>::new();
+            for line in lines.iter().filter(|line| !line.trim().is_empty()) {
+                *frequencies
+                    .entry(split_fields(line, separator, quote_char).len())
+                    .or_default() += 1;
+            }
+            frequencies
+                .into_iter()
+                .filter(|(fields, _)| *fields > 1)
+                .max_by_key(|(fields, lines)| (*lines, *fields))
+                .map(|(fields, lines)| (separator, fields, lines))
+        })
+        .fold(None, |best, candidate| match best {
+            Some((_, _, lines)) if lines >= candidate.2 => best,
+            _ => Some(candidate),
+        })
+        .map(|(separator, fields, _)| (separator, fields))
+}
+
+/// Looks for columns whose first value does not look like the values below it, such as a
+/// name or a year on top of amounts. Columns of text give no hint, so a header is assumed.
+fn sniff_header(rows: &[Vec<&str>]) -> bool {
+    let Some((first, rest)) = rows.split_first() else {
+        return true;
+    };
+    let is_number = |value: &str| value.replace(' ', "").parse::<f64>().is_ok();
+    let mut votes = 0_i32;
+    for (idx, head) in first.iter().enumerate() {
+        let values = rest
+            .iter()
+            .filter_map(|row| row.get(idx).copied())
+            .filter(|value| !value.is_empty())
+            .collect::<Vec<_>>();
+        if head.is_empty() {
+            votes -= 1;
+        } else if !values.is_empty() && values.iter().all(|value| is_number(value)) {
+            votes += if !is_number(head) || is_number_label(head, &values) {
+                1
+            } else {
+                -1
+            };
+        }
+    }
+    votes >= 0
+}
+
+/// Whether a number on top of a column of numbers reads as a label: a whole number over
+/// decimals, or one longer than every value below, like years over counts.
+fn is_number_label(head: &str, values: &[&str]) -> bool {
+    let is_whole = |value: &str| !value.contains(['.', 'e', 'E']);
+    (is_whole(head) && !values.iter().any(|value| is_whole(value)))
+        || values.iter().all(|value| value.len() < head.len())
+}
+
+/// Splits a line on the separator, skipping separators inside quotes and removing the quotes
+/// around fields.
+fn split_fields(line: &str, separator: char, quote_char: char) -> Vec<&str> {
+    split_raw(line, separator, quote_char)
+        .into_iter()
+        .map(|field| {
+            field
+                .strip_prefix(quote_char)
+                .and_then(|field| field.strip_suffix(quote_char))
+                .unwrap_or(field)
+        })
+        .collect()
+}
+
+/// Splits a line on the separator, skipping separators inside quotes and keeping the quotes.
+fn split_raw(line: &str, separator: char, quote_char: char) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+    for (idx, c) in line.char_indices() {
+        if c == quote_char {
+            quoted = !quoted;
+        } else if c == separator && !quoted {
+            fields.push(&line[start..idx]);
+            start = idx + c.len_utf8();
+        }
+    }
+    fields.push(&line[start..]);
+    fields.into_iter().map(str::trim).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_semicolon() {
+        let dialect = CsvDialect::sniff("name;price\nfoo;1,5\nbar;2,25\n");
+        assert_eq!(dialect.separator, ';');
+        assert_eq!(dialect.quote_char, '"');
+        assert!(dialect.has_header);
+    }
+
+    #[test]
+    fn test_tab() {
+        let dialect = CsvDialect::sniff("id\tname\n1\t\"a, b\"\n2\tc\n");
+        assert_eq!(dialect.separator, '\t');
+        assert!(dialect.has_header);
+    }
+
+    #[test]
+    fn test_no_header() {
+        let dialect = CsvDialect::sniff("a,1,2.5\nb,2,3.5\nc,3,4.5\n");
+        assert_eq!(dialect.separator, ',');
+        assert!(!dialect.has_header);
+    }
+
+    #[test]
+    fn test_numeric_header() {
+        let dialect = CsvDialect::sniff("2022,2023,2024\n1.5,2.5,3.5\n4.5,5.5,6.5\n");
+        assert!(dialect.has_header);
+
+        let dialect = CsvDialect::sniff("region;2023;2024\nnorth;15;320\nsouth;7;41\n");
+        assert_eq!(dialect.separator, ';');
+        assert!(dialect.has_header);
+    }
+
+    #[test]
+    fn test_leading_lines() {
+        let dialect = CsvDialect::sniff(
+            "Sales report\nExported 2024-01-01\n\nid,name,amount\n1,a,10\n2,b,20\n",
+        );
+        assert_eq!(dialect.separator, ',');
+        assert_eq!(dialect.skip_lines, 3);
+        assert!(dialect.has_header);
+    }
+
+    #[test]
+    fn test_comment_lines() {
+        let dialect = CsvDialect::sniff("id|name\n# first batch\n1|a\n2|b\n# second batch\n3|c\n");
+        assert_eq!(dialect.separator, '|');
+        assert_eq!(dialect.comment_prefix.as_deref(), Some("#"));
+        assert_eq!(dialect.skip_lines, 0);
+    }
+
+    #[test]
+    fn test_single_quotes() {
+        let dialect = CsvDialect::sniff("id,name\n1,'a, b'\n2,'c'\n3,'d, e'\n");
+        assert_eq!(dialect.quote_char, '\'');
+        assert_eq!(dialect.separator, ',');
+
+        let dialect = CsvDialect::sniff("code,name\n'0123,a\n'0456,b\n'0789,c\n");
+        assert_eq!(dialect.quote_char, '"');
+        assert_eq!(dialect.separator, ',');
+    }
+}
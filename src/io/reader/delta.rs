@@ -0,0 +1,433 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail};
+use indexmap::IndexMap;
+use polars::{
+    io::SerReader,
+    prelude::{DataType, ParquetReader, TimeUnit, TimeZone},
+};
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    AppResult,
+    args::Args,
+    io::reader::{
+        DataFrameReader, NamedFrame, NamedFrames, ReaderSource,
+        lakehouse::{DataFile, FileColumn, SchemaField, decimal_type, read_data_files},
+    },
+    misc::metadata::TableMetadata,
+};
+
+#[derive(Debug, Default)]
+pub struct DeltaToDataFrame {
+    version: Option<i64>,
+}
+
+impl DeltaToDataFrame {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            version: args.table_version,
+        }
+    }
+
+    pub fn with_version(mut self, version: Option<i64>) -> Self {
+        self.version = version;
+        self
+    }
+}
+
+impl DataFrameReader for DeltaToDataFrame {
+    fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
+        let ReaderSource::File(path) = &input else {
+            return Err(anyhow!("Delta tables can only be read from a directory"));
+        };
+        let log = DeltaLog::new(path)?;
+        let version = match self.version {
+            Some(version) => u64::try_from(version)
+                .map_err(|_| anyhow!("Invalid Delta table version {version}"))?,
+            None => log.latest_version()?,
+        };
+        let snapshot = log.snapshot(version)?;
+        let df = read_data_files(snapshot.files(path), &snapshot.schema()?)?;
+
+        let metadata = TableMetadata::default().with_version(format!("Delta version {version}"));
+        Ok([NamedFrame::new(input.table_name(), df).with_metadata(metadata)].into())
+    }
+}
+
+/// Field metadata holding the name of a column in the data files under column mapping.
+const PHYSICAL_NAME_KEY: &str = "delta.columnMapping.physicalName";
+
+/// The table feature that marks rows of data files as deleted without rewriting them.
+const DELETION_VECTORS: &str = "deletionVectors";
+
+#[derive(Debug, Default)]
+struct DeltaLog {
+    dir: PathBuf,
+    commits: BTreeMap<u64, PathBuf>,
+    checkpoints: BTreeMap<u64, Vec<PathBuf>>,
+}
+
+impl DeltaLog {
+    fn new(table: &Path) -> AppResult<Self> {
+        let dir = table.join("_delta_log");
+        if !dir.is_dir() {
+            return Err(anyhow!("{} is not a Delta table", table.display()));
+        }
+        let mut log = DeltaLog {
+            dir,
+            ..Default::default()
+        };
+        let mut parts = BTreeMap::<u64, (usize, Vec<PathBuf>)>::new();
+        for entry in std::fs::read_dir(&log.dir)? {
+            let path = entry?.path();
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            // <version>.json, <version>.checkpoint.parquet or
+            // <version>.checkpoint.<part>.<parts>.parquet
+            match file_name.split('.').collect::<Vec<_>>().as_slice() {
+                [version, "json"] => {
+                    if let Ok(version) = version.parse() {
+                        log.commits.insert(version, path);
+                    }
+                }
+                [version, "checkpoint", "parquet"] => {
+                    if let Ok(version) = version.parse() {
+                        parts.insert(version, (1, vec![path]));
+                    }
+                }
+                [version, "checkpoint", _, total, "parquet"] => {
+                    if let (Ok(version), Ok(total)) = (version.parse(), total.parse()) {
+                        let entry = parts.entry(version).or_insert((total, Vec::new()));
+                        entry.1.push(path);
+                    }
+                }
+                _ => (),
+            }
+        }
+        log.checkpoints = parts
+            .into_iter()
+            .filter(|(_, (total, paths))| *total == paths.len())
+            .map(|(version, (_, mut paths))| {
+                paths.sort();
+                (version, paths)
+            })
+            .collect();
+        Ok(log)
+    }
+
+    fn latest_version(&self) -> AppResult<u64> {
+        self.commits
+            .keys()
+            .chain(self.checkpoints.keys())
+            .max()
+            .copied()
+            .ok_or(anyhow!("No commits found in {}", self.dir.display()))
+    }
+
+    fn snapshot(&self, version: u64) -> AppResult<Snapshot> {
+        let mut snapshot = Snapshot::default();
+        let start = match self.checkpoints.range(..=version).next_back() {
+            Some((checkpoint, paths)) => {
+                for path in paths {
+                    snapshot.apply_checkpoint(path)?;
+                }
+                checkpoint + 1
+            }
+            None => 0,
+        };
+        for commit in start..=version {
+            let path = self.commits.get(&commit).ok_or(anyhow!(
+                "Version {version} of the Delta table is not available"
+            ))?;
+            for line in std::fs::read_to_string(path)?.lines() {
+                if !line.trim().is_empty() {
+                    snapshot.apply_action(serde_json::from_str(line)?)?;
+                }
+            }
+        }
+        Ok(snapshot)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Snapshot {
+    schema_string: Option<String>,
+    files: IndexMap<String, Vec<(String, Option<String>)>>,
+}
+
+impl Snapshot {
+    fn apply_action(&mut self, action: Value) -> AppResult<()> {
+        if let Some(add) = action.get("add") {
+            if add.get("deletionVector").is_some_and(|dv| !dv.is_null()) {
+                bail!("Delta tables with deletion vectors are not supported");
+            }
+            if let Some(path) = add.get("path").and_then(Value::as_str) {
+                let partition_values = add
+                    .get("partitionValues")
+                    .and_then(Value::as_object)
+                    .map(|values| {
+                        values
+                            .iter()
+                            .map(|(key, value)| (key.to_owned(), value.as_str().map(str::to_owned)))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                self.files.insert(path.to_owned(), partition_values);
+            }
+        } else if let Some(remove) = action.get("remove") {
+            if let Some(path) = remove.get("path").and_then(Value::as_str) {
+                self.files.shift_remove(path);
+            }
+        } else if let Some(protocol) = action.get("protocol") {
+            if protocol
+                .get("readerFeatures")
+                .and_then(Value::as_array)
+                .is_some_and(|features| features.iter().any(|feature| feature == DELETION_VECTORS))
+            {
+                bail!("Delta tables with deletion vectors are not supported");
+            }
+        } else if let Some(schema_string) = action
+            .get("metaData")
+            .and_then(|meta_data| meta_data.get("schemaString"))
+            .and_then(Value::as_str)
+        {
+            self.schema_string = Some(schema_string.to_owned());
+        }
+        Ok(())
+    }
+
+    fn apply_checkpoint(&mut self, path: &Path) -> AppResult<()> {
+        let df = ParquetReader::new(File::open(path)?).finish()?;
+        if let Ok(column) = df.column("protocol")
+            && let Ok(features) = column.struct_()?.field_by_name("readerFeatures")
+            && features.list()?.amortized_iter().flatten().any(|features| {
+                features
+                    .as_ref()
+                    .str()
+                    .is_ok_and(|features| features.iter().any(|f| f == Some(DELETION_VECTORS)))
+            })
+        {
+            bail!("Delta tables with deletion vectors are not supported");
+        }
+        if let Ok(column) = df.column("metaData") {
+            let schema_strings = column.struct_()?.field_by_name("schemaString")?;
+            if let Some(schema_string) = schema_strings.str()?.iter().flatten().next() {
+                self.schema_string = Some(schema_string.to_owned());
+            }
+        }
+        if let Ok(column) = df.column("add") {
+            let adds = column.struct_()?;
+            let paths = adds.field_by_name("path")?;
+            if let Ok(deletion_vectors) = adds.field_by_name("deletionVector")
+                && (paths.is_not_null() & deletion_vectors.is_not_null()).any()
+            {
+                bail!("Delta tables with deletion vectors are not supported");
+            }
+            let partition_values = adds.field_by_name("partitionValues")?;
+            for (path, values) in paths
+                .str()?
+                .iter()
+                .zip(partition_values.list()?.amortized_iter())
+            {
+                let Some(path) = path else {
+                    continue;
+                };
+                let values = match values {
+                    Some(values) => {
+                        let entries = values.as_ref().struct_()?;
+                        let keys = entries.field_by_name("key")?;
+                        let values = entries.field_by_name("value")?;
+                        keys.str()?
+                            .iter()
+                            .zip(values.str()?.iter())
+                            .filter_map(|(key, value)| {
+                                Some((key?.to_owned(), value.map(str::to_owned)))
+                            })
+                            .collect()
+                    }
+                    None => Vec::new(),
+                };
+                self.files.insert(path.to_owned(), values);
+            }
+        }
+        Ok(())
+    }
+
+    fn schema(&self) -> AppResult<Vec<SchemaField>> {
+        let schema_string = self
+            .schema_string
+            .as_deref()
+            .ok_or(anyhow!("The Delta table has no schema"))?;
+        let schema = serde_json::from_str::<Value>(schema_string)?;
+        Ok(schema
+            .get("fields")
+            .and_then(Value::as_array)
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter_map(|field| {
+                        let name = field.get("name")?.as_str()?.to_owned();
+                        let dtype = field.get("type")?.as_str().and_then(primitive_type);
+                        // with column mapping, files name their columns by the physical name
+                        let physical_name = field
+                            .get("metadata")
+                            .and_then(|metadata| metadata.get(PHYSICAL_NAME_KEY))
+                            .and_then(Value::as_str)
+                            .unwrap_or(&name)
+                            .to_owned();
+                        Some(SchemaField {
+                            name,
+                            dtype,
+                            column: FileColumn::Name(physical_name),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn files(&self, table: &Path) -> Vec<DataFile> {
+        self.files
+            .iter()
+            .map(|(path, partition_values)| DataFile {
+                path: resolve_path(table, path),
+                partition_values: partition_values.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Paths in the log are either absolute URIs or URI-encoded paths relative to the table.
+fn resolve_path(table: &Path, path: &str) -> PathBuf {
+    if let Ok(url) = Url::parse(path)
+        && let Ok(path) = url.to_file_path()
+    {
+        return path;
+    }
+    let decoded = percent_encoding::percent_decode_str(path).decode_utf8_lossy();
+    table.join(decoded.as_ref())
+}
+
+fn primitive_type(name: &str) -> Option<DataType> {
+    match name {
+        "string" => Some(DataType::String),
+        "long" => Some(DataType::Int64),
+        "integer" => Some(DataType::Int32),
+        "short" => Some(DataType::Int16),
+        "byte" => Some(DataType::Int8),
+        "float" => Some(DataType::Float32),
+        "double" => Some(DataType::Float64),
+        "boolean" => Some(DataType::Boolean),
+        "binary" => Some(DataType::Binary),
+        "date" => Some(DataType::Date),
+        "timestamp" => Some(DataType::Datetime(
+            TimeUnit::Microseconds,
+            Some(TimeZone::UTC),
+        )),
+        "timestamp_ntz" => Some(DataType::Datetime(TimeUnit::Microseconds, None)),
+        _ => decimal_type(name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_deletion_vectors() {
+        let mut snapshot = Snapshot::default();
+        let add = |deletion_vector: &str| {
+            serde_json::from_str::<Value>(&format!(
+                r#"{{"add": {{"path": "a.parquet", "partitionValues": {{}}, "deletionVector": {deletion_vector}}}}}"#
+            ))
+            .unwrap()
+        };
+        assert!(snapshot.apply_action(add("null")).is_ok());
+        assert_eq!(snapshot.files.len(), 1);
+        assert!(
+            snapshot
+                .apply_action(add(r#"{"storageType": "u", "pathOrInlineDv": "ab", "offset": 1, "sizeInBytes": 36, "cardinality": 2}"#))
+                .is_err()
+        );
+
+        let protocol = serde_json::json!({
+            "protocol": {
+                "minReaderVersion": 3,
+                "minWriterVersion": 7,
+                "readerFeatures": ["deletionVectors"],
+                "writerFeatures": ["deletionVectors"]
+            }
+        });
+        assert!(snapshot.apply_action(protocol).is_err());
+    }
+
+    #[test]
+    fn test_column_mapping() {
+        let table = tempfile::tempdir().unwrap();
+        std::fs::create_dir(table.path().join("_delta_log")).unwrap();
+        let mut df = polars::df!(
+            "col-5f2a" => [1_i64, 2],
+            "col-9c1e" => ["x", "y"]
+        )
+        .unwrap();
+        polars::prelude::ParquetWriter::new(
+            File::create(table.path().join("part-0.parquet")).unwrap(),
+        )
+        .finish(&mut df)
+        .unwrap();
+        let field = |name: &str, dtype: &str, id: i64, physical_name: &str| {
+            serde_json::json!({
+                "name": name,
+                "type": dtype,
+                "nullable": true,
+                "metadata": {
+                    "delta.columnMapping.id": id,
+                    "delta.columnMapping.physicalName": physical_name
+                }
+            })
+        };
+        let schema = serde_json::json!({
+            "type": "struct",
+            "fields": [field("id", "long", 1, "col-5f2a"), field("label", "string", 2, "col-9c1e")]
+        });
+        let commit = [
+            serde_json::json!({"protocol": {"minReaderVersion": 2, "minWriterVersion": 5}}),
+            serde_json::json!({"metaData": {
+                "id": "table",
+                "format": {"provider": "parquet", "options": {}},
+                "schemaString": schema.to_string(),
+                "partitionColumns": [],
+                "configuration": {"delta.columnMapping.mode": "name"}
+            }}),
+            serde_json::json!({"add": {
+                "path": "part-0.parquet",
+                "partitionValues": {},
+                "size": 1,
+                "modificationTime": 0,
+                "dataChange": true
+            }}),
+        ]
+        .map(|action| action.to_string())
+        .join("\n");
+        std::fs::write(
+            table.path().join("_delta_log/00000000000000000000.json"),
+            commit,
+        )
+        .unwrap();
+
+        let frames = DeltaToDataFrame::default()
+            .read_to_data_frames(ReaderSource::File(table.path().to_owned()))
+            .unwrap();
+        assert_eq!(
+            frames[0].data_frame(),
+            &polars::df!("id" => [1_i64, 2], "label" => ["x", "y"]).unwrap()
+        );
+    }
+}
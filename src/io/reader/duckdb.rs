@@ -0,0 +1,111 @@
+use std::{
+    io::{Cursor, Read},
+    path::Path,
+};
+
+use arrow_ipc::writer::StreamWriter;
+use duckdb::{AccessMode, Config, Connection};
+use polars::{frame::DataFrame, io::SerReader, prelude::IpcStreamReader};
+use tempfile::NamedTempFile;
+
+use crate::{AppResult, args::Args, io::reader::ReaderSource, misc::stdin::stdin};
+
+use super::{DataFrameReader, NamedFrame, NamedFrames};
+
+#[derive(Debug, Default)]
+pub struct DuckDbToDataFrames {
+    query: Option<String>,
+}
+
+impl DuckDbToDataFrames {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            query: args.duckdb_query.clone(),
+        }
+    }
+
+    pub fn query(self, query: String) -> Self {
+        DuckDbToDataFrames { query: Some(query) }
+    }
+}
+
+impl DataFrameReader for DuckDbToDataFrames {
+    fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
+        let name = input.table_name();
+        match input {
+            ReaderSource::File(path) => path_to_named_frames(path, name, self.query.as_deref()),
+            ReaderSource::Stdin => {
+                let temp_file = NamedTempFile::new()?;
+                let mut buf = Vec::new();
+                stdin().read_to_end(&mut buf)?;
+                std::fs::write(temp_file.path(), buf)?;
+                path_to_named_frames(temp_file.path(), name, self.query.as_deref())
+            }
+        }
+    }
+}
+
+fn path_to_named_frames(
+    path: impl AsRef<Path>,
+    name: String,
+    query: Option<&str>,
+) -> AppResult<NamedFrames> {
+    let conn =
+        Connection::open_with_flags(path, Config::default().access_mode(AccessMode::ReadOnly)?)?;
+
+    if let Some(query) = query {
+        return Ok([NamedFrame::new(name, query_data_frame(&conn, query)?)].into());
+    }
+
+    // Fetch tables and views of every user schema
+    let tables = conn
+        .prepare(
+            "SELECT table_schema, table_name FROM information_schema.tables \
+             WHERE table_schema NOT IN ('information_schema', 'pg_catalog') \
+             ORDER BY table_schema, table_name;",
+        )?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<(String, String)>, _>>()?;
+
+    tables
+        .into_iter()
+        .map(|(schema, table)| {
+            let df = query_data_frame(
+                &conn,
+                &format!(
+                    "SELECT * FROM {}.{}",
+                    quote_identifier(&schema),
+                    quote_identifier(&table)
+                ),
+            )?;
+            let name = if schema == "main" {
+                table
+            } else {
+                format!("{schema}.{table}")
+            };
+            Ok(NamedFrame::new(name, df))
+        })
+        .collect::<AppResult<Vec<_>>>()
+        .map(|vec| vec.into_boxed_slice())
+}
+
+fn query_data_frame(conn: &Connection, query: &str) -> AppResult<DataFrame> {
+    // Round-trip through Arrow IPC to keep DuckDB's native (nested) types
+    let mut stmt = conn.prepare(query)?;
+    let batches = stmt.query_arrow([])?;
+    let mut buf = Vec::new();
+    let mut writer = StreamWriter::try_new(&mut buf, &batches.get_schema())?;
+    for batch in batches {
+        writer.write(&batch)?;
+    }
+    writer.finish()?;
+    drop(writer);
+
+    Ok(IpcStreamReader::new(Cursor::new(buf))
+        .set_rechunk(true)
+        .finish()?)
+}
+
+fn quote_identifier(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
@@ -9,7 +9,7 @@ use polars::{
 
 use crate::{AppResult, args::Args, io::reader::ReaderSource, misc::stdin::stdin};
 
-use super::{DataFrameReader, NamedFrames};
+use super::{DataFrameReader, NamedFrame, NamedFrames};
 
 #[derive(Debug, Default)]
 pub struct ExcelToDataFrames;
@@ -29,7 +29,7 @@ impl DataFrameReader for ExcelToDataFrames {
                     .into_iter()
                     .map(|(name, sheet)| {
                         let df = sheet_to_data_frame(sheet)?;
-                        Ok((name, df))
+                        Ok(NamedFrame::new(name, df))
                     })
                     .collect::<AppResult<Vec<_>>>()?
                     .into_boxed_slice()
@@ -39,7 +39,7 @@ impl DataFrameReader for ExcelToDataFrames {
                 .into_iter()
                 .map(|(name, sheet)| {
                     let df = sheet_to_data_frame(sheet)?;
-                    Ok((name, df))
+                    Ok(NamedFrame::new(name, df))
                 })
                 .collect::<AppResult<Vec<_>>>()?
                 .into_boxed_slice(),
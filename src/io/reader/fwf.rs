@@ -1,9 +1,4 @@
-use std::{
-    collections::HashSet,
-    fs::read_to_string,
-    io::{Cursor, Read},
-    iter::once,
-};
+use std::{collections::HashSet, io::Cursor, iter::once};
 
 use fwf_rs::Reader;
 use itertools::Itertools;
@@ -13,10 +8,12 @@ use crate::{
     AppResult,
     args::Args,
     io::reader::ReaderSource,
-    misc::{iter_ext::ZipItersExt, stdin::stdin, table_name_generator::TableNameGeneratorExt},
+    misc::{
+        encoding::TextEncoding, iter_ext::ZipItersExt, table_name_generator::TableNameGeneratorExt,
+    },
 };
 
-use super::{DataFrameReader, NamedFrames};
+use super::{DataFrameReader, NamedFrame, NamedFrames};
 
 #[derive(Debug)]
 pub struct FwfToDataFrame {
@@ -24,6 +21,7 @@ pub struct FwfToDataFrame {
     has_header: bool,
     separator_length: usize,
     flexible_width: bool,
+    encoding: TextEncoding,
 }
 
 impl FwfToDataFrame {
@@ -33,6 +31,7 @@ impl FwfToDataFrame {
             has_header: !args.no_header,
             separator_length: args.separator_length,
             flexible_width: !args.no_flexible_width,
+            encoding: TextEncoding::from_args(args),
         }
     }
 
@@ -55,6 +54,11 @@ impl FwfToDataFrame {
         self.flexible_width = flexible_width;
         self
     }
+
+    pub fn with_encoding(mut self, encoding: TextEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
 }
 
 impl Default for FwfToDataFrame {
@@ -64,20 +68,14 @@ impl Default for FwfToDataFrame {
             has_header: true,
             separator_length: 0,
             flexible_width: true,
+            encoding: Default::default(),
         }
     }
 }
 
 impl DataFrameReader for FwfToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
-        let file_content = match &input {
-            ReaderSource::File(path) => read_to_string(path)?,
-            ReaderSource::Stdin => {
-                let mut buf = String::new();
-                stdin().read_to_string(&mut buf)?;
-                buf
-            }
-        };
+        let file_content = input.read_to_string(self.encoding)?;
 
         let widths = if self.widths.is_empty() {
             let common_space_indices = file_content
@@ -147,7 +145,7 @@ impl DataFrameReader for FwfToDataFrame {
                 .collect(),
         )?;
 
-        Ok([(input.table_name(), df)].into())
+        Ok([NamedFrame::new(input.table_name(), df)].into())
     }
 }
 
@@ -1,5 +1,3 @@
-use std::io::Read;
-
 use polars::{
     frame::DataFrame,
     prelude::{AnyValue, Column},
@@ -9,29 +7,31 @@ use scraper::{ElementRef, Html, Selector};
 use crate::{
     AppResult,
     args::Args,
-    io::reader::{DataFrameReader, NamedFrames, ReaderSource},
-    misc::stdin::stdin,
+    io::reader::{DataFrameReader, NamedFrame, NamedFrames, ReaderSource},
+    misc::encoding::TextEncoding,
 };
 
 #[derive(Debug, Default)]
-pub struct HtmlToDataFrame;
+pub struct HtmlToDataFrame {
+    encoding: TextEncoding,
+}
 
 impl HtmlToDataFrame {
-    pub fn from_args(_args: &Args) -> Self {
-        Self
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            encoding: TextEncoding::from_args(args),
+        }
+    }
+
+    pub fn with_encoding(mut self, encoding: TextEncoding) -> Self {
+        self.encoding = encoding;
+        self
     }
 }
 
 impl DataFrameReader for HtmlToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
-        let contents = match &input {
-            ReaderSource::File(path) => std::fs::read_to_string(path)?,
-            ReaderSource::Stdin => {
-                let mut s = String::new();
-                stdin().read_to_string(&mut s)?;
-                s
-            }
-        };
+        let contents = input.read_to_string(self.encoding)?;
 
         let document = Html::parse_document(&contents);
         let table_selector = Selector::parse("table").unwrap();
@@ -41,7 +41,7 @@ impl DataFrameReader for HtmlToDataFrame {
             .enumerate()
             .map(|(idx, table)| {
                 let df = table_to_data_frame(table)?;
-                Ok((format!("html_table_{}", idx + 1), df))
+                Ok(NamedFrame::new(format!("html_table_{}", idx + 1), df))
             })
             .collect::<AppResult<Vec<_>>>()
             .map(Vec::into_boxed_slice)
@@ -0,0 +1,232 @@
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+use polars::{
+    io::{SerReader, avro::AvroReader},
+    prelude::{DataType, TimeUnit, TimeZone},
+};
+use serde_json::Value;
+
+use crate::{
+    AppResult,
+    args::Args,
+    io::reader::{
+        DataFrameReader, NamedFrame, NamedFrames, ReaderSource,
+        lakehouse::{DataFile, FileColumn, SchemaField, decimal_type, read_data_files},
+    },
+    misc::metadata::TableMetadata,
+};
+
+#[derive(Debug, Default)]
+pub struct IcebergToDataFrame {
+    snapshot_id: Option<i64>,
+}
+
+impl IcebergToDataFrame {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            snapshot_id: args.table_version,
+        }
+    }
+
+    pub fn with_snapshot_id(mut self, snapshot_id: Option<i64>) -> Self {
+        self.snapshot_id = snapshot_id;
+        self
+    }
+}
+
+impl DataFrameReader for IcebergToDataFrame {
+    fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
+        let ReaderSource::File(path) = &input else {
+            return Err(anyhow!("Iceberg tables can only be read from a directory"));
+        };
+        let table = serde_json::from_reader::<_, Value>(File::open(metadata_file(path)?)?)?;
+        let location = table
+            .get("location")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let snapshot_id = self
+            .snapshot_id
+            .or(table.get("current-snapshot-id").and_then(Value::as_i64))
+            .filter(|id| *id != -1);
+        let snapshot = snapshot_id
+            .map(|id| {
+                table
+                    .get("snapshots")
+                    .and_then(Value::as_array)
+                    .and_then(|snapshots| {
+                        snapshots
+                            .iter()
+                            .find(|snapshot| snapshot.get("snapshot-id") == Some(&id.into()))
+                    })
+                    .ok_or(anyhow!(
+                        "Snapshot {id} of the Iceberg table is not available"
+                    ))
+            })
+            .transpose()?;
+
+        let schema = schema(&table, snapshot)?;
+        let files = match snapshot.and_then(|snapshot| snapshot.get("manifest-list")) {
+            Some(Value::String(manifest_list)) => {
+                data_files(&resolve_path(path, location, manifest_list), path, location)?
+            }
+            _ => Vec::new(),
+        };
+        let df = read_data_files(files, &schema)?;
+
+        let version = match snapshot_id {
+            Some(id) => format!("Iceberg snapshot {id}"),
+            None => "Iceberg (no snapshot)".to_owned(),
+        };
+        let metadata = TableMetadata::default().with_version(version);
+        Ok([NamedFrame::new(input.table_name(), df).with_metadata(metadata)].into())
+    }
+}
+
+/// The latest metadata file, as pointed to by `version-hint.text` or with the highest version.
+fn metadata_file(table: &Path) -> AppResult<PathBuf> {
+    let dir = table.join("metadata");
+    if !dir.is_dir() {
+        return Err(anyhow!("{} is not an Iceberg table", table.display()));
+    }
+    if let Ok(hint) = std::fs::read_to_string(dir.join("version-hint.text")) {
+        let hint = hint.trim();
+        for file_name in [format!("v{hint}.metadata.json"), hint.to_owned()] {
+            if dir.join(&file_name).is_file() {
+                return Ok(dir.join(file_name));
+            }
+        }
+    }
+    // v<version>.metadata.json or <version>-<uuid>.metadata.json
+    std::fs::read_dir(&dir)?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let file_name = path.file_name()?.to_str()?;
+            let version = file_name
+                .strip_suffix(".metadata.json")?
+                .trim_start_matches('v')
+                .split('-')
+                .next()?
+                .parse::<u64>()
+                .ok()?;
+            Some((version, path))
+        })
+        .max_by_key(|(version, _)| *version)
+        .map(|(_, path)| path)
+        .ok_or(anyhow!("No metadata file found in {}", dir.display()))
+}
+
+fn schema(table: &Value, snapshot: Option<&Value>) -> AppResult<Vec<SchemaField>> {
+    let schema_id = snapshot
+        .and_then(|snapshot| snapshot.get("schema-id"))
+        .or(table.get("current-schema-id"));
+    let schema = table
+        .get("schemas")
+        .and_then(Value::as_array)
+        .and_then(|schemas| {
+            schemas
+                .iter()
+                .find(|schema| schema_id.is_none() || schema.get("schema-id") == schema_id)
+        })
+        .or(table.get("schema"))
+        .ok_or(anyhow!("The Iceberg table has no schema"))?;
+    Ok(schema
+        .get("fields")
+        .and_then(Value::as_array)
+        .map(|fields| {
+            fields
+                .iter()
+                .filter_map(|field| {
+                    let name = field.get("name")?.as_str()?.to_owned();
+                    let dtype = field.get("type")?.as_str().and_then(primitive_type);
+                    let id = field.get("id")?.as_i64()?;
+                    Some(SchemaField {
+                        name,
+                        dtype,
+                        column: FileColumn::Id(id),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Data files of the snapshot, read from its manifest list and manifests.
+fn data_files(manifest_list: &Path, table: &Path, location: &str) -> AppResult<Vec<DataFile>> {
+    let manifests = AvroReader::new(File::open(manifest_list)?).finish()?;
+    if let Ok(content) = manifests.column("content")
+        && content.i32()?.iter().any(|content| content == Some(1))
+    {
+        return Err(anyhow!(
+            "Iceberg tables with delete files are not supported"
+        ));
+    }
+    let mut files = Vec::new();
+    for manifest in manifests.column("manifest_path")?.str()?.iter().flatten() {
+        let entries =
+            AvroReader::new(File::open(resolve_path(table, location, manifest))?).finish()?;
+        let data_file = entries.column("data_file")?.struct_()?;
+        let paths = data_file.field_by_name("file_path")?;
+        let formats = data_file.field_by_name("file_format")?;
+        // Status 2 marks a file deleted in this snapshot
+        for ((status, path), format) in entries
+            .column("status")?
+            .i32()?
+            .iter()
+            .zip(paths.str()?.iter())
+            .zip(formats.str()?.iter())
+        {
+            let (Some(path), false) = (path, status == Some(2)) else {
+                continue;
+            };
+            if !format.is_some_and(|format| format.eq_ignore_ascii_case("parquet")) {
+                return Err(anyhow!("Only Parquet data files are supported: {path}"));
+            }
+            files.push(DataFile {
+                path: resolve_path(table, location, path),
+                partition_values: Vec::new(),
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// Paths in the metadata are absolute URIs under the table location, which may differ from
+/// where the table is now, so they are made relative to the table directory if possible.
+fn resolve_path(table: &Path, location: &str, path: &str) -> PathBuf {
+    let strip_scheme = |path: &'_ str| -> String {
+        path.strip_prefix("file://")
+            .or(path.strip_prefix("file:"))
+            .unwrap_or(path)
+            .to_owned()
+    };
+    let location = strip_scheme(location);
+    let path = strip_scheme(path);
+    match path.strip_prefix(location.trim_end_matches('/')) {
+        Some(relative) if !location.is_empty() => table.join(relative.trim_start_matches('/')),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn primitive_type(name: &str) -> Option<DataType> {
+    match name {
+        "boolean" => Some(DataType::Boolean),
+        "int" => Some(DataType::Int32),
+        "long" => Some(DataType::Int64),
+        "float" => Some(DataType::Float32),
+        "double" => Some(DataType::Float64),
+        "date" => Some(DataType::Date),
+        "time" => Some(DataType::Time),
+        "timestamp" => Some(DataType::Datetime(TimeUnit::Microseconds, None)),
+        "timestamptz" => Some(DataType::Datetime(
+            TimeUnit::Microseconds,
+            Some(TimeZone::UTC),
+        )),
+        "string" => Some(DataType::String),
+        "binary" => Some(DataType::Binary),
+        _ => decimal_type(name),
+    }
+}
@@ -1,4 +1,4 @@
-use std::fs::File;
+use std::io::Cursor;
 
 use polars::{io::SerReader, prelude::JsonReader};
 
@@ -7,46 +7,47 @@ use crate::{
     args::Args,
     io::{
         reader::ReaderSource,
-        reader::{DataFrameReader, NamedFrames},
+        reader::{DataFrameReader, NamedFrame, NamedFrames},
     },
-    misc::stdin::stdin,
+    misc::encoding::TextEncoding,
 };
 
 #[derive(Debug)]
 pub struct JsonToDataFrame {
     ignore_errors: bool,
+    encoding: TextEncoding,
 }
 
 impl JsonToDataFrame {
     pub fn from_args(args: &Args) -> Self {
         Self {
             ignore_errors: args.ignore_errors,
+            encoding: TextEncoding::from_args(args),
         }
     }
+
+    pub fn with_encoding(mut self, encoding: TextEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
 }
 
 impl Default for JsonToDataFrame {
     fn default() -> Self {
         Self {
             ignore_errors: true,
+            encoding: Default::default(),
         }
     }
 }
 
 impl DataFrameReader for JsonToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
-        let df = match &input {
-            ReaderSource::File(path) => JsonReader::new(File::open(path)?)
-                .set_rechunk(true)
-                .infer_schema_len(None)
-                .with_ignore_errors(self.ignore_errors)
-                .finish()?,
-            ReaderSource::Stdin => JsonReader::new(stdin())
-                .set_rechunk(true)
-                .infer_schema_len(None)
-                .with_ignore_errors(self.ignore_errors)
-                .finish()?,
-        };
-        Ok([(input.table_name(), df)].into())
+        let df = JsonReader::new(Cursor::new(input.read_to_utf8(self.encoding)?))
+            .set_rechunk(true)
+            .infer_schema_len(None)
+            .with_ignore_errors(self.ignore_errors)
+            .finish()?;
+        Ok([NamedFrame::new(input.table_name(), df)].into())
     }
 }
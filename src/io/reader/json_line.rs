@@ -1,8 +1,10 @@
-use std::fs::File;
+use std::io::Cursor;
 
+use itertools::Itertools;
 use polars::{
-    io::SerReader,
-    prelude::{JsonFormat, JsonReader},
+    frame::DataFrame,
+    io::{SerReader, mmap::MmapBytesReader},
+    prelude::{JsonFormat, JsonReader, PlSmallStr},
 };
 
 use crate::{
@@ -10,48 +12,130 @@ use crate::{
     args::Args,
     io::{
         reader::ReaderSource,
-        reader::{DataFrameReader, NamedFrames},
+        reader::{DataFrameReader, NamedFrame, NamedFrames, ReadOptions, source::Utf8Input},
     },
-    misc::stdin::stdin,
+    misc::encoding::TextEncoding,
 };
 
 #[derive(Debug)]
 pub struct JsonLineToDataFrame {
     ignore_errors: bool,
+    encoding: TextEncoding,
+    comment_prefix: Option<String>,
+    read_options: ReadOptions,
 }
 
 impl JsonLineToDataFrame {
     pub fn from_args(args: &Args) -> Self {
         Self {
             ignore_errors: args.ignore_errors,
+            encoding: TextEncoding::from_args(args),
+            comment_prefix: args.comment_prefix.clone(),
+            read_options: ReadOptions::from_args(args),
         }
     }
+
+    pub fn with_encoding(mut self, encoding: TextEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn with_comment_prefix(mut self, comment_prefix: Option<String>) -> Self {
+        self.comment_prefix = comment_prefix;
+        self
+    }
+
+    pub fn with_read_options(mut self, read_options: ReadOptions) -> Self {
+        self.read_options = read_options;
+        self
+    }
+
+    fn filters_lines(&self) -> bool {
+        self.comment_prefix.is_some()
+            || self.read_options.skip_rows() > 0
+            || self.read_options.n_rows().is_some()
+    }
+
+    /// Drops the comment lines and the rows outside the read options before parsing.
+    fn filter_lines(&self, bytes: Vec<u8>) -> AppResult<Vec<u8>> {
+        if !self.filters_lines() {
+            return Ok(bytes);
+        }
+        Ok(String::from_utf8(bytes)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter(|line| {
+                self.comment_prefix
+                    .as_deref()
+                    .is_none_or(|prefix| !line.starts_with(prefix))
+            })
+            .skip(self.read_options.skip_rows())
+            .take(self.read_options.n_rows().unwrap_or(usize::MAX))
+            .join("\n")
+            .into_bytes())
+    }
+
+    fn try_into_frame(&self, reader: impl MmapBytesReader) -> AppResult<DataFrame> {
+        Ok(JsonReader::new(reader)
+            .with_json_format(JsonFormat::JsonLines)
+            .with_projection(
+                self.read_options
+                    .columns()
+                    .map(|columns| columns.iter().map(PlSmallStr::from).collect()),
+            )
+            .infer_schema_len(None)
+            .with_ignore_errors(self.ignore_errors)
+            .set_rechunk(true)
+            .finish()?)
+    }
 }
 
 impl Default for JsonLineToDataFrame {
     fn default() -> Self {
         Self {
             ignore_errors: true,
+            encoding: Default::default(),
+            comment_prefix: None,
+            read_options: Default::default(),
         }
     }
 }
 
 impl DataFrameReader for JsonLineToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
-        let df = match &input {
-            ReaderSource::File(path) => JsonReader::new(File::open(path)?)
-                .with_json_format(JsonFormat::JsonLines)
-                .infer_schema_len(None)
-                .with_ignore_errors(self.ignore_errors)
-                .set_rechunk(true)
-                .finish()?,
-            ReaderSource::Stdin => JsonReader::new(stdin())
-                .with_json_format(JsonFormat::JsonLines)
-                .infer_schema_len(None)
-                .with_ignore_errors(self.ignore_errors)
-                .set_rechunk(true)
-                .finish()?,
+        let df = match input.open_utf8(self.encoding)? {
+            Utf8Input::File(file, _) if !self.filters_lines() => self.try_into_frame(file)?,
+            utf8 => self.try_into_frame(Cursor::new(self.filter_lines(utf8.into_bytes()?)?))?,
+        };
+        let df = self
+            .read_options
+            .replace_null_values(self.read_options.select_columns(df)?)?;
+        Ok([NamedFrame::new(input.table_name(), df)].into())
+    }
+}
+
+/// Converts a list of JSON records into a data frame, letting polars infer nested maps as
+/// struct columns. Records that are not objects are wrapped into a single `value` column.
+pub(super) fn records_to_data_frame(
+    records: Vec<serde_json::Value>,
+    ignore_errors: bool,
+) -> AppResult<DataFrame> {
+    if records.is_empty() {
+        return Ok(DataFrame::empty());
+    }
+    let mut buf = Vec::new();
+    for record in records {
+        let record = match record {
+            serde_json::Value::Object(_) => record,
+            _ => serde_json::json!({ "value": record }),
         };
-        Ok([(input.table_name(), df)].into())
+        serde_json::to_writer(&mut buf, &record)?;
+        buf.push(b'\n');
     }
+    Ok(JsonReader::new(Cursor::new(buf))
+        .with_json_format(JsonFormat::JsonLines)
+        .infer_schema_len(None)
+        .with_ignore_errors(ignore_errors)
+        .set_rechunk(true)
+        .finish()?)
 }
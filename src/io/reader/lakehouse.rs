@@ -0,0 +1,219 @@
+use std::{collections::HashMap, fs::File, path::PathBuf};
+
+use anyhow::bail;
+use polars::{
+    error::PolarsResult,
+    frame::DataFrame,
+    io::SerReader,
+    prelude::{ArrowSchema, Column, DataType, ParquetReader, PlSmallStr},
+};
+
+use crate::AppResult;
+
+/// Metadata key under which Parquet field ids are exposed.
+const FIELD_ID_KEY: &str = "PARQUET:field_id";
+
+/// A Parquet file of a table snapshot, along with the values of its partition columns.
+#[derive(Debug)]
+pub(super) struct DataFile {
+    pub path: PathBuf,
+    pub partition_values: Vec<(String, Option<String>)>,
+}
+
+/// A column of the table schema. The data type is `None` for nested types, which are taken from
+/// the Parquet files as they are.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SchemaField {
+    pub name: String,
+    pub dtype: Option<DataType>,
+    pub column: FileColumn,
+}
+
+/// How a column of the table schema is found in the data files, which may name it differently
+/// than the schema does once it is renamed.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum FileColumn {
+    /// The column and partition value of that name.
+    Name(String),
+    /// The column with that Parquet field id.
+    Id(i64),
+}
+
+/// Reads the data files of a snapshot and conforms each of them to the table schema.
+pub(super) fn read_data_files(
+    files: Vec<DataFile>,
+    schema: &[SchemaField],
+) -> AppResult<DataFrame> {
+    let mut frames = files.into_iter().map(|file| read_data_file(file, schema));
+    let Some(first) = frames.next() else {
+        return Ok(DataFrame::new_infer_height(
+            schema
+                .iter()
+                .map(|field| {
+                    Column::new_empty(
+                        field.name.as_str().into(),
+                        field.dtype.as_ref().unwrap_or(&DataType::Null),
+                    )
+                })
+                .collect(),
+        )?);
+    };
+    let mut df = first?;
+    for other in frames {
+        df.vstack_mut_owned(other?)?;
+    }
+    df.rechunk_mut_par();
+    Ok(df)
+}
+
+fn read_data_file(file: DataFile, schema: &[SchemaField]) -> AppResult<DataFrame> {
+    let mut reader = ParquetReader::new(File::open(&file.path)?);
+    let ids = field_ids(&*reader.schema()?);
+    if ids.is_empty()
+        && schema
+            .iter()
+            .any(|field| matches!(field.column, FileColumn::Id(_)))
+    {
+        bail!(
+            "{} has no field ids to match its columns with the table schema",
+            file.path.display()
+        );
+    }
+    let df = reader.finish()?;
+    let height = df.height();
+    let columns = schema
+        .iter()
+        .map(|field| {
+            let name = PlSmallStr::from(field.name.as_str());
+            let found = match &field.column {
+                FileColumn::Name(file_name) => {
+                    if let Some((_, value)) = file
+                        .partition_values
+                        .iter()
+                        .find(|(key, _)| key == file_name)
+                    {
+                        Some(Column::new(name.clone(), vec![value.clone(); height]))
+                    } else {
+                        df.column(file_name).ok().cloned()
+                    }
+                }
+                FileColumn::Id(id) => ids
+                    .get(id)
+                    .and_then(|file_name| df.column(file_name).ok())
+                    .cloned(),
+            };
+            // a column missing from the file was added to the table after it was written
+            let column = match found {
+                Some(column) => column.with_name(name),
+                None => Column::full_null(
+                    name,
+                    height,
+                    field.dtype.as_ref().unwrap_or(&DataType::Null),
+                ),
+            };
+            match &field.dtype {
+                Some(dtype) if column.dtype() != dtype => column.cast(dtype),
+                _ => Ok(column),
+            }
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    Ok(DataFrame::new(height, columns)?)
+}
+
+/// Names of the top-level columns of a Parquet file by their field id.
+fn field_ids(schema: &ArrowSchema) -> HashMap<i64, PlSmallStr> {
+    schema
+        .iter_values()
+        .filter_map(|field| {
+            let id = field.metadata.as_ref()?.get(FIELD_ID_KEY)?.parse().ok()?;
+            Some((id, field.name.clone()))
+        })
+        .collect()
+}
+
+/// Parses decimal types written as `decimal(<precision>, <scale>)`.
+pub(super) fn decimal_type(name: &str) -> Option<DataType> {
+    let (precision, scale) = name
+        .strip_prefix("decimal(")?
+        .strip_suffix(')')?
+        .split_once(',')?;
+    Some(DataType::Decimal(
+        precision.trim().parse().ok()?,
+        scale.trim().parse().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::{
+        df,
+        prelude::{ArrowDataType, ArrowField, ParquetWriter},
+    };
+
+    use super::*;
+
+    fn field(name: &str, column: FileColumn) -> SchemaField {
+        SchemaField {
+            name: name.to_owned(),
+            dtype: Some(DataType::Int64),
+            column,
+        }
+    }
+
+    #[test]
+    fn test_field_ids() {
+        let field = |name: &str, id: Option<&str>| {
+            let field = ArrowField::new(name.into(), ArrowDataType::Int64, true);
+            let field = match id {
+                Some(id) => field.with_metadata([(FIELD_ID_KEY.into(), id.into())].into()),
+                None => field,
+            };
+            (name.into(), field)
+        };
+        let schema = ArrowSchema::from_iter([
+            field("a", Some("1")),
+            field("b", None),
+            field("c", Some("3")),
+        ]);
+        assert_eq!(
+            field_ids(&schema),
+            HashMap::from([(1, "a".into()), (3, "c".into())])
+        );
+    }
+
+    #[test]
+    fn test_read_data_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("part-0.parquet");
+        let mut df = df!("a" => [1_i64, 2], "b" => [3_i64, 4]).unwrap();
+        ParquetWriter::new(File::create(&path).unwrap())
+            .finish(&mut df)
+            .unwrap();
+        let file = || DataFile {
+            path: path.clone(),
+            partition_values: vec![("p".to_owned(), Some("7".to_owned()))],
+        };
+
+        let df = read_data_file(
+            file(),
+            &[
+                field("renamed", FileColumn::Name("b".to_owned())),
+                field("p", FileColumn::Name("p".to_owned())),
+                field("added", FileColumn::Name("c".to_owned())),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            df,
+            df!(
+                "renamed" => [3_i64, 4],
+                "p" => [7_i64, 7],
+                "added" => [None::<i64>, None]
+            )
+            .unwrap()
+        );
+
+        // files without field ids cannot be matched with a schema by field id
+        assert!(read_data_file(file(), &[field("a", FileColumn::Id(1))]).is_err());
+    }
+}
@@ -1,5 +1,3 @@
-use std::{fs, io::Read};
-
 use indexmap::IndexMap;
 use logfmt_zerocopy::Logfmt;
 use polars::{
@@ -12,30 +10,32 @@ use crate::{
     args::Args,
     io::{
         reader::ReaderSource,
-        reader::{DataFrameReader, NamedFrames},
+        reader::{DataFrameReader, NamedFrame, NamedFrames},
     },
-    misc::stdin::stdin,
+    misc::encoding::TextEncoding,
 };
 
 #[derive(Debug, Default)]
-pub struct LogfmtToDataFrame {}
+pub struct LogfmtToDataFrame {
+    encoding: TextEncoding,
+}
 
 impl LogfmtToDataFrame {
-    pub fn from_args(_args: &Args) -> Self {
-        LogfmtToDataFrame::default()
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            encoding: TextEncoding::from_args(args),
+        }
+    }
+
+    pub fn with_encoding(mut self, encoding: TextEncoding) -> Self {
+        self.encoding = encoding;
+        self
     }
 }
 
 impl DataFrameReader for LogfmtToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
-        let contents = match &input {
-            ReaderSource::File(path_buf) => fs::read_to_string(path_buf)?,
-            ReaderSource::Stdin => {
-                let mut s = String::new();
-                stdin().read_to_string(&mut s)?;
-                s
-            }
-        };
+        let contents = input.read_to_string(self.encoding)?;
         let row_count = contents.lines().count();
         let mut data = IndexMap::new();
 
@@ -50,7 +50,7 @@ impl DataFrameReader for LogfmtToDataFrame {
                 .map(|(name, values)| Column::new(name.into(), values))
                 .collect(),
         )?;
-        Ok([(input.table_name(), df)].into())
+        Ok([NamedFrame::new(input.table_name(), df)].into())
     }
 }
 
@@ -1,5 +1,3 @@
-use std::io::Read;
-
 use polars::{
     frame::DataFrame,
     prelude::{AnyValue, Column},
@@ -9,29 +7,31 @@ use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
 use crate::{
     AppResult,
     args::Args,
-    io::reader::{DataFrameReader, NamedFrames, ReaderSource},
-    misc::stdin::stdin,
+    io::reader::{DataFrameReader, NamedFrame, NamedFrames, ReaderSource},
+    misc::encoding::TextEncoding,
 };
 
 #[derive(Debug, Default)]
-pub struct MarkdownToDataFrame;
+pub struct MarkdownToDataFrame {
+    encoding: TextEncoding,
+}
 
 impl MarkdownToDataFrame {
-    pub fn from_args(_args: &Args) -> Self {
-        Self
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            encoding: TextEncoding::from_args(args),
+        }
+    }
+
+    pub fn with_encoding(mut self, encoding: TextEncoding) -> Self {
+        self.encoding = encoding;
+        self
     }
 }
 
 impl DataFrameReader for MarkdownToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
-        let contents = match &input {
-            ReaderSource::File(path) => std::fs::read_to_string(path)?,
-            ReaderSource::Stdin => {
-                let mut s = String::new();
-                stdin().read_to_string(&mut s)?;
-                s
-            }
-        };
+        let contents = input.read_to_string(self.encoding)?;
 
         let parser = Parser::new_ext(&contents, Options::ENABLE_TABLES);
 
@@ -40,7 +40,7 @@ impl DataFrameReader for MarkdownToDataFrame {
             .enumerate()
             .map(|(idx, (headers, rows))| {
                 let df = build_data_frame(headers, rows)?;
-                Ok((format!("markdown_table_{}", idx + 1), df))
+                Ok(NamedFrame::new(format!("markdown_table_{}", idx + 1), df))
             })
             .collect::<AppResult<Vec<_>>>()
             .map(Vec::into_boxed_slice)
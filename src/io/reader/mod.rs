@@ -1,29 +1,55 @@
 mod arrow;
 mod avro;
 mod csv;
+mod csv_dialect;
+mod delta;
+#[cfg(feature = "duckdb")]
+mod duckdb;
 mod excel;
 mod fwf;
 mod html;
+mod iceberg;
 mod json;
 mod json_line;
+mod lakehouse;
 mod logfmt;
 mod markdown;
 mod parquet;
+mod read_options;
+mod regex;
+mod sas;
 mod source;
+mod spss;
 mod sqlite;
+mod stata;
+mod statistical;
+mod toml;
 mod traits;
+mod yaml;
 
 pub use arrow::ArrowIpcToDataFrame;
 pub use avro::AvroToDataFrame;
 pub use csv::CsvToDataFrame;
+pub use csv_dialect::CsvDialect;
+pub use delta::DeltaToDataFrame;
+#[cfg(feature = "duckdb")]
+pub use duckdb::DuckDbToDataFrames;
 pub use excel::ExcelToDataFrames;
 pub use fwf::FwfToDataFrame;
 pub use html::HtmlToDataFrame;
+pub use iceberg::IcebergToDataFrame;
 pub use json::JsonToDataFrame;
 pub use json_line::JsonLineToDataFrame;
 pub use logfmt::LogfmtToDataFrame;
 pub use markdown::MarkdownToDataFrame;
 pub use parquet::ParquetToDataFrame;
+pub use read_options::ReadOptions;
+pub use regex::RegexToDataFrame;
+pub use sas::SasToDataFrame;
 pub use source::ReaderSource;
+pub use spss::SpssToDataFrame;
 pub use sqlite::SqliteToDataFrames;
-pub use traits::{BuildReader, DataFrameReader, NamedFrames};
+pub use stata::StataToDataFrame;
+pub use toml::TomlToDataFrame;
+pub use traits::{BuildReader, DataFrameReader, NamedFrame, NamedFrames};
+pub use yaml::YamlToDataFrame;
@@ -1,25 +1,61 @@
 use std::fs::File;
 
-use polars::{io::SerReader, prelude::ParquetReader};
+use polars::{
+    frame::DataFrame,
+    io::{SerReader, mmap::MmapBytesReader},
+    prelude::ParquetReader,
+};
 
 use crate::{
     AppResult,
-    io::reader::{DataFrameReader, NamedFrames, ReaderSource},
+    args::Args,
+    io::reader::{DataFrameReader, NamedFrame, NamedFrames, ReadOptions, ReaderSource},
     misc::stdin::stdin,
 };
 
-#[derive(Debug)]
-pub struct ParquetToDataFrame;
+#[derive(Debug, Default)]
+pub struct ParquetToDataFrame {
+    read_options: ReadOptions,
+}
+
+impl ParquetToDataFrame {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            read_options: ReadOptions::from_args(args),
+        }
+    }
+
+    pub fn with_read_options(mut self, read_options: ReadOptions) -> Self {
+        self.read_options = read_options;
+        self
+    }
+
+    fn try_into_frame(&self, reader: impl MmapBytesReader) -> AppResult<DataFrame> {
+        let slice = self
+            .read_options
+            .n_rows()
+            .map(|n_rows| (self.read_options.skip_rows(), n_rows));
+        let df = ParquetReader::new(reader)
+            .with_slice(slice)
+            .with_columns(self.read_options.columns().map(<[_]>::to_vec))
+            .set_rechunk(true)
+            .finish()?;
+        let df = if slice.is_some() {
+            df
+        } else {
+            self.read_options.slice_rows(df)
+        };
+        self.read_options
+            .replace_null_values(self.read_options.select_columns(df)?)
+    }
+}
 
 impl DataFrameReader for ParquetToDataFrame {
     fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
         let df = match &input {
-            ReaderSource::File(path) => ParquetReader::new(File::open(path)?)
-                .set_rechunk(true)
-                .finish()?,
-
-            ReaderSource::Stdin => ParquetReader::new(stdin()).set_rechunk(true).finish()?,
+            ReaderSource::File(path) => self.try_into_frame(File::open(path)?)?,
+            ReaderSource::Stdin => self.try_into_frame(stdin())?,
         };
-        Ok([(input.table_name(), df)].into())
+        Ok([NamedFrame::new(input.table_name(), df)].into())
     }
 }
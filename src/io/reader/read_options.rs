@@ -0,0 +1,111 @@
+use polars::{
+    frame::DataFrame,
+    prelude::{Column, DataType, IntoColumn, NamedFrom, Series},
+};
+
+use crate::{AppResult, args::Args};
+
+/// Rows and columns to load, shared by the readers that can skip the rest while reading.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReadOptions {
+    skip_rows: usize,
+    n_rows: Option<usize>,
+    columns: Option<Vec<String>>,
+    null_values: Vec<String>,
+}
+
+impl ReadOptions {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            skip_rows: args.skip_rows,
+            n_rows: args.n_rows,
+            columns: (!args.columns.is_empty()).then(|| args.columns.clone()),
+            null_values: args.null_values.clone(),
+        }
+    }
+
+    /// Data rows skipped at the start, after the header if any.
+    pub fn with_skip_rows(mut self, skip_rows: usize) -> Self {
+        self.skip_rows = skip_rows;
+        self
+    }
+
+    pub fn with_n_rows(mut self, n_rows: Option<usize>) -> Self {
+        self.n_rows = n_rows;
+        self
+    }
+
+    /// Columns to keep, in order, or every column if `None`.
+    pub fn with_columns(mut self, columns: Option<Vec<String>>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Strings read as null in text columns.
+    pub fn with_null_values(mut self, null_values: Vec<String>) -> Self {
+        self.null_values = null_values;
+        self
+    }
+
+    pub fn skip_rows(&self) -> usize {
+        self.skip_rows
+    }
+
+    pub fn n_rows(&self) -> Option<usize> {
+        self.n_rows
+    }
+
+    pub fn columns(&self) -> Option<&[String]> {
+        self.columns.as_deref()
+    }
+
+    pub fn null_values(&self) -> &[String] {
+        &self.null_values
+    }
+
+    /// Rows to read when the reader can only stop early, before skipping.
+    pub fn rows_to_read(&self) -> Option<usize> {
+        self.n_rows.map(|n_rows| self.skip_rows + n_rows)
+    }
+
+    pub fn slice_rows(&self, df: DataFrame) -> DataFrame {
+        if self.skip_rows == 0 && self.n_rows.is_none() {
+            return df;
+        }
+        let len = self
+            .n_rows
+            .unwrap_or(df.height().saturating_sub(self.skip_rows));
+        df.slice(self.skip_rows as i64, len)
+    }
+
+    pub fn select_columns(&self, df: DataFrame) -> AppResult<DataFrame> {
+        match &self.columns {
+            Some(columns) => Ok(df.select(columns.iter().map(String::as_str))?),
+            None => Ok(df),
+        }
+    }
+
+    pub fn replace_null_values(&self, df: DataFrame) -> AppResult<DataFrame> {
+        if self.null_values.is_empty() {
+            return Ok(df);
+        }
+        let columns = df
+            .columns()
+            .iter()
+            .map(|column| self.replace_column_null_values(column))
+            .collect::<AppResult<Vec<_>>>()?;
+        Ok(DataFrame::new(df.height(), columns)?)
+    }
+
+    fn replace_column_null_values(&self, column: &Column) -> AppResult<Column> {
+        if column.dtype() != &DataType::String {
+            return Ok(column.clone());
+        }
+        let values = column
+            .str()?
+            .iter()
+            .map(|value| value.filter(|value| !self.null_values.iter().any(|null| null == value)))
+            .collect::<Vec<_>>();
+        Ok(Series::new(column.name().clone(), values).into_column())
+    }
+}
@@ -0,0 +1,191 @@
+use anyhow::anyhow;
+use polars::{frame::DataFrame, prelude::Column};
+use regex::Regex;
+
+use crate::{
+    AppResult,
+    args::Args,
+    io::reader::{DataFrameReader, NamedFrame, NamedFrames, ReaderSource},
+    misc::{encoding::TextEncoding, table_name_generator::TableNameGeneratorExt},
+};
+
+/// Column that collects the lines not matching the pattern, suffixed if a capture group of
+/// the pattern already has the name.
+pub const UNMATCHED_COLUMN: &str = "unmatched";
+
+#[derive(Debug, Default)]
+pub struct RegexToDataFrame {
+    pattern: Option<String>,
+    skip_unmatched: bool,
+    encoding: TextEncoding,
+}
+
+impl RegexToDataFrame {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            pattern: args.pattern.clone().or(args
+                .pattern_preset
+                .as_ref()
+                .map(|preset| preset.pattern().to_owned())),
+            skip_unmatched: args.skip_unmatched,
+            encoding: TextEncoding::from_args(args),
+        }
+    }
+
+    pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    pub fn with_skip_unmatched(mut self, skip_unmatched: bool) -> Self {
+        self.skip_unmatched = skip_unmatched;
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: TextEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Parses every non-empty line into a row, with a column for each named capture group.
+    pub fn parse(&self, contents: &str) -> AppResult<DataFrame> {
+        let pattern = self.pattern.as_deref().ok_or(anyhow!(
+            "The regex format requires a pattern (--pattern or --pattern-preset)"
+        ))?;
+        let regex = Regex::new(pattern)?;
+        let names = regex.capture_names().flatten().collect::<Vec<_>>();
+        if names.is_empty() {
+            return Err(anyhow!("The pattern has no named capture groups"));
+        }
+
+        let mut columns = vec![Vec::<Option<&str>>::new(); names.len()];
+        let mut unmatched = Vec::<Option<&str>>::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            match regex.captures(line) {
+                Some(captures) => {
+                    for (column, name) in columns.iter_mut().zip(names.iter()) {
+                        column.push(captures.name(name).map(|value| value.as_str()));
+                    }
+                    unmatched.push(None);
+                }
+                None if self.skip_unmatched => (),
+                None => {
+                    for column in columns.iter_mut() {
+                        column.push(None);
+                    }
+                    unmatched.push(Some(line));
+                }
+            }
+        }
+
+        let unmatched_name = UNMATCHED_COLUMN
+            .snake_case_names()
+            .find(|name| !names.contains(&name.as_str()))
+            .expect("Unable to find a name");
+        let mut columns = names
+            .into_iter()
+            .zip(columns)
+            .map(|(name, values)| Column::new(name.into(), values))
+            .collect::<Vec<_>>();
+        if unmatched.iter().any(Option::is_some) {
+            columns.push(Column::new(unmatched_name.into(), unmatched));
+        }
+        Ok(DataFrame::new_infer_height(columns)?)
+    }
+}
+
+impl DataFrameReader for RegexToDataFrame {
+    fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
+        let contents = input.read_to_string(self.encoding)?;
+        Ok([NamedFrame::new(input.table_name(), self.parse(&contents)?)].into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::AnyValue;
+
+    use crate::args::PatternPreset;
+
+    use super::*;
+
+    fn parse(preset: PatternPreset, line: &str) -> DataFrame {
+        let df = RegexToDataFrame::default()
+            .with_pattern(preset.pattern())
+            .parse(line)
+            .unwrap();
+        assert_eq!(df.height(), 1);
+        assert!(df.column(UNMATCHED_COLUMN).is_err(), "{line}");
+        df
+    }
+
+    fn value(df: &DataFrame, name: &str) -> String {
+        match df.column(name).unwrap().get(0).unwrap() {
+            AnyValue::String(value) => value.to_owned(),
+            other => panic!("{name} is {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_common_preset() {
+        let df = parse(
+            PatternPreset::Common,
+            r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326"#,
+        );
+        assert_eq!(value(&df, "user"), "frank");
+        assert_eq!(value(&df, "request"), "GET /apache_pb.gif HTTP/1.0");
+        assert_eq!(value(&df, "size"), "2326");
+    }
+
+    #[test]
+    fn test_combined_preset() {
+        let df = parse(
+            PatternPreset::Combined,
+            r#"127.0.0.1 - - [10/Oct/2000:13:55:36 -0700] "GET / HTTP/1.1" 304 - "http://example.com/" "Mozilla/5.0 (X11; Linux x86_64)""#,
+        );
+        assert_eq!(value(&df, "status"), "304");
+        assert_eq!(value(&df, "referer"), "http://example.com/");
+        assert_eq!(value(&df, "user_agent"), "Mozilla/5.0 (X11; Linux x86_64)");
+    }
+
+    #[test]
+    fn test_syslog_preset() {
+        let df = parse(
+            PatternPreset::Syslog,
+            "<34>Oct  1 22:14:15 mymachine su[230]: 'su root' failed for lonvick on /dev/pts/8",
+        );
+        assert_eq!(value(&df, "priority"), "34");
+        assert_eq!(value(&df, "time"), "Oct  1 22:14:15");
+        assert_eq!(value(&df, "app"), "su");
+        assert_eq!(value(&df, "pid"), "230");
+        assert_eq!(
+            value(&df, "message"),
+            "'su root' failed for lonvick on /dev/pts/8"
+        );
+    }
+
+    #[test]
+    fn test_rfc5424_preset() {
+        let df = parse(
+            PatternPreset::Rfc5424,
+            r#"<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut="3" eventSource="Application"] An application event"#,
+        );
+        assert_eq!(value(&df, "version"), "1");
+        assert_eq!(value(&df, "msgid"), "ID47");
+        assert_eq!(
+            value(&df, "structured_data"),
+            r#"[exampleSDID@32473 iut="3" eventSource="Application"]"#
+        );
+        assert_eq!(value(&df, "message"), "An application event");
+    }
+
+    #[test]
+    fn test_unmatched_column_name() {
+        let df = RegexToDataFrame::default()
+            .with_pattern(r"^(?P<level>\w+): (?P<unmatched>.*)$")
+            .parse("INFO: started\nnot a log line\n")
+            .unwrap();
+        assert_eq!(df.get_column_names(), ["level", "unmatched", "unmatched_2"]);
+        assert_eq!(value(&df, "unmatched"), "started");
+    }
+}
@@ -0,0 +1,816 @@
+use std::borrow::Cow;
+
+use anyhow::{anyhow, bail};
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+
+use crate::{
+    AppResult,
+    io::reader::{
+        DataFrameReader, NamedFrames, ReaderSource,
+        source::read_all,
+        statistical::{
+            BinaryReader, ByteOrder, ColumnValues, LabeledColumn, decode_text, labeled_data_frame,
+        },
+    },
+    misc::metadata::FieldMetadata,
+};
+
+const MAGIC: [u8; 32] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc2, 0xea, 0x81, 0x60,
+    0xb3, 0x14, 0x11, 0xcf, 0xbd, 0x92, 0x08, 0x00, 0x09, 0xc7, 0x31, 0x8c, 0x18, 0x1f, 0x10, 0x11,
+];
+
+/// Days between the SAS epoch (1960-01-01) and the unix epoch
+const EPOCH_OFFSET_DAYS: i64 = 3653;
+
+const DATE_FORMATS: &[&str] = &[
+    "DATE", "DAY", "DDMMYY", "DDMMYYB", "DDMMYYC", "DDMMYYD", "DDMMYYN", "DDMMYYP", "DDMMYYS",
+    "E8601DA", "B8601DA", "JULIAN", "MMDDYY", "MMDDYYC", "MMDDYYD", "MMDDYYN", "MMDDYYP",
+    "MMDDYYS", "MMYY", "MONYY", "WEEKDATE", "WEEKDATX", "WORDDATE", "WORDDATX", "YYMM", "YYMMDD",
+    "YYMMDDB", "YYMMDDC", "YYMMDDD", "YYMMDDN", "YYMMDDP", "YYMMDDS", "YYMON", "YYQ",
+];
+
+const DATETIME_FORMATS: &[&str] = &[
+    "DATETIME", "DATEAMPM", "DTDATE", "DTMONYY", "DTWKDATX", "DTYEAR", "E8601DT", "E8601DX",
+    "E8601DZ", "E8601LX", "B8601DT", "B8601DX", "B8601DZ", "B8601LX", "MDYAMPM",
+];
+
+#[derive(Debug)]
+pub struct SasToDataFrame;
+
+impl DataFrameReader for SasToDataFrame {
+    fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
+        let buf = read_all(&input)?;
+        Ok([labeled_data_frame(
+            input.table_name(),
+            Sas7bdat::parse(&buf)?.columns()?,
+            false,
+        )?]
+        .into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Compression {
+    Rle,
+    Rdc,
+}
+
+#[derive(Debug, Default)]
+struct Column {
+    name: String,
+    format: String,
+    label: String,
+    offset: usize,
+    len: usize,
+    numeric: bool,
+}
+
+#[derive(Debug)]
+struct Sas7bdat<'a> {
+    order: ByteOrder,
+    encoding: &'static Encoding,
+    int_len: usize,
+    compression: Option<Compression>,
+    text_blocks: Vec<&'a [u8]>,
+    columns: Vec<Column>,
+    names: usize,
+    formats: usize,
+    attributes: usize,
+    row_len: usize,
+    row_count: usize,
+    mix_page_row_count: usize,
+    rows: Vec<Cow<'a, [u8]>>,
+}
+
+impl<'a> Sas7bdat<'a> {
+    fn parse(buf: &'a [u8]) -> AppResult<Self> {
+        if buf.get(..32) != Some(&MAGIC) || buf.len() < 288 {
+            bail!("Not a SAS7BDAT file");
+        }
+        let is_64_bit = buf[32] == b'3';
+        let align = if buf[35] == b'3' { 4 } else { 0 };
+        let order = if buf[37] == 1 {
+            ByteOrder::Little
+        } else {
+            ByteOrder::Big
+        };
+        let reader = BinaryReader::new(buf, order);
+        let header_len = reader.at(196 + align).u32()? as usize;
+        let page_len = reader.at(200 + align).u32()? as usize;
+        if page_len == 0 {
+            bail!("Invalid page length");
+        }
+        let mut sas = Self {
+            order,
+            encoding: sas_encoding(buf[70]),
+            int_len: if is_64_bit { 8 } else { 4 },
+            compression: None,
+            text_blocks: Vec::new(),
+            columns: Vec::new(),
+            names: 0,
+            formats: 0,
+            attributes: 0,
+            row_len: 0,
+            row_count: 0,
+            mix_page_row_count: 0,
+            rows: Vec::new(),
+        };
+        for page in buf.get(header_len..).unwrap_or_default().chunks(page_len) {
+            sas.read_page(page)?;
+            if sas.row_len > 0 && sas.rows.len() >= sas.row_count {
+                break;
+            }
+        }
+        Ok(sas)
+    }
+
+    fn read_page(&mut self, page: &'a [u8]) -> AppResult<()> {
+        let bit_offset = if self.int_len == 8 { 32 } else { 16 };
+        let pointer_len = if self.int_len == 8 { 24 } else { 12 };
+        let mut reader = BinaryReader::new(page, self.order).at(bit_offset);
+        let page_type = reader.u16()? & 0xff00;
+        let block_count = reader.u16()? as usize;
+        let subheader_count = reader.u16()? as usize;
+        let pointers_end = bit_offset + 8 + subheader_count * pointer_len;
+        match page_type {
+            // meta, amd and mix pages
+            0 | 16384 | 1024 | 512 => {
+                for idx in 0..subheader_count {
+                    self.read_subheader(page, bit_offset + 8 + idx * pointer_len)?;
+                }
+                if page_type == 512 {
+                    let offset = pointers_end + pointers_end % 8;
+                    let count = self
+                        .mix_page_row_count
+                        .min(self.row_count.saturating_sub(self.rows.len()));
+                    self.read_rows(page, offset, count);
+                }
+            }
+            // data pages
+            256 => {
+                let count = block_count.min(self.row_count.saturating_sub(self.rows.len()));
+                self.read_rows(page, bit_offset + 8, count);
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    fn read_rows(&mut self, page: &'a [u8], offset: usize, count: usize) {
+        if self.row_len == 0 {
+            return;
+        }
+        self.rows.extend(
+            page.get(offset..)
+                .unwrap_or_default()
+                .chunks_exact(self.row_len)
+                .take(count)
+                .map(Cow::Borrowed),
+        );
+    }
+
+    fn read_subheader(&mut self, page: &'a [u8], pointer: usize) -> AppResult<()> {
+        let int_len = self.int_len;
+        let mut reader = BinaryReader::new(page, self.order).at(pointer);
+        let offset = reader.uint(int_len)? as usize;
+        let len = reader.uint(int_len)? as usize;
+        let compression = reader.u8()?;
+        let subheader_type = reader.u8()?;
+        if len == 0 || compression == 1 {
+            return Ok(());
+        }
+        let subheader = page
+            .get(offset..offset + len)
+            .ok_or(anyhow!("Invalid subheader pointer"))?;
+        let reader = BinaryReader::new(subheader, self.order);
+        let signature = match self.order {
+            ByteOrder::Little => reader.at(0).u32()?,
+            ByteOrder::Big => reader.at(int_len - 4).u32()?,
+        };
+        let uint = |offset: usize, len: usize| reader.at(offset).uint(len).map(|v| v as usize);
+        match signature {
+            // row size
+            0xF7F7F7F7 => {
+                self.row_len = uint(5 * int_len, int_len)?;
+                self.row_count = uint(6 * int_len, int_len)?;
+                self.mix_page_row_count = uint(15 * int_len, int_len)?;
+            }
+            // column text
+            0xFFFFFFFD => {
+                let block_len = uint(int_len, 2)?;
+                let block = subheader
+                    .get(int_len..int_len + block_len)
+                    .ok_or(anyhow!("Invalid column text subheader"))?;
+                if self.text_blocks.is_empty() {
+                    if contains(block, b"SASYZCRL") {
+                        self.compression = Some(Compression::Rle);
+                    } else if contains(block, b"SASYZCR2") {
+                        self.compression = Some(Compression::Rdc);
+                    }
+                }
+                self.text_blocks.push(block);
+            }
+            // column name
+            0xFFFFFFFF => {
+                for idx in 0..len.saturating_sub(2 * int_len + 12) / 8 {
+                    let base = int_len + 8 * (idx + 1);
+                    let name = self.text(uint(base, 2)?, uint(base + 2, 2)?, uint(base + 4, 2)?);
+                    self.column(self.names).name = name;
+                    self.names += 1;
+                }
+            }
+            // column attributes
+            0xFFFFFFFC => {
+                for idx in 0..len.saturating_sub(2 * int_len + 12) / (int_len + 8) {
+                    let base = idx * (int_len + 8);
+                    let offset = uint(int_len + 8 + base, int_len)?;
+                    let len = uint(2 * int_len + 8 + base, 4)?;
+                    let numeric = uint(2 * int_len + 14 + base, 1)? == 1;
+                    let column = self.column(self.attributes);
+                    column.offset = offset;
+                    column.len = len;
+                    column.numeric = numeric;
+                    self.attributes += 1;
+                }
+            }
+            // format and label
+            0xFFFFFBFE => {
+                let base = 3 * int_len;
+                let format = self.text(
+                    uint(base + 22, 2)?,
+                    uint(base + 24, 2)?,
+                    uint(base + 26, 2)?,
+                );
+                let label = self.text(
+                    uint(base + 28, 2)?,
+                    uint(base + 30, 2)?,
+                    uint(base + 32, 2)?,
+                );
+                let column = self.column(self.formats);
+                column.format = format;
+                column.label = label;
+                self.formats += 1;
+            }
+            // column size, subheader counts and column list
+            0xF6F6F6F6 | 0xFFFFFC00 | 0xFFFFFFFE => (),
+            _ if self.compression.is_some()
+                && (compression == 4 || compression == 0)
+                && subheader_type == 1 =>
+            {
+                self.rows.push(if len < self.row_len {
+                    Cow::Owned(self.decompress(subheader)?)
+                } else {
+                    Cow::Borrowed(&subheader[..self.row_len])
+                });
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    fn column(&mut self, idx: usize) -> &mut Column {
+        if self.columns.len() <= idx {
+            self.columns.resize_with(idx + 1, Default::default);
+        }
+        &mut self.columns[idx]
+    }
+
+    fn text(&self, block: usize, offset: usize, len: usize) -> String {
+        self.text_blocks
+            .get(block.min(self.text_blocks.len().saturating_sub(1)))
+            .and_then(|block| block.get(offset..offset + len))
+            .map(|bytes| decode_text(bytes, self.encoding))
+            .unwrap_or_default()
+    }
+
+    fn decompress(&self, data: &[u8]) -> AppResult<Vec<u8>> {
+        let mut row = match self.compression {
+            Some(Compression::Rle) => rle_decompress(data, self.row_len)?,
+            Some(Compression::Rdc) => rdc_decompress(data, self.row_len)?,
+            None => data.to_vec(),
+        };
+        row.resize(self.row_len, 0);
+        Ok(row)
+    }
+
+    fn columns(&self) -> AppResult<Vec<LabeledColumn>> {
+        self.columns
+            .iter()
+            .map(|column| {
+                let cells = self
+                    .rows
+                    .iter()
+                    .map(|row| row.get(column.offset..column.offset + column.len));
+                let values = if column.numeric {
+                    let numbers = cells.map(|cell| cell.and_then(|cell| self.number(cell)));
+                    let format = column.format.to_uppercase();
+                    if DATE_FORMATS.contains(&format.as_str()) {
+                        ColumnValues::Date(
+                            numbers
+                                .map(|days| {
+                                    days.map(|days| (days as i64 - EPOCH_OFFSET_DAYS) as i32)
+                                })
+                                .collect(),
+                        )
+                    } else if DATETIME_FORMATS.contains(&format.as_str()) {
+                        ColumnValues::Datetime(
+                            numbers
+                                .map(|secs| {
+                                    secs.map(|secs| {
+                                        (secs * 1000.0).round() as i64
+                                            - EPOCH_OFFSET_DAYS * 24 * 60 * 60 * 1000
+                                    })
+                                })
+                                .collect(),
+                        )
+                    } else {
+                        ColumnValues::Float64(numbers.collect())
+                    }
+                } else {
+                    ColumnValues::String(
+                        cells
+                            .map(|cell| cell.map(|cell| decode_text(cell, self.encoding)))
+                            .collect(),
+                    )
+                };
+                Ok(LabeledColumn {
+                    name: column.name.clone(),
+                    values,
+                    metadata: FieldMetadata::default().with_label(column.label.as_str()),
+                })
+            })
+            .collect()
+    }
+
+    /// Reads a numeric cell, which holds the leading bytes of a double when it is shorter
+    /// than eight bytes. Missing values are stored as NaNs.
+    fn number(&self, cell: &[u8]) -> Option<f64> {
+        let len = cell.len().min(8);
+        let mut bytes = [0_u8; 8];
+        let value = match self.order {
+            ByteOrder::Little => {
+                bytes[8 - len..].copy_from_slice(&cell[..len]);
+                f64::from_le_bytes(bytes)
+            }
+            ByteOrder::Big => {
+                bytes[..len].copy_from_slice(&cell[..len]);
+                f64::from_be_bytes(bytes)
+            }
+        };
+        (!value.is_nan()).then_some(value)
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+fn rle_decompress(input: &[u8], len: usize) -> AppResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(len);
+    let mut pos = 0;
+    let byte = |pos: usize| {
+        input
+            .get(pos)
+            .copied()
+            .ok_or(anyhow!("Invalid RLE compressed row"))
+    };
+    let slice = |pos: usize, len: usize| {
+        input
+            .get(pos..pos + len)
+            .ok_or(anyhow!("Invalid RLE compressed row"))
+    };
+    while pos < input.len() {
+        let control = input[pos] & 0xf0;
+        let low = (input[pos] & 0x0f) as usize;
+        pos += 1;
+        match control {
+            0x00 => {
+                let count = byte(pos)? as usize + 64 + low * 256;
+                out.extend_from_slice(slice(pos + 1, count)?);
+                pos += 1 + count;
+            }
+            0x10 => {
+                let count = byte(pos)? as usize + 64 + low * 256 + 4096;
+                out.extend_from_slice(slice(pos + 1, count)?);
+                pos += 1 + count;
+            }
+            0x20 => {
+                let count = low + 96;
+                out.extend_from_slice(slice(pos, count)?);
+                pos += count;
+            }
+            0x40 => {
+                let count = byte(pos)? as usize + 18 + low * 256;
+                out.extend(std::iter::repeat_n(byte(pos + 1)?, count));
+                pos += 2;
+            }
+            0x50 => {
+                out.extend(std::iter::repeat_n(
+                    b'@',
+                    byte(pos)? as usize + 17 + low * 256,
+                ));
+                pos += 1;
+            }
+            0x60 | 0x70 => {
+                let count = byte(pos)? as usize + 17 + low * 256;
+                out.extend(std::iter::repeat_n(
+                    if control == 0x60 { b' ' } else { 0 },
+                    count,
+                ));
+                pos += 1;
+            }
+            0x80 | 0x90 | 0xa0 | 0xb0 => {
+                let count = low + 1 + ((control as usize - 0x80) >> 4) * 16;
+                out.extend_from_slice(slice(pos, count)?);
+                pos += count;
+            }
+            0xc0 => {
+                out.extend(std::iter::repeat_n(byte(pos)?, low + 3));
+                pos += 1;
+            }
+            0xd0 => out.extend(std::iter::repeat_n(b'@', low + 2)),
+            0xe0 => out.extend(std::iter::repeat_n(b' ', low + 2)),
+            0xf0 => out.extend(std::iter::repeat_n(0, low + 2)),
+            _ => bail!("Unknown RLE control byte {:#04x}", input[pos - 1]),
+        }
+    }
+    Ok(out)
+}
+
+fn rdc_decompress(input: &[u8], len: usize) -> AppResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(len);
+    let mut pos = 0;
+    let mut ctrl_bits = 0_u16;
+    let mut ctrl_mask = 0_u16;
+    let byte = |pos: usize| {
+        input
+            .get(pos)
+            .copied()
+            .map(u16::from)
+            .ok_or(anyhow!("Invalid RDC compressed row"))
+    };
+    while pos < input.len() {
+        ctrl_mask >>= 1;
+        if ctrl_mask == 0 {
+            ctrl_bits = (byte(pos)? << 8) + byte(pos + 1)?;
+            pos += 2;
+            ctrl_mask = 0x8000;
+        }
+        if ctrl_bits & ctrl_mask == 0 {
+            out.push(byte(pos)? as u8);
+            pos += 1;
+            continue;
+        }
+        let cmd = (byte(pos)? >> 4) & 0x0f;
+        let cnt = byte(pos)? & 0x0f;
+        pos += 1;
+        match cmd {
+            // short run
+            0 => {
+                out.extend(std::iter::repeat_n(byte(pos)? as u8, cnt as usize + 3));
+                pos += 1;
+            }
+            // long run
+            1 => {
+                let cnt = cnt + (byte(pos)? << 4) + 19;
+                out.extend(std::iter::repeat_n(byte(pos + 1)? as u8, cnt as usize));
+                pos += 2;
+            }
+            // long and short patterns
+            _ => {
+                let offset = (cnt + 3 + (byte(pos)? << 4)) as usize;
+                pos += 1;
+                let cnt = if cmd == 2 {
+                    pos += 1;
+                    byte(pos - 1)? as usize + 16
+                } else {
+                    cmd as usize
+                };
+                let start = out
+                    .len()
+                    .checked_sub(offset)
+                    .ok_or(anyhow!("Invalid RDC compressed row"))?;
+                for idx in start..start + cnt {
+                    out.push(out[idx]);
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn sas_encoding(code: u8) -> &'static Encoding {
+    let label: &[u8] = match code {
+        20 => b"utf-8",
+        30 => b"iso-8859-2",
+        31 => b"iso-8859-3",
+        32 => b"iso-8859-4",
+        33 => b"iso-8859-5",
+        34 => b"iso-8859-6",
+        35 => b"iso-8859-7",
+        36 => b"iso-8859-8",
+        39 => b"iso-8859-11",
+        40 => b"iso-8859-15",
+        60 => b"windows-1250",
+        61 => b"windows-1251",
+        63 => b"windows-1253",
+        64 => b"windows-1254",
+        65 => b"windows-1255",
+        66 => b"windows-1256",
+        67 => b"windows-1257",
+        68 => b"windows-1258",
+        119 => b"euc-tw",
+        123 => b"big5",
+        125 => b"gb18030",
+        134 => b"euc-jp",
+        138 => b"shift_jis",
+        140 => b"euc-kr",
+        _ => b"windows-1252",
+    };
+    Encoding::for_label(label).unwrap_or(if code == 20 { UTF_8 } else { WINDOWS_1252 })
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::{DataType, PlSmallStr};
+
+    use super::*;
+
+    #[test]
+    fn test_rle_copy_commands() {
+        let mut input = vec![0x01, 0x02];
+        input.extend((0..64 + 256 + 2).map(|idx| idx as u8));
+        assert_eq!(rle_decompress(&input, 0).unwrap(), input[2..]);
+
+        let mut input = vec![0x10, 0x00];
+        input.extend((0..64 + 4096).map(|idx| idx as u8));
+        assert_eq!(rle_decompress(&input, 0).unwrap(), input[2..]);
+
+        let mut input = vec![0x21];
+        input.extend((0..97).map(|idx| idx as u8));
+        assert_eq!(rle_decompress(&input, 0).unwrap(), input[1..]);
+
+        assert_eq!(rle_decompress(&[0x82, 1, 2, 3], 0).unwrap(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_rle_insert_commands() {
+        assert_eq!(rle_decompress(&[0x50, 0x03], 0).unwrap(), [b'@'; 20]);
+        assert_eq!(
+            rle_decompress(&[0x51, 0x00], 0).unwrap(),
+            vec![b'@'; 17 + 256]
+        );
+        assert_eq!(rle_decompress(&[0x40, 0x00, b'x'], 0).unwrap(), [b'x'; 18]);
+        assert_eq!(rle_decompress(&[0x60, 0x00], 0).unwrap(), [b' '; 17]);
+        assert_eq!(rle_decompress(&[0x70, 0x01], 0).unwrap(), [0; 18]);
+        assert_eq!(rle_decompress(&[0xc1, b'y'], 0).unwrap(), [b'y'; 4]);
+        assert_eq!(rle_decompress(&[0xd0], 0).unwrap(), [b'@'; 2]);
+        assert_eq!(rle_decompress(&[0xe1], 0).unwrap(), [b' '; 3]);
+        assert_eq!(rle_decompress(&[0xf2], 0).unwrap(), [0; 4]);
+    }
+
+    #[test]
+    fn test_rle_rejects_unknown_commands() {
+        assert!(rle_decompress(&[0x30], 0).is_err());
+        assert!(rle_decompress(&[0x20, 1, 2], 0).is_err());
+    }
+
+    #[test]
+    fn test_rdc_decompress() {
+        // a run, three literals and a pattern copying the literals back twice
+        let input = [
+            0x8c, 0x00, 0x01, b'x', b'a', b'b', b'c', 0x30, 0x00, 0x30, 0x00,
+        ];
+        assert_eq!(rdc_decompress(&input, 0).unwrap(), b"xxxxabcabcabc");
+        // a long run of 19 + 1 + 16 bytes
+        assert_eq!(
+            rdc_decompress(&[0x80, 0x00, 0x11, 0x01, b'z'], 0).unwrap(),
+            [b'z'; 36]
+        );
+    }
+
+    const HEADER_LEN: usize = 1024;
+    const PAGE_LEN: usize = 2048;
+
+    /// Rows of `id` (a number), `name` (8 characters) and `day` (a date).
+    fn rows() -> [Vec<u8>; 2] {
+        let row = |id: f64, name: &[u8; 8], day: f64| {
+            [&id.to_le_bytes()[..], name, &day.to_le_bytes()].concat()
+        };
+        [
+            row(1.0, b"ann     ", 0.0),
+            row(f64::NAN, b"bob     ", 3653.0),
+        ]
+    }
+
+    fn subheader(signature: u32, len: usize, fields: &[(usize, u64, usize)]) -> Vec<u8> {
+        let mut subheader = vec![0; len];
+        subheader[..4].copy_from_slice(&signature.to_le_bytes());
+        for (offset, value, len) in fields {
+            subheader[*offset..offset + len].copy_from_slice(&value.to_le_bytes()[..*len]);
+        }
+        subheader
+    }
+
+    /// The subheaders describing the columns, with the text block tagged for `compression`.
+    fn meta_subheaders(compression: &[u8; 8]) -> Vec<(Vec<u8>, u8, u8)> {
+        let texts = [b"id".as_slice(), b"name", b"day", b"Identifier", b"DATE"];
+        let mut block = [&[0; 4][..], compression].concat();
+        let mut refs = Vec::new();
+        for text in texts {
+            refs.push((block.len() as u64, text.len() as u64));
+            block.extend_from_slice(text);
+        }
+        let block_len = block.len() as u16;
+        block[..2].copy_from_slice(&block_len.to_le_bytes());
+        let text = [&0xFFFFFFFD_u64.to_le_bytes()[..], &block].concat();
+
+        let row_size = subheader(0xF7F7F7F7, 480, &[(40, 24, 8), (48, 2, 8), (120, 2, 8)]);
+        let names = subheader(
+            0xFFFFFFFF,
+            28 + 8 * 3,
+            &(0..3)
+                .flat_map(|idx| {
+                    [
+                        (18 + 8 * idx, refs[idx].0, 2),
+                        (20 + 8 * idx, refs[idx].1, 2),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        );
+        let attributes = subheader(
+            0xFFFFFFFC,
+            28 + 16 * 3,
+            &(0..3)
+                .flat_map(|idx| {
+                    [
+                        (16 + 16 * idx, 8 * idx as u64, 8),
+                        (24 + 16 * idx, 8, 4),
+                        (30 + 16 * idx, (idx != 1) as u64, 1),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        );
+        let format = |format: Option<usize>, label: Option<usize>| {
+            let mut fields = Vec::new();
+            if let Some(idx) = format {
+                fields.extend([(48, refs[idx].0, 2), (50, refs[idx].1, 2)]);
+            }
+            if let Some(idx) = label {
+                fields.extend([(54, refs[idx].0, 2), (56, refs[idx].1, 2)]);
+            }
+            (subheader(0xFFFFFBFE, 64, &fields), 0, 0)
+        };
+        vec![
+            (row_size, 0, 0),
+            (text, 0, 0),
+            (names, 0, 0),
+            (attributes, 0, 0),
+            format(None, Some(3)),
+            format(None, None),
+            format(Some(4), None),
+        ]
+    }
+
+    /// A page whose subheaders are laid out from its end and whose rows follow the pointers.
+    fn page(page_type: u16, subheaders: &[(Vec<u8>, u8, u8)], rows: &[Vec<u8>]) -> Vec<u8> {
+        let mut page = vec![0; PAGE_LEN];
+        page[32..34].copy_from_slice(&page_type.to_le_bytes());
+        page[34..36].copy_from_slice(&(rows.len() as u16).to_le_bytes());
+        page[36..38].copy_from_slice(&(subheaders.len() as u16).to_le_bytes());
+        let mut end = PAGE_LEN;
+        for (idx, (subheader, compression, subheader_type)) in subheaders.iter().enumerate() {
+            end -= subheader.len();
+            page[end..end + subheader.len()].copy_from_slice(subheader);
+            let pointer = 40 + 24 * idx;
+            page[pointer..pointer + 8].copy_from_slice(&(end as u64).to_le_bytes());
+            page[pointer + 8..pointer + 16]
+                .copy_from_slice(&(subheader.len() as u64).to_le_bytes());
+            page[pointer + 16] = *compression;
+            page[pointer + 17] = *subheader_type;
+        }
+        let pointers_end = 40 + 24 * subheaders.len();
+        let rows = rows.concat();
+        let start = pointers_end + pointers_end % 8;
+        page[start..start + rows.len()].copy_from_slice(&rows);
+        page
+    }
+
+    fn sas7bdat(pages: &[Vec<u8>]) -> Vec<u8> {
+        let mut header = vec![0; HEADER_LEN];
+        header[..32].copy_from_slice(&MAGIC);
+        header[32] = b'3';
+        header[37] = 1;
+        header[70] = 20;
+        header[196..200].copy_from_slice(&(HEADER_LEN as u32).to_le_bytes());
+        header[200..204].copy_from_slice(&(PAGE_LEN as u32).to_le_bytes());
+        [header, pages.concat()].concat()
+    }
+
+    fn assert_frame(buf: &[u8]) {
+        let columns = Sas7bdat::parse(buf).unwrap().columns().unwrap();
+        let frame = labeled_data_frame("sas", columns, false).unwrap();
+        let df = frame.data_frame();
+        assert_eq!(
+            df.get_column_names()
+                .into_iter()
+                .map(PlSmallStr::as_str)
+                .collect::<Vec<_>>(),
+            ["id", "name", "day"]
+        );
+        assert_eq!(
+            df.dtypes(),
+            [DataType::Float64, DataType::String, DataType::Date]
+        );
+        assert_eq!(
+            df.column("id").unwrap().f64().unwrap().to_vec(),
+            [Some(1.0), None]
+        );
+        assert_eq!(
+            df.column("name")
+                .unwrap()
+                .str()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            [Some("ann"), Some("bob")]
+        );
+        assert_eq!(
+            df.column("day")
+                .unwrap()
+                .date()
+                .unwrap()
+                .physical()
+                .to_vec(),
+            [Some(-3653), Some(0)]
+        );
+        assert_eq!(
+            frame.metadata().field("id").and_then(FieldMetadata::label),
+            Some("Identifier")
+        );
+        assert!(frame.metadata().field("name").is_none());
+    }
+
+    #[test]
+    fn test_data_pages() {
+        let meta = page(0, &meta_subheaders(&[b' '; 8]), &[]);
+        let data = page(256, &[], &rows());
+        assert_frame(&sas7bdat(&[meta, data]));
+    }
+
+    #[test]
+    fn test_mix_pages() {
+        assert_frame(&sas7bdat(&[page(
+            512,
+            &meta_subheaders(&[b' '; 8]),
+            &rows(),
+        )]));
+    }
+
+    #[test]
+    fn test_compressed_rows() {
+        let [first, second] = rows();
+        let rle = [
+            [
+                &[0xf4, 0x81][..],
+                &first[6..8],
+                &[0x82],
+                b"ann",
+                &[0xe3, 0xf6],
+            ]
+            .concat(),
+            [
+                &[0xf4, 0x81][..],
+                &second[6..8],
+                &[0x82],
+                b"bob",
+                &[0xe3, 0x87],
+                &second[16..],
+            ]
+            .concat(),
+        ];
+        let rdc = [
+            [
+                &[0x83, 0x00, 0x03, 0x00][..],
+                &first[6..11],
+                &[0x02, b' ', 0x05, 0x00],
+            ]
+            .concat(),
+            [
+                &[0x82, 0x00, 0x03, 0x00][..],
+                &second[6..11],
+                &[0x02, b' '],
+                &second[16..],
+            ]
+            .concat(),
+        ];
+        for (compression, compressed) in [(b"SASYZCRL", rle), (b"SASYZCR2", rdc)] {
+            let mut subheaders = meta_subheaders(compression);
+            subheaders.extend(compressed.into_iter().map(|row| (row, 4, 1)));
+            assert_frame(&sas7bdat(&[page(0, &subheaders, &[])]));
+        }
+    }
+}
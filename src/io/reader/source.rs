@@ -1,4 +1,19 @@
-use std::{convert::Infallible, ffi::OsStr, path::PathBuf, str::FromStr};
+use std::{
+    convert::Infallible,
+    ffi::OsStr,
+    fs::File,
+    io::{Read, Seek},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use crate::{
+    AppResult,
+    misc::{
+        encoding::{SNIFF_LEN, TextEncoding},
+        stdin::stdin,
+    },
+};
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum ReaderSource {
@@ -17,6 +32,77 @@ impl ReaderSource {
             ReaderSource::Stdin => String::from("Stdin"),
         }
     }
+
+    /// Reads the whole input, transcoded from the given encoding to UTF-8.
+    pub fn read_to_utf8(&self, encoding: TextEncoding) -> AppResult<Vec<u8>> {
+        Ok(encoding.to_utf8(read_all(self)?))
+    }
+
+    pub fn read_to_string(&self, encoding: TextEncoding) -> AppResult<String> {
+        Ok(String::from_utf8(self.read_to_utf8(encoding)?)?)
+    }
+
+    /// Opens a file in UTF-8 as it is, so that the reader can memory map it, and transcodes
+    /// anything else into memory.
+    pub(super) fn open_utf8(&self, encoding: TextEncoding) -> AppResult<Utf8Input> {
+        let ReaderSource::File(path) = self else {
+            return Ok(Utf8Input::Bytes(self.read_to_utf8(encoding)?));
+        };
+        let mut file = File::open(path)?;
+        let mut head = Vec::new();
+        file.by_ref()
+            .take(SNIFF_LEN as u64)
+            .read_to_end(&mut head)?;
+        if encoding.is_plain_utf8(&head) {
+            file.rewind()?;
+            Ok(Utf8Input::File(file, head))
+        } else {
+            file.read_to_end(&mut head)?;
+            Ok(Utf8Input::Bytes(encoding.to_utf8(head)))
+        }
+    }
+}
+
+/// Reads the whole input into memory, for formats that are not streamed.
+pub(super) fn read_all(input: &ReaderSource) -> AppResult<Vec<u8>> {
+    match input {
+        ReaderSource::File(path) => Ok(std::fs::read(path)?),
+        ReaderSource::Stdin => {
+            let mut buf = Vec::new();
+            stdin().read_to_end(&mut buf)?;
+            Ok(buf)
+        }
+    }
+}
+
+/// An input ready to be parsed as UTF-8.
+#[derive(Debug)]
+pub(super) enum Utf8Input {
+    /// A file that is already in UTF-8, along with its first bytes
+    File(File, Vec<u8>),
+    /// The input transcoded into memory
+    Bytes(Vec<u8>),
+}
+
+impl Utf8Input {
+    /// The start of the input, to sniff its format from.
+    pub fn head(&self) -> &[u8] {
+        match self {
+            Utf8Input::File(_, head) => head,
+            Utf8Input::Bytes(bytes) => &bytes[..bytes.len().min(SNIFF_LEN)],
+        }
+    }
+
+    pub fn into_bytes(self) -> AppResult<Vec<u8>> {
+        match self {
+            Utf8Input::File(mut file, _) => {
+                let mut bytes = Vec::new();
+                file.read_to_end(&mut bytes)?;
+                Ok(bytes)
+            }
+            Utf8Input::Bytes(bytes) => Ok(bytes),
+        }
+    }
 }
 
 impl From<PathBuf> for ReaderSource {
@@ -32,3 +118,40 @@ impl FromStr for ReaderSource {
         Ok(ReaderSource::File(PathBuf::from(s)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use encoding_rs::{UTF_8, WINDOWS_1252};
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    fn open(contents: &[u8], encoding: TextEncoding) -> Utf8Input {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(contents).unwrap();
+        ReaderSource::File(file.path().to_owned())
+            .open_utf8(encoding)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_open_utf8() {
+        for encoding in [TextEncoding::Auto, TextEncoding::Fixed(UTF_8)] {
+            let input = open("a,b\n1,é\n".as_bytes(), encoding);
+            assert!(matches!(input, Utf8Input::File(..)));
+            assert_eq!(input.into_bytes().unwrap(), "a,b\n1,é\n".as_bytes());
+
+            let input = open(b"\xEF\xBB\xBFa\n", encoding);
+            assert!(matches!(input, Utf8Input::Bytes(_)));
+            assert_eq!(input.into_bytes().unwrap(), b"a\n");
+        }
+
+        for encoding in [TextEncoding::Auto, TextEncoding::Fixed(WINDOWS_1252)] {
+            let input = open(b"caf\xE9\n", encoding);
+            assert!(matches!(input, Utf8Input::Bytes(_)));
+            assert_eq!(input.into_bytes().unwrap(), "café\n".as_bytes());
+        }
+    }
+}
@@ -0,0 +1,634 @@
+use std::{collections::HashMap, io::Read};
+
+use anyhow::bail;
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+use flate2::read::ZlibDecoder;
+
+use crate::{
+    AppResult,
+    args::Args,
+    io::reader::{
+        DataFrameReader, NamedFrames, ReaderSource,
+        source::read_all,
+        statistical::{
+            BinaryReader, ByteOrder, ColumnValues, LabeledColumn, decode_text, labeled_data_frame,
+        },
+    },
+    misc::metadata::{FieldMetadata, value_label_key},
+};
+
+/// Seconds between the SPSS epoch (1582-10-14) and the unix epoch
+const EPOCH_OFFSET_SECS: f64 = 12_219_379_200.0;
+
+#[derive(Debug, Default)]
+pub struct SpssToDataFrame {
+    apply_value_labels: bool,
+}
+
+impl SpssToDataFrame {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            apply_value_labels: args.apply_value_labels,
+        }
+    }
+
+    pub fn with_apply_value_labels(mut self, apply_value_labels: bool) -> Self {
+        self.apply_value_labels = apply_value_labels;
+        self
+    }
+}
+
+impl DataFrameReader for SpssToDataFrame {
+    fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
+        let buf = read_all(&input)?;
+        let sav = Sav::parse(&buf)?;
+        Ok([labeled_data_frame(
+            input.table_name(),
+            sav.columns()?,
+            self.apply_value_labels,
+        )?]
+        .into())
+    }
+}
+
+#[derive(Debug)]
+enum VarKind {
+    Numeric {
+        format: u8,
+    },
+    /// Byte ranges of the string within a case, more than one for very long strings
+    String {
+        segments: Vec<(usize, usize)>,
+    },
+}
+
+#[derive(Debug)]
+struct Variable {
+    /// Index of the first 8-byte slot of the variable within a case
+    slot: usize,
+    short_name: String,
+    name: String,
+    label: Vec<u8>,
+    width: usize,
+    format: u8,
+    value_labels: Vec<(String, Vec<u8>)>,
+}
+
+#[derive(Debug)]
+struct Sav {
+    order: ByteOrder,
+    encoding: &'static Encoding,
+    sysmis: f64,
+    variables: Vec<(VarKind, Variable)>,
+    /// Concatenated cases, `case_len` bytes each
+    data: Vec<u8>,
+    case_len: usize,
+}
+
+impl Sav {
+    fn parse(buf: &[u8]) -> AppResult<Self> {
+        let zlib = match buf.get(..4) {
+            Some(b"$FL2") => false,
+            Some(b"$FL3") => true,
+            _ => bail!("Not an SPSS system file"),
+        };
+        let order = match BinaryReader::new(buf, ByteOrder::Little).at(64).i32()? {
+            2 | 3 => ByteOrder::Little,
+            _ => ByteOrder::Big,
+        };
+        let mut reader = BinaryReader::new(buf, order).at(72);
+        let compression = reader.i32()?;
+        reader.skip(8)?;
+        let bias = reader.f64()?;
+        reader.seek(176);
+
+        let mut slots = Vec::<Option<Variable>>::new();
+        let mut value_labels = Vec::<(Vec<(Vec<u8>, Vec<u8>)>, Vec<usize>)>::new();
+        let mut long_names = HashMap::new();
+        let mut very_long_strings = HashMap::new();
+        let mut long_string_labels = Vec::new();
+        let mut encoding = None;
+        let mut sysmis = -f64::MAX;
+        loop {
+            match reader.i32()? {
+                2 => {
+                    let var_type = reader.i32()?;
+                    let has_label = reader.i32()? == 1;
+                    let missing_values = reader.i32()?;
+                    let print = reader.i32()?;
+                    reader.skip(4)?;
+                    let short_name = decode_text(reader.bytes(8)?, WINDOWS_1252);
+                    let label = if has_label {
+                        let len = reader.u32()? as usize;
+                        let label = reader.bytes(len)?.to_vec();
+                        reader.skip(len.next_multiple_of(4) - len)?;
+                        label
+                    } else {
+                        Vec::new()
+                    };
+                    reader.skip(missing_values.unsigned_abs() as usize * 8)?;
+                    slots.push((var_type >= 0).then(|| Variable {
+                        slot: slots.len(),
+                        name: short_name.clone(),
+                        short_name,
+                        label,
+                        width: var_type as usize,
+                        format: (print >> 16) as u8,
+                        value_labels: Vec::new(),
+                    }));
+                }
+                3 => {
+                    let count = reader.u32()? as usize;
+                    let mut labels = Vec::with_capacity(count);
+                    for _ in 0..count {
+                        let value = reader.bytes(8)?.to_vec();
+                        let len = reader.u8()? as usize;
+                        let label = reader.bytes(len)?.to_vec();
+                        reader.skip((len + 1).next_multiple_of(8) - len - 1)?;
+                        labels.push((value, label));
+                    }
+                    if reader.i32()? != 4 {
+                        bail!("Value labels are not followed by their variables");
+                    }
+                    let count = reader.u32()? as usize;
+                    let indices = (0..count)
+                        .map(|_| reader.u32().map(|idx| (idx as usize).saturating_sub(1)))
+                        .collect::<AppResult<Vec<_>>>()?;
+                    value_labels.push((labels, indices));
+                }
+                6 => {
+                    let lines = reader.u32()? as usize;
+                    reader.skip(lines * 80)?;
+                }
+                7 => {
+                    let subtype = reader.i32()?;
+                    let size = reader.u32()? as usize;
+                    let count = reader.u32()? as usize;
+                    let data = reader.bytes(size * count)?;
+                    match subtype {
+                        3 if encoding.is_none() => {
+                            let character_code = BinaryReader::new(data, order).at(28).i32()?;
+                            encoding = code_page_encoding(character_code);
+                        }
+                        4 => sysmis = BinaryReader::new(data, order).f64()?,
+                        13 => long_names = key_values(data),
+                        14 => very_long_strings = key_values(data),
+                        20 => encoding = Encoding::for_label(data),
+                        21 => long_string_labels = long_string_value_labels(data, order)?,
+                        _ => (),
+                    }
+                }
+                999 => {
+                    reader.skip(4)?;
+                    break;
+                }
+                rec_type => bail!("Unknown SPSS record type {rec_type}"),
+            }
+        }
+        let encoding = encoding.unwrap_or(WINDOWS_1252);
+
+        for (labels, indices) in value_labels {
+            for idx in indices {
+                if let Some(Some(var)) = slots.get_mut(idx) {
+                    var.value_labels.extend(labels.iter().map(|(value, label)| {
+                        let value = if var.width == 0 {
+                            value_label_key(BinaryReader::new(value, order).f64().unwrap_or(sysmis))
+                        } else {
+                            decode_text(value, encoding)
+                        };
+                        (value, label.clone())
+                    }));
+                }
+            }
+        }
+
+        let case_len = slots.len() * 8;
+        let mut vars = slots.into_iter().flatten().collect::<Vec<_>>();
+        for var in vars.iter_mut() {
+            if let Some(name) = long_names.get(&var.short_name) {
+                var.name = decode_text(name, encoding);
+            }
+        }
+        for (var_name, labels) in long_string_labels {
+            let var_name = decode_text(&var_name, encoding);
+            if let Some(var) = vars
+                .iter_mut()
+                .find(|var| var.name == var_name || var.short_name == var_name)
+            {
+                var.value_labels.extend(
+                    labels
+                        .into_iter()
+                        .map(|(value, label)| (decode_text(&value, encoding), label)),
+                );
+            }
+        }
+
+        let mut variables = Vec::with_capacity(vars.len());
+        let mut vars = vars.into_iter();
+        while let Some(var) = vars.next() {
+            let kind = if var.width == 0 {
+                VarKind::Numeric { format: var.format }
+            } else if let Some(width) = very_long_strings
+                .get(&var.short_name)
+                .and_then(|width| {
+                    std::str::from_utf8(width)
+                        .ok()?
+                        .trim()
+                        .parse::<usize>()
+                        .ok()
+                })
+                .filter(|width| *width > 255)
+            {
+                // Very long strings are written as 255-byte segments holding 252 bytes each
+                let count = width.div_ceil(252);
+                let mut segments = vec![(var.slot * 8, 252)];
+                for segment in vars.by_ref().take(count - 1) {
+                    segments.push((segment.slot * 8, segment.width.min(252)));
+                }
+                VarKind::String { segments }
+            } else {
+                VarKind::String {
+                    segments: vec![(var.slot * 8, var.width)],
+                }
+            };
+            variables.push((kind, var));
+        }
+
+        let data = &buf[reader.pos()..];
+        let data = match (compression, zlib) {
+            (0, _) => data.to_vec(),
+            (1, _) => decompress_bytecode(data, bias, sysmis, order),
+            (2, true) => decompress_bytecode(&inflate_zsav(buf, reader)?, bias, sysmis, order),
+            _ => bail!("Unsupported SPSS compression {compression}"),
+        };
+
+        Ok(Self {
+            order,
+            encoding,
+            sysmis,
+            variables,
+            data,
+            case_len,
+        })
+    }
+
+    fn columns(&self) -> AppResult<Vec<LabeledColumn>> {
+        if self.case_len == 0 {
+            return Ok(Vec::new());
+        }
+        let cases = || self.data.chunks_exact(self.case_len);
+        Ok(self
+            .variables
+            .iter()
+            .map(|(kind, var)| {
+                let values = match kind {
+                    VarKind::Numeric { format } => {
+                        let numbers = cases().map(|case| {
+                            BinaryReader::new(&case[var.slot * 8..], self.order)
+                                .f64()
+                                .ok()
+                                .filter(|value| *value != self.sysmis && !value.is_nan())
+                        });
+                        match format {
+                            20 | 23 | 24 | 28 | 29 | 30 | 38 | 39 => ColumnValues::Date(
+                                numbers
+                                    .map(|secs| {
+                                        secs.map(|secs| {
+                                            ((secs - EPOCH_OFFSET_SECS) / 86_400.0).floor() as i32
+                                        })
+                                    })
+                                    .collect(),
+                            ),
+                            22 | 41 => ColumnValues::Datetime(
+                                numbers
+                                    .map(|secs| {
+                                        secs.map(|secs| {
+                                            ((secs - EPOCH_OFFSET_SECS) * 1000.0).round() as i64
+                                        })
+                                    })
+                                    .collect(),
+                            ),
+                            _ => ColumnValues::Float64(numbers.collect()),
+                        }
+                    }
+                    VarKind::String { segments } => ColumnValues::String(
+                        cases()
+                            .map(|case| {
+                                let bytes = segments
+                                    .iter()
+                                    .filter_map(|(offset, len)| case.get(*offset..offset + len))
+                                    .flatten()
+                                    .copied()
+                                    .collect::<Vec<_>>();
+                                Some(decode_text(&bytes, self.encoding))
+                            })
+                            .collect(),
+                    ),
+                };
+                LabeledColumn {
+                    name: var.name.clone(),
+                    values,
+                    metadata: FieldMetadata::default()
+                        .with_label(decode_text(&var.label, self.encoding))
+                        .with_value_labels(
+                            var.value_labels
+                                .iter()
+                                .map(|(value, label)| {
+                                    (value.clone(), decode_text(label, self.encoding))
+                                })
+                                .collect(),
+                        ),
+                }
+            })
+            .collect())
+    }
+}
+
+/// Expands the bytecode compressed cases, where every 8-byte block of opcodes is followed by
+/// the raw values that some of its opcodes refer to.
+fn decompress_bytecode(data: &[u8], bias: f64, sysmis: f64, order: ByteOrder) -> Vec<u8> {
+    let to_bytes = |value: f64| match order {
+        ByteOrder::Little => value.to_le_bytes(),
+        ByteOrder::Big => value.to_be_bytes(),
+    };
+    let mut out = Vec::with_capacity(data.len() * 2);
+    let mut pos = 0;
+    'blocks: while let Some(opcodes) = data.get(pos..pos + 8) {
+        pos += 8;
+        for opcode in opcodes {
+            match opcode {
+                0 => (),
+                1..=251 => out.extend(to_bytes(*opcode as f64 - bias)),
+                252 => break 'blocks,
+                253 => match data.get(pos..pos + 8) {
+                    Some(raw) => {
+                        out.extend(raw);
+                        pos += 8;
+                    }
+                    None => break 'blocks,
+                },
+                254 => out.extend([b' '; 8]),
+                255 => out.extend(to_bytes(sysmis)),
+            }
+        }
+    }
+    out
+}
+
+/// Inflates the zlib blocks of a `.zsav` file into a bytecode compressed stream.
+fn inflate_zsav(buf: &[u8], mut reader: BinaryReader) -> AppResult<Vec<u8>> {
+    reader.skip(8)?;
+    let trailer_offset = reader.u64()? as usize;
+    reader.seek(trailer_offset + 20);
+    let blocks = reader.u32()? as usize;
+    let mut out = Vec::new();
+    for _ in 0..blocks {
+        reader.skip(8)?;
+        let offset = reader.u64()? as usize;
+        reader.skip(4)?;
+        let len = reader.u32()? as usize;
+        let block = BinaryReader::new(buf, reader.order())
+            .at(offset)
+            .bytes(len)?;
+        ZlibDecoder::new(block).read_to_end(&mut out)?;
+    }
+    Ok(out)
+}
+
+/// Parses `key=value` pairs separated by tabs, as used by the long variable name and the very
+/// long string records.
+fn key_values(data: &[u8]) -> HashMap<String, Vec<u8>> {
+    data.split(|byte| *byte == b'\t')
+        .filter_map(|pair| {
+            let pair = pair.strip_suffix(b"\0").unwrap_or(pair);
+            let idx = pair.iter().position(|byte| *byte == b'=')?;
+            Some((
+                String::from_utf8_lossy(&pair[..idx]).trim().to_owned(),
+                pair[idx + 1..].to_vec(),
+            ))
+        })
+        .collect()
+}
+
+type LongStringLabels = Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)>;
+
+fn long_string_value_labels(data: &[u8], order: ByteOrder) -> AppResult<LongStringLabels> {
+    let mut reader = BinaryReader::new(data, order);
+    let mut vars = Vec::new();
+    while !reader.is_empty() {
+        let len = reader.u32()? as usize;
+        let name = reader.bytes(len)?.to_vec();
+        reader.skip(4)?;
+        let count = reader.u32()? as usize;
+        let mut labels = Vec::with_capacity(count);
+        for _ in 0..count {
+            let len = reader.u32()? as usize;
+            let value = reader.bytes(len)?.to_vec();
+            let len = reader.u32()? as usize;
+            let label = reader.bytes(len)?.to_vec();
+            labels.push((value, label));
+        }
+        vars.push((name, labels));
+    }
+    Ok(vars)
+}
+
+fn code_page_encoding(code: i32) -> Option<&'static Encoding> {
+    match code {
+        65001 => Some(UTF_8),
+        ..=4 => None,
+        code => Encoding::for_label(format!("cp{code}").as_bytes()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{Compression, write::ZlibEncoder};
+    use polars::prelude::{DataType, PlSmallStr};
+
+    use super::*;
+    use crate::io::reader::NamedFrame;
+
+    const SYSMIS: f64 = -f64::MAX;
+
+    fn i32s(values: &[i32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    fn variable(var_type: i32, format: i32, name: &[u8; 8], label: &[u8]) -> Vec<u8> {
+        let print = (format << 16) | (8 << 8);
+        let mut record = i32s(&[2, var_type, !label.is_empty() as i32, 0, print, print]);
+        record.extend_from_slice(name);
+        if !label.is_empty() {
+            record.extend(i32s(&[label.len() as i32]));
+            record.extend_from_slice(label);
+            record.resize(
+                record.len() + label.len().next_multiple_of(4) - label.len(),
+                0,
+            );
+        }
+        record
+    }
+
+    fn extension(subtype: i32, data: &[u8]) -> Vec<u8> {
+        [&i32s(&[7, subtype, 1, data.len() as i32])[..], data].concat()
+    }
+
+    /// The dictionary of `score` (labeled numbers), `name` (8 characters) and `born` (a date).
+    fn dictionary(compression: i32) -> Vec<u8> {
+        let mut buf = if compression == 2 { b"$FL3" } else { b"$FL2" }.to_vec();
+        buf.resize(64, b' ');
+        buf.extend(i32s(&[2, 3, compression, 0, 2]));
+        buf.extend(100.0_f64.to_le_bytes());
+        buf.resize(176, 0);
+
+        buf.extend(variable(0, 5, b"SCORE   ", b"Test score"));
+        buf.extend(variable(8, 1, b"NAME    ", b""));
+        buf.extend(variable(0, 20, b"BORN    ", b""));
+        buf.extend(i32s(&[3, 2]));
+        for (value, label) in [(1.0_f64, b"low".as_slice()), (2.0, b"high")] {
+            buf.extend(value.to_le_bytes());
+            buf.push(label.len() as u8);
+            buf.extend_from_slice(label);
+            buf.resize(
+                buf.len() + (label.len() + 1).next_multiple_of(8) - label.len() - 1,
+                0,
+            );
+        }
+        buf.extend(i32s(&[4, 1, 1]));
+        buf.extend(extension(20, b"UTF-8"));
+        buf.extend(extension(13, b"SCORE=score\tNAME=name\tBORN=born"));
+        buf.extend(i32s(&[999, 0]));
+        buf
+    }
+
+    fn cases() -> Vec<u8> {
+        [
+            &1.0_f64.to_le_bytes()[..],
+            b"ann     ",
+            &EPOCH_OFFSET_SECS.to_le_bytes(),
+            &SYSMIS.to_le_bytes(),
+            b"bob     ",
+            &SYSMIS.to_le_bytes(),
+        ]
+        .concat()
+    }
+
+    fn bytecode() -> Vec<u8> {
+        [
+            &[101, 253, 253, 255, 253, 255, 0, 0][..],
+            b"ann     ",
+            &EPOCH_OFFSET_SECS.to_le_bytes(),
+            b"bob     ",
+            &[252, 0, 0, 0, 0, 0, 0, 0],
+        ]
+        .concat()
+    }
+
+    fn zsav() -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&bytecode()).unwrap();
+        let block = encoder.finish().unwrap();
+
+        let mut buf = dictionary(2);
+        let header = buf.len() as u64;
+        let trailer = header + 24 + block.len() as u64;
+        for value in [header, trailer, 48] {
+            buf.extend(value.to_le_bytes());
+        }
+        buf.extend(&block);
+        buf.extend((-100_i64).to_le_bytes());
+        buf.extend(0_i64.to_le_bytes());
+        buf.extend(i32s(&[0x3ff000, 1]));
+        buf.extend(header.to_le_bytes());
+        buf.extend((header + 24).to_le_bytes());
+        buf.extend(i32s(&[bytecode().len() as i32, block.len() as i32]));
+        buf
+    }
+
+    fn read(buf: &[u8], apply_value_labels: bool) -> NamedFrame {
+        let columns = Sav::parse(buf).unwrap().columns().unwrap();
+        labeled_data_frame("spss", columns, apply_value_labels).unwrap()
+    }
+
+    #[test]
+    fn test_read_sav() {
+        for buf in [
+            [dictionary(0), cases()].concat(),
+            [dictionary(1), bytecode()].concat(),
+            zsav(),
+        ] {
+            let frame = read(&buf, false);
+            let df = frame.data_frame();
+            assert_eq!(
+                df.get_column_names()
+                    .into_iter()
+                    .map(PlSmallStr::as_str)
+                    .collect::<Vec<_>>(),
+                ["score", "name", "born"]
+            );
+            assert_eq!(
+                df.dtypes(),
+                [DataType::Float64, DataType::String, DataType::Date]
+            );
+            assert_eq!(
+                df.column("score").unwrap().f64().unwrap().to_vec(),
+                [Some(1.0), None]
+            );
+            assert_eq!(
+                df.column("name")
+                    .unwrap()
+                    .str()
+                    .unwrap()
+                    .iter()
+                    .collect::<Vec<_>>(),
+                [Some("ann"), Some("bob")]
+            );
+            assert_eq!(
+                df.column("born")
+                    .unwrap()
+                    .date()
+                    .unwrap()
+                    .physical()
+                    .to_vec(),
+                [Some(0), None]
+            );
+
+            let score = frame.metadata().field("score").unwrap();
+            assert_eq!(score.label(), Some("Test score"));
+            assert_eq!(
+                score.value_labels(),
+                [
+                    ("1".to_owned(), "low".to_owned()),
+                    ("2".to_owned(), "high".to_owned())
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply_value_labels() {
+        let frame = read(&[dictionary(0), cases()].concat(), true);
+        let score = frame
+            .data_frame()
+            .column("score")
+            .unwrap()
+            .cast(&DataType::String)
+            .unwrap();
+        assert_eq!(
+            score.str().unwrap().iter().collect::<Vec<_>>(),
+            [Some("low"), None]
+        );
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        assert!(Sav::parse(b"PAR1").is_err());
+        assert!(Sav::parse(&[dictionary(0), cases()].concat()[..200]).is_err());
+    }
+}
@@ -10,7 +10,7 @@ use tempfile::NamedTempFile;
 
 use crate::{AppResult, args::Args, io::reader::ReaderSource, misc::stdin::stdin};
 
-use super::{DataFrameReader, NamedFrames};
+use super::{DataFrameReader, NamedFrame, NamedFrames};
 
 #[derive(Debug, Default)]
 pub struct SqliteToDataFrames {
@@ -61,7 +61,7 @@ fn path_to_name_frames(path: impl AsRef<Path>, key: Option<&str>) -> AppResult<N
         .into_iter()
         .map(|name| {
             let df = get_data_frame(&conn, &name)?;
-            Ok((name, df))
+            Ok(NamedFrame::new(name, df))
         })
         .collect::<AppResult<Vec<_>>>()
         .map(|vec| vec.into_boxed_slice())
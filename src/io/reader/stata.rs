@@ -0,0 +1,744 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail};
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+
+use crate::{
+    AppResult,
+    args::Args,
+    io::reader::{
+        DataFrameReader, NamedFrames, ReaderSource,
+        source::read_all,
+        statistical::{
+            BinaryReader, ByteOrder, ColumnValues, LabeledColumn, decode_text, labeled_data_frame,
+        },
+    },
+    misc::metadata::{FieldMetadata, value_label_key},
+};
+
+/// Days between the Stata epoch (1960-01-01) and the unix epoch
+const EPOCH_OFFSET_DAYS: i32 = 3653;
+
+#[derive(Debug, Default)]
+pub struct StataToDataFrame {
+    apply_value_labels: bool,
+}
+
+impl StataToDataFrame {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            apply_value_labels: args.apply_value_labels,
+        }
+    }
+
+    pub fn with_apply_value_labels(mut self, apply_value_labels: bool) -> Self {
+        self.apply_value_labels = apply_value_labels;
+        self
+    }
+}
+
+impl DataFrameReader for StataToDataFrame {
+    fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
+        let buf = read_all(&input)?;
+        let dta = if buf.starts_with(b"<stata_dta>") {
+            Dta::parse(&buf)?
+        } else {
+            Dta::parse_legacy(&buf)?
+        };
+        Ok([labeled_data_frame(
+            input.table_name(),
+            dta.columns(&buf)?,
+            self.apply_value_labels,
+        )?]
+        .into())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum VarType {
+    Str(usize),
+    StrL,
+    Byte,
+    Int,
+    Long,
+    Float,
+    Double,
+}
+
+impl VarType {
+    fn from_legacy_code(code: u8) -> AppResult<Self> {
+        Ok(match code {
+            1..=244 => VarType::Str(code as usize),
+            251 => VarType::Byte,
+            252 => VarType::Int,
+            253 => VarType::Long,
+            254 => VarType::Float,
+            255 => VarType::Double,
+            _ => bail!("Unknown Stata variable type {code}"),
+        })
+    }
+
+    fn from_code(code: u16) -> AppResult<Self> {
+        Ok(match code {
+            1..=2045 => VarType::Str(code as usize),
+            32768 => VarType::StrL,
+            65526 => VarType::Double,
+            65527 => VarType::Float,
+            65528 => VarType::Long,
+            65529 => VarType::Int,
+            65530 => VarType::Byte,
+            _ => bail!("Unknown Stata variable type {code}"),
+        })
+    }
+
+    fn width(&self) -> usize {
+        match self {
+            VarType::Str(width) => *width,
+            VarType::StrL | VarType::Double => 8,
+            VarType::Long | VarType::Float => 4,
+            VarType::Int => 2,
+            VarType::Byte => 1,
+        }
+    }
+
+    /// Reads a numeric cell, mapping Stata's missing values (`.`, `.a`, ..., `.z`) to `None`.
+    fn number(&self, cell: &[u8], order: ByteOrder) -> Option<f64> {
+        let mut reader = BinaryReader::new(cell, order);
+        match self {
+            VarType::Byte => reader.i8().ok().filter(|v| *v <= 100).map(f64::from),
+            VarType::Int => reader.i16().ok().filter(|v| *v <= 32740).map(f64::from),
+            VarType::Long => reader
+                .i32()
+                .ok()
+                .filter(|v| *v <= 2147483620)
+                .map(f64::from),
+            VarType::Float => reader.f32().ok().filter(|v| *v <= 1.701e38).map(f64::from),
+            VarType::Double => reader.f64().ok().filter(|v| *v <= 8.988e307),
+            VarType::Str(_) | VarType::StrL => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Variable {
+    name: String,
+    var_type: VarType,
+    format: String,
+    value_label_name: String,
+    label: String,
+}
+
+#[derive(Debug)]
+struct Dta {
+    release: u16,
+    order: ByteOrder,
+    encoding: &'static Encoding,
+    variables: Vec<Variable>,
+    nobs: usize,
+    data_offset: usize,
+    strls: HashMap<(u64, u64), String>,
+    value_labels: HashMap<String, Vec<(String, String)>>,
+}
+
+impl Dta {
+    /// Parses releases 117 to 119, which wrap the sections of the file in tags.
+    fn parse(buf: &[u8]) -> AppResult<Self> {
+        let mut reader = BinaryReader::new(buf, ByteOrder::Little);
+        reader.expect(b"<stata_dta><header><release>")?;
+        let release = std::str::from_utf8(reader.bytes(3)?)?.parse::<u16>()?;
+        if !(117..=119).contains(&release) {
+            bail!("Unsupported Stata release {release}");
+        }
+        reader.expect(b"</release><byteorder>")?;
+        let order = match reader.bytes(3)? {
+            b"MSF" => ByteOrder::Big,
+            b"LSF" => ByteOrder::Little,
+            _ => bail!("Invalid byte order"),
+        };
+        let mut reader = BinaryReader::new(buf, order).at(reader.pos());
+        let encoding = if release == 117 { WINDOWS_1252 } else { UTF_8 };
+        let name_len = if release == 117 { 33 } else { 129 };
+
+        reader.expect(b"</byteorder><K>")?;
+        let nvar = if release == 119 {
+            reader.u32()? as usize
+        } else {
+            reader.u16()? as usize
+        };
+        reader.expect(b"</K><N>")?;
+        let nobs = if release == 117 {
+            reader.u32()? as usize
+        } else {
+            reader.u64()? as usize
+        };
+        reader.expect(b"</N><label>")?;
+        let label_len = if release == 117 {
+            reader.u8()? as usize
+        } else {
+            reader.u16()? as usize
+        };
+        reader.skip(label_len)?;
+        reader.expect(b"</label><timestamp>")?;
+        let timestamp_len = reader.u8()? as usize;
+        reader.skip(timestamp_len)?;
+        reader.expect(b"</timestamp></header><map>")?;
+        let map = (0..14)
+            .map(|_| reader.u64().map(|offset| offset as usize))
+            .collect::<AppResult<Vec<_>>>()?;
+        reader.expect(b"</map><variable_types>")?;
+        let types = (0..nvar)
+            .map(|_| reader.u16().and_then(VarType::from_code))
+            .collect::<AppResult<Vec<_>>>()?;
+        reader.expect(b"</variable_types><varnames>")?;
+        let names = texts(&mut reader, nvar, name_len, encoding)?;
+        reader.expect(b"</varnames><sortlist>")?;
+        reader.skip((nvar + 1) * if release == 119 { 4 } else { 2 })?;
+        reader.expect(b"</sortlist><formats>")?;
+        let formats = texts(
+            &mut reader,
+            nvar,
+            if release == 117 { 49 } else { 57 },
+            encoding,
+        )?;
+        reader.expect(b"</formats><value_label_names>")?;
+        let value_label_names = texts(&mut reader, nvar, name_len, encoding)?;
+        reader.expect(b"</value_label_names><variable_labels>")?;
+        let labels = texts(
+            &mut reader,
+            nvar,
+            if release == 117 { 81 } else { 321 },
+            encoding,
+        )?;
+        reader.expect(b"</variable_labels>")?;
+
+        reader.seek(map[9]);
+        reader.expect(b"<data>")?;
+        let data_offset = reader.pos();
+
+        reader.seek(map[10]);
+        reader.expect(b"<strls>")?;
+        let mut strls = HashMap::new();
+        while reader.clone().bytes(3).is_ok_and(|tag| tag == b"GSO") {
+            reader.skip(3)?;
+            let v = reader.u32()? as u64;
+            let o = if release == 117 {
+                reader.u32()? as u64
+            } else {
+                reader.u64()?
+            };
+            let binary = reader.u8()? == 129;
+            let len = reader.u32()? as usize;
+            let bytes = reader.bytes(len)?;
+            let text = if binary {
+                String::from_utf8_lossy(bytes).into_owned()
+            } else {
+                decode_text(bytes, encoding)
+            };
+            strls.insert((v, o), text);
+        }
+
+        reader.seek(map[11]);
+        reader.expect(b"<value_labels>")?;
+        let mut value_labels = HashMap::new();
+        while reader.clone().bytes(5).is_ok_and(|tag| tag == b"<lbl>") {
+            reader.skip(5)?;
+            let len = reader.u32()? as usize;
+            let name = decode_text(reader.bytes(name_len)?, encoding);
+            reader.skip(3)?;
+            let table = value_label_table(reader.bytes(len)?, order, encoding)?;
+            value_labels.insert(name, table);
+            reader.expect(b"</lbl>")?;
+        }
+
+        Ok(Self {
+            release,
+            order,
+            encoding,
+            variables: variables(types, names, formats, value_label_names, labels),
+            nobs,
+            data_offset,
+            strls,
+            value_labels,
+        })
+    }
+
+    /// Parses releases 113 to 115, the binary formats written by Stata 8 to 12.
+    fn parse_legacy(buf: &[u8]) -> AppResult<Self> {
+        let release = buf.first().copied().unwrap_or_default() as u16;
+        if !(113..=115).contains(&release) {
+            bail!("Unsupported Stata release {release}");
+        }
+        let order = match buf.get(1) {
+            Some(1) => ByteOrder::Big,
+            Some(2) => ByteOrder::Little,
+            _ => bail!("Invalid byte order"),
+        };
+        let encoding = WINDOWS_1252;
+        let mut reader = BinaryReader::new(buf, order).at(4);
+        let nvar = reader.u16()? as usize;
+        let nobs = reader.u32()? as usize;
+        reader.skip(81 + 18)?;
+        let types = (0..nvar)
+            .map(|_| reader.u8().and_then(VarType::from_legacy_code))
+            .collect::<AppResult<Vec<_>>>()?;
+        let names = texts(&mut reader, nvar, 33, encoding)?;
+        reader.skip((nvar + 1) * 2)?;
+        let formats = texts(
+            &mut reader,
+            nvar,
+            if release == 113 { 12 } else { 49 },
+            encoding,
+        )?;
+        let value_label_names = texts(&mut reader, nvar, 33, encoding)?;
+        let labels = texts(&mut reader, nvar, 81, encoding)?;
+        loop {
+            let data_type = reader.u8()?;
+            let len = reader.u32()? as usize;
+            if data_type == 0 && len == 0 {
+                break;
+            }
+            reader.skip(len)?;
+        }
+        let data_offset = reader.pos();
+
+        let row_len = types.iter().map(VarType::width).sum::<usize>();
+        reader.skip(nobs * row_len)?;
+        let mut value_labels = HashMap::new();
+        while !reader.is_empty() {
+            let len = reader.u32()? as usize;
+            let name = decode_text(reader.bytes(33)?, encoding);
+            reader.skip(3)?;
+            let table = value_label_table(reader.bytes(len)?, order, encoding)?;
+            value_labels.insert(name, table);
+        }
+
+        Ok(Self {
+            release,
+            order,
+            encoding,
+            variables: variables(types, names, formats, value_label_names, labels),
+            nobs,
+            data_offset,
+            strls: HashMap::new(),
+            value_labels,
+        })
+    }
+
+    fn columns(&self, buf: &[u8]) -> AppResult<Vec<LabeledColumn>> {
+        let row_len = self
+            .variables
+            .iter()
+            .map(|var| var.var_type.width())
+            .sum::<usize>();
+        if row_len == 0 {
+            return Ok(Vec::new());
+        }
+        let data = self
+            .data_offset
+            .checked_add(self.nobs * row_len)
+            .and_then(|end| buf.get(self.data_offset..end))
+            .ok_or(anyhow!("Unexpected end of file"))?;
+
+        let mut offset = 0;
+        let mut columns = Vec::with_capacity(self.variables.len());
+        for var in self.variables.iter() {
+            let width = var.var_type.width();
+            let cells = data
+                .chunks_exact(row_len)
+                .map(|row| &row[offset..offset + width]);
+            let number = |cell| var.var_type.number(cell, self.order);
+            let values = match var.var_type {
+                VarType::Str(_) => ColumnValues::String(
+                    cells
+                        .map(|cell| Some(decode_text(cell, self.encoding)))
+                        .collect(),
+                ),
+                VarType::StrL => ColumnValues::String(
+                    cells
+                        .map(|cell| self.strl(cell))
+                        .collect::<AppResult<_>>()?,
+                ),
+                _ if is_date_format(&var.format) => ColumnValues::Date(
+                    cells
+                        .map(|cell| number(cell).map(|days| days as i32 - EPOCH_OFFSET_DAYS))
+                        .collect(),
+                ),
+                _ if is_datetime_format(&var.format) => ColumnValues::Datetime(
+                    cells
+                        .map(|cell| {
+                            number(cell).map(|ms| {
+                                ms as i64 - EPOCH_OFFSET_DAYS as i64 * 24 * 60 * 60 * 1000
+                            })
+                        })
+                        .collect(),
+                ),
+                VarType::Byte => {
+                    ColumnValues::Int8(cells.map(|cell| number(cell).map(|v| v as i8)).collect())
+                }
+                VarType::Int => {
+                    ColumnValues::Int16(cells.map(|cell| number(cell).map(|v| v as i16)).collect())
+                }
+                VarType::Long => {
+                    ColumnValues::Int32(cells.map(|cell| number(cell).map(|v| v as i32)).collect())
+                }
+                VarType::Float => ColumnValues::Float32(
+                    cells.map(|cell| number(cell).map(|v| v as f32)).collect(),
+                ),
+                VarType::Double => ColumnValues::Float64(cells.map(number).collect()),
+            };
+            offset += width;
+            columns.push(LabeledColumn {
+                name: var.name.clone(),
+                values,
+                metadata: FieldMetadata::default()
+                    .with_label(var.label.as_str())
+                    .with_value_labels(
+                        self.value_labels
+                            .get(&var.value_label_name)
+                            .cloned()
+                            .unwrap_or_default(),
+                    ),
+            });
+        }
+        Ok(columns)
+    }
+
+    /// Resolves a strL cell, which points into the strls section with a (variable,
+    /// observation) pair.
+    fn strl(&self, cell: &[u8]) -> AppResult<Option<String>> {
+        let mut reader = BinaryReader::new(cell, self.order);
+        let key = match self.release {
+            117 => (reader.u32()? as u64, reader.u32()? as u64),
+            118 => (reader.uint(2)?, reader.uint(6)?),
+            _ => (reader.uint(3)?, reader.uint(5)?),
+        };
+        Ok(match key {
+            (0, 0) => Some(String::new()),
+            key => self.strls.get(&key).cloned(),
+        })
+    }
+}
+
+fn variables(
+    types: Vec<VarType>,
+    names: Vec<String>,
+    formats: Vec<String>,
+    value_label_names: Vec<String>,
+    labels: Vec<String>,
+) -> Vec<Variable> {
+    types
+        .into_iter()
+        .zip(names)
+        .zip(formats)
+        .zip(value_label_names)
+        .zip(labels)
+        .map(
+            |((((var_type, name), format), value_label_name), label)| Variable {
+                name,
+                var_type,
+                format,
+                value_label_name,
+                label,
+            },
+        )
+        .collect()
+}
+
+fn texts(
+    reader: &mut BinaryReader,
+    count: usize,
+    len: usize,
+    encoding: &'static Encoding,
+) -> AppResult<Vec<String>> {
+    (0..count)
+        .map(|_| Ok(decode_text(reader.bytes(len)?, encoding)))
+        .collect()
+}
+
+fn value_label_table(
+    table: &[u8],
+    order: ByteOrder,
+    encoding: &'static Encoding,
+) -> AppResult<Vec<(String, String)>> {
+    let mut reader = BinaryReader::new(table, order);
+    let count = reader.u32()? as usize;
+    let text_len = reader.u32()? as usize;
+    let offsets = (0..count)
+        .map(|_| reader.u32().map(|offset| offset as usize))
+        .collect::<AppResult<Vec<_>>>()?;
+    let values = (0..count)
+        .map(|_| reader.i32())
+        .collect::<AppResult<Vec<_>>>()?;
+    let text = reader.bytes(text_len)?;
+    Ok(values
+        .into_iter()
+        .zip(offsets)
+        .map(|(value, offset)| {
+            (
+                value_label_key(value as f64),
+                decode_text(text.get(offset..).unwrap_or_default(), encoding),
+            )
+        })
+        .collect())
+}
+
+fn is_date_format(format: &str) -> bool {
+    format.starts_with("%td") || format.starts_with("%d")
+}
+
+fn is_datetime_format(format: &str) -> bool {
+    format.starts_with("%tc") || format.starts_with("%tC")
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::{DataType, PlSmallStr};
+
+    use super::*;
+    use crate::io::reader::NamedFrame;
+
+    fn text(text: &str, len: usize) -> Vec<u8> {
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.resize(len, 0);
+        bytes
+    }
+
+    fn value_label_table() -> Vec<u8> {
+        [2_u32, 9, 0, 4, 1, 2]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .chain(*b"low\0high\0")
+            .collect()
+    }
+
+    /// Rows of `score` (a labeled int), `name` (5 characters) and `born` (a date), with a
+    /// trailing `note` strL in the releases that have them.
+    fn rows(strl: Option<&dyn Fn(u64, u64) -> Vec<u8>>) -> Vec<u8> {
+        let mut rows = Vec::new();
+        for (score, name, born, note) in
+            [(1_i16, "ann", 0_i32, (4, 1)), (32741, "bob", 3653, (0, 0))]
+        {
+            rows.extend(score.to_le_bytes());
+            rows.extend(text(name, 5));
+            rows.extend(born.to_le_bytes());
+            if let Some(strl) = strl {
+                rows.extend(strl(note.0, note.1));
+            }
+        }
+        rows
+    }
+
+    fn dta(release: u16) -> Vec<u8> {
+        let (name_len, format_len, label_len) = match release {
+            117 => (33, 49, 81),
+            _ => (129, 57, 321),
+        };
+        let mut buf =
+            format!("<stata_dta><header><release>{release}</release><byteorder>LSF</byteorder><K>")
+                .into_bytes();
+        match release {
+            119 => buf.extend(4_u32.to_le_bytes()),
+            _ => buf.extend(4_u16.to_le_bytes()),
+        }
+        buf.extend(b"</K><N>");
+        match release {
+            117 => buf.extend(2_u32.to_le_bytes()),
+            _ => buf.extend(2_u64.to_le_bytes()),
+        }
+        buf.extend(b"</N><label>");
+        buf.extend(if release == 117 { &[0][..] } else { &[0, 0] });
+        buf.extend(b"</label><timestamp>\0</timestamp></header><map>");
+        let map = buf.len();
+        buf.extend([0; 14 * 8]);
+        buf.extend(b"</map><variable_types>");
+        for code in [65529_u16, 5, 65528, 32768] {
+            buf.extend(code.to_le_bytes());
+        }
+        buf.extend(b"</variable_types><varnames>");
+        for name in ["score", "name", "born", "note"] {
+            buf.extend(text(name, name_len));
+        }
+        buf.extend(b"</varnames><sortlist>");
+        buf.extend(vec![0; 5 * if release == 119 { 4 } else { 2 }]);
+        buf.extend(b"</sortlist><formats>");
+        for format in ["%8.0g", "%5s", "%td", "%9s"] {
+            buf.extend(text(format, format_len));
+        }
+        buf.extend(b"</formats><value_label_names>");
+        for name in ["grades", "", "", ""] {
+            buf.extend(text(name, name_len));
+        }
+        buf.extend(b"</value_label_names><variable_labels>");
+        for label in ["Test score", "", "", ""] {
+            buf.extend(text(label, label_len));
+        }
+        buf.extend(b"</variable_labels><characteristics></characteristics>");
+
+        let mut offsets = vec![0_u64; 14];
+        offsets[9] = buf.len() as u64;
+        buf.extend(b"<data>");
+        buf.extend(rows(Some(&|v: u64, o: u64| {
+            let (v_len, o_len) = match release {
+                117 => (4, 4),
+                118 => (2, 6),
+                _ => (3, 5),
+            };
+            [&v.to_le_bytes()[..v_len], &o.to_le_bytes()[..o_len]].concat()
+        })));
+        buf.extend(b"</data>");
+        offsets[10] = buf.len() as u64;
+        buf.extend(b"<strls>GSO");
+        buf.extend(4_u32.to_le_bytes());
+        match release {
+            117 => buf.extend(1_u32.to_le_bytes()),
+            _ => buf.extend(1_u64.to_le_bytes()),
+        }
+        buf.push(130);
+        buf.extend(10_u32.to_le_bytes());
+        buf.extend(b"long note\0</strls>");
+        offsets[11] = buf.len() as u64;
+        let table = value_label_table();
+        buf.extend(b"<value_labels><lbl>");
+        buf.extend((table.len() as u32).to_le_bytes());
+        buf.extend(text("grades", name_len));
+        buf.extend([0; 3]);
+        buf.extend(table);
+        buf.extend(b"</lbl></value_labels></stata_dta>");
+
+        for (idx, offset) in offsets.into_iter().enumerate() {
+            buf[map + idx * 8..map + idx * 8 + 8].copy_from_slice(&offset.to_le_bytes());
+        }
+        buf
+    }
+
+    fn legacy_dta(release: u8) -> Vec<u8> {
+        let mut buf = vec![release, 2, 1, 0];
+        buf.extend(3_u16.to_le_bytes());
+        buf.extend(2_u32.to_le_bytes());
+        buf.extend([0; 81 + 18]);
+        buf.extend([252, 5, 253]);
+        for name in ["score", "name", "born"] {
+            buf.extend(text(name, 33));
+        }
+        buf.extend([0; 8]);
+        for format in ["%8.0g", "%5s", "%td"] {
+            buf.extend(text(format, if release == 113 { 12 } else { 49 }));
+        }
+        for name in ["grades", "", ""] {
+            buf.extend(text(name, 33));
+        }
+        for label in ["Test score", "", ""] {
+            buf.extend(text(label, 81));
+        }
+        buf.extend([0; 5]);
+        buf.extend(rows(None));
+        let table = value_label_table();
+        buf.extend((table.len() as u32).to_le_bytes());
+        buf.extend(text("grades", 33));
+        buf.extend([0; 3]);
+        buf.extend(table);
+        buf
+    }
+
+    fn read(buf: &[u8]) -> NamedFrame {
+        let dta = if buf.starts_with(b"<stata_dta>") {
+            Dta::parse(buf).unwrap()
+        } else {
+            Dta::parse_legacy(buf).unwrap()
+        };
+        labeled_data_frame("stata", dta.columns(buf).unwrap(), false).unwrap()
+    }
+
+    fn assert_frame(frame: &NamedFrame) {
+        let df = frame.data_frame();
+        assert_eq!(
+            df.dtypes()[..3],
+            [DataType::Int16, DataType::String, DataType::Date]
+        );
+        assert_eq!(
+            df.column("score").unwrap().i16().unwrap().to_vec(),
+            [Some(1), None]
+        );
+        assert_eq!(
+            df.column("name")
+                .unwrap()
+                .str()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            [Some("ann"), Some("bob")]
+        );
+        assert_eq!(
+            df.column("born")
+                .unwrap()
+                .date()
+                .unwrap()
+                .physical()
+                .to_vec(),
+            [Some(-3653), Some(0)]
+        );
+
+        let score = frame.metadata().field("score").unwrap();
+        assert_eq!(score.label(), Some("Test score"));
+        assert_eq!(
+            score.value_labels(),
+            [
+                ("1".to_owned(), "low".to_owned()),
+                ("2".to_owned(), "high".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_dta() {
+        for release in 117..=119 {
+            let frame = read(&dta(release));
+            assert_eq!(
+                frame
+                    .data_frame()
+                    .get_column_names()
+                    .into_iter()
+                    .map(PlSmallStr::as_str)
+                    .collect::<Vec<_>>(),
+                ["score", "name", "born", "note"]
+            );
+            assert_frame(&frame);
+            assert_eq!(
+                frame
+                    .data_frame()
+                    .column("note")
+                    .unwrap()
+                    .str()
+                    .unwrap()
+                    .iter()
+                    .collect::<Vec<_>>(),
+                [Some("long note"), Some("")]
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_legacy_dta() {
+        for release in 113..=115 {
+            let frame = read(&legacy_dta(release));
+            assert_eq!(
+                frame
+                    .data_frame()
+                    .get_column_names()
+                    .into_iter()
+                    .map(PlSmallStr::as_str)
+                    .collect::<Vec<_>>(),
+                ["score", "name", "born"]
+            );
+            assert_frame(&frame);
+        }
+    }
+
+    #[test]
+    fn test_rejects_unsupported_releases() {
+        assert!(Dta::parse_legacy(&legacy_dta(112)).is_err());
+        let mut buf = dta(118);
+        buf[28..31].copy_from_slice(b"120");
+        assert!(Dta::parse(&buf).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! Building blocks shared by the SAS, SPSS and Stata readers.
+
+use anyhow::anyhow;
+use encoding_rs::Encoding;
+use polars::{
+    frame::DataFrame,
+    prelude::{Column, DataType, IntoColumn, NamedFrom, TimeUnit},
+    series::Series,
+};
+
+use crate::{
+    AppResult,
+    io::reader::NamedFrame,
+    misc::metadata::{FieldMetadata, TableMetadata},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ByteOrder {
+    Little,
+    Big,
+}
+
+/// A cursor over an in-memory binary file that reads numbers in the file's byte order.
+#[derive(Debug, Clone)]
+pub(super) struct BinaryReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    order: ByteOrder,
+}
+
+macro_rules! read_number {
+    ($name:ident, $ty:ty) => {
+        pub fn $name(&mut self) -> AppResult<$ty> {
+            let bytes = self.bytes(size_of::<$ty>())?.try_into()?;
+            Ok(match self.order {
+                ByteOrder::Little => <$ty>::from_le_bytes(bytes),
+                ByteOrder::Big => <$ty>::from_be_bytes(bytes),
+            })
+        }
+    };
+}
+
+impl<'a> BinaryReader<'a> {
+    pub fn new(buf: &'a [u8], order: ByteOrder) -> Self {
+        Self { buf, pos: 0, order }
+    }
+
+    pub fn at(&self, pos: usize) -> Self {
+        Self {
+            buf: self.buf,
+            pos,
+            order: self.order,
+        }
+    }
+
+    pub fn order(&self) -> ByteOrder {
+        self.order
+    }
+
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn seek(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.buf.len()
+    }
+
+    pub fn skip(&mut self, len: usize) -> AppResult<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    pub fn bytes(&mut self, len: usize) -> AppResult<&'a [u8]> {
+        let bytes = self
+            .pos
+            .checked_add(len)
+            .and_then(|end| self.buf.get(self.pos..end))
+            .ok_or(anyhow!("Unexpected end of file"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    /// Consumes `tag` if the next bytes match it, otherwise fails with an error.
+    pub fn expect(&mut self, tag: &[u8]) -> AppResult<()> {
+        if self.bytes(tag.len())? == tag {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Expected '{}' at offset {}",
+                String::from_utf8_lossy(tag),
+                self.pos - tag.len()
+            ))
+        }
+    }
+
+    /// Reads an unsigned integer of `len` bytes, where `len` is at most eight.
+    pub fn uint(&mut self, len: usize) -> AppResult<u64> {
+        let bytes = self.bytes(len)?;
+        Ok(match self.order {
+            ByteOrder::Little => bytes
+                .iter()
+                .rev()
+                .fold(0, |acc, byte| (acc << 8) | *byte as u64),
+            ByteOrder::Big => bytes.iter().fold(0, |acc, byte| (acc << 8) | *byte as u64),
+        })
+    }
+
+    read_number!(u8, u8);
+    read_number!(i8, i8);
+    read_number!(u16, u16);
+    read_number!(i16, i16);
+    read_number!(u32, u32);
+    read_number!(i32, i32);
+    read_number!(u64, u64);
+    read_number!(f32, f32);
+    read_number!(f64, f64);
+}
+
+/// Decodes a fixed-width text field, dropping the trailing padding.
+pub(super) fn decode_text(bytes: &[u8], encoding: &'static Encoding) -> String {
+    let bytes = match bytes.iter().position(|byte| *byte == 0) {
+        Some(end) => &bytes[..end],
+        None => bytes,
+    };
+    encoding
+        .decode_without_bom_handling(bytes)
+        .0
+        .trim_end()
+        .to_owned()
+}
+
+#[derive(Debug)]
+pub(super) enum ColumnValues {
+    Int8(Vec<Option<i8>>),
+    Int16(Vec<Option<i16>>),
+    Int32(Vec<Option<i32>>),
+    Float32(Vec<Option<f32>>),
+    Float64(Vec<Option<f64>>),
+    String(Vec<Option<String>>),
+    /// Days since the unix epoch
+    Date(Vec<Option<i32>>),
+    /// Milliseconds since the unix epoch
+    Datetime(Vec<Option<i64>>),
+}
+
+impl ColumnValues {
+    fn into_series(self, name: &str) -> AppResult<Series> {
+        let name = name.into();
+        Ok(match self {
+            ColumnValues::Int8(values) => Series::new(name, values),
+            ColumnValues::Int16(values) => Series::new(name, values),
+            ColumnValues::Int32(values) => Series::new(name, values),
+            ColumnValues::Float32(values) => Series::new(name, values),
+            ColumnValues::Float64(values) => Series::new(name, values),
+            ColumnValues::String(values) => Series::new(name, values),
+            ColumnValues::Date(values) => Series::new(name, values).cast(&DataType::Date)?,
+            ColumnValues::Datetime(values) => {
+                Series::new(name, values).cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?
+            }
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct LabeledColumn {
+    pub name: String,
+    pub values: ColumnValues,
+    pub metadata: FieldMetadata,
+}
+
+/// Builds the data frame along with the labels of its columns. With `apply_value_labels`, labeled columns are replaced by categorical columns of
+/// their labels.
+pub(super) fn labeled_data_frame(
+    name: impl Into<String>,
+    columns: Vec<LabeledColumn>,
+    apply_value_labels: bool,
+) -> AppResult<NamedFrame> {
+    let mut table = TableMetadata::default();
+    let mut cols = Vec::<Column>::with_capacity(columns.len());
+    for LabeledColumn {
+        name,
+        values,
+        metadata,
+    } in columns
+    {
+        let mut series = values.into_series(&name)?;
+        if apply_value_labels && !metadata.value_labels().is_empty() {
+            series = metadata.apply_value_labels(&series)?;
+        }
+        cols.push(series.into_column());
+        table = table.with_field(name, metadata);
+    }
+    let df = DataFrame::new_infer_height(cols)?;
+    Ok(NamedFrame::new(name, df).with_metadata(table))
+}
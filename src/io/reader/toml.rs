@@ -0,0 +1,157 @@
+use anyhow::anyhow;
+use toml::{Table, Value};
+
+use crate::{
+    AppResult,
+    args::Args,
+    io::reader::{
+        DataFrameReader, NamedFrame, NamedFrames, ReaderSource, json_line::records_to_data_frame,
+    },
+    misc::encoding::TextEncoding,
+};
+
+#[derive(Debug)]
+pub struct TomlToDataFrame {
+    ignore_errors: bool,
+    encoding: TextEncoding,
+}
+
+impl TomlToDataFrame {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            ignore_errors: args.ignore_errors,
+            encoding: TextEncoding::from_args(args),
+        }
+    }
+
+    pub fn with_encoding(mut self, encoding: TextEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+}
+
+impl Default for TomlToDataFrame {
+    fn default() -> Self {
+        Self {
+            ignore_errors: true,
+            encoding: Default::default(),
+        }
+    }
+}
+
+impl DataFrameReader for TomlToDataFrame {
+    fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
+        let contents = input.read_to_string(self.encoding)?;
+
+        let table = toml::from_str::<Table>(&contents)?;
+        let mut tables = Vec::new();
+        collect_tables(&table, None, &mut tables);
+        if tables.is_empty() {
+            return Err(anyhow!("No array of tables found in the TOML document"));
+        }
+
+        tables
+            .into_iter()
+            .map(|(name, items)| {
+                let df = records_to_data_frame(
+                    items.iter().map(toml_to_json).collect(),
+                    self.ignore_errors,
+                )?;
+                Ok(NamedFrame::new(name, df))
+            })
+            .collect::<AppResult<Vec<_>>>()
+            .map(Vec::into_boxed_slice)
+    }
+}
+
+/// Collects every array of tables (`[[name]]`), including the ones nested in plain tables,
+/// which are named after their dotted key path.
+fn collect_tables<'a>(
+    table: &'a Table,
+    path: Option<&str>,
+    tables: &mut Vec<(String, &'a [Value])>,
+) {
+    for (key, value) in table {
+        let path = match path {
+            Some(path) => format!("{path}.{key}"),
+            None => key.to_owned(),
+        };
+        match value {
+            Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_table) => {
+                tables.push((path, items))
+            }
+            Value::Table(table) => collect_tables(table, Some(&path), tables),
+            _ => (),
+        }
+    }
+}
+
+fn toml_to_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::String(s) => serde_json::Value::from(s.as_str()),
+        Value::Integer(i) => serde_json::Value::from(*i),
+        Value::Float(f) => serde_json::Number::from_f64(*f)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        Value::Boolean(b) => serde_json::Value::from(*b),
+        Value::Datetime(dt) => serde_json::Value::from(dt.to_string()),
+        Value::Array(items) => items.iter().map(toml_to_json).collect(),
+        Value::Table(table) => serde_json::Value::Object(
+            table
+                .iter()
+                .map(|(key, value)| (key.to_owned(), toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use polars::prelude::AnyValue;
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    fn read(contents: &str) -> Vec<NamedFrame> {
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        TomlToDataFrame::default()
+            .read_to_data_frames(ReaderSource::File(file.path().to_owned()))
+            .unwrap()
+            .into_vec()
+    }
+
+    #[test]
+    fn test_nested_tables() {
+        let frames = read(
+            "title = \"shop\"\n\n[[items]]\nname = \"pen\"\nprice = 1.5\n\n[[items]]\nname = \"ink\"\nprice = 4.0\n\n[store.staff]\nlead = \"ann\"\n\n[[store.staff.shifts]]\nday = \"mon\"\n",
+        );
+        assert_eq!(
+            frames.iter().map(NamedFrame::name).collect::<Vec<_>>(),
+            ["items", "store.staff.shifts"]
+        );
+        assert_eq!(frames[0].data_frame().shape(), (2, 2));
+        assert_eq!(
+            frames[0]
+                .data_frame()
+                .column("price")
+                .unwrap()
+                .get(1)
+                .unwrap(),
+            AnyValue::Float64(4.0)
+        );
+        assert_eq!(frames[1].data_frame().height(), 1);
+    }
+
+    #[test]
+    fn test_missing_keys() {
+        let frames = read("[[rows]]\na = 1\nb = \"x\"\n\n[[rows]]\na = 2\n\n[[rows]]\nc = true\n");
+        let df = frames[0].data_frame();
+        assert_eq!(df.shape(), (3, 3));
+        assert_eq!(df.column("a").unwrap().null_count(), 1);
+        assert_eq!(df.column("b").unwrap().null_count(), 2);
+        assert_eq!(df.column("c").unwrap().null_count(), 2);
+    }
+}
@@ -2,20 +2,64 @@ use std::path::Path;
 
 use polars::frame::DataFrame;
 
+#[cfg(feature = "duckdb")]
+use crate::io::reader::DuckDbToDataFrames;
 use crate::{
     AppResult,
     args::{Args, Format},
     io::{
         reader::ReaderSource,
         reader::{
-            ArrowIpcToDataFrame, AvroToDataFrame, CsvToDataFrame, ExcelToDataFrames,
-            FwfToDataFrame, HtmlToDataFrame, JsonLineToDataFrame, JsonToDataFrame,
-            LogfmtToDataFrame, MarkdownToDataFrame, ParquetToDataFrame, SqliteToDataFrames,
+            ArrowIpcToDataFrame, AvroToDataFrame, CsvToDataFrame, DeltaToDataFrame,
+            ExcelToDataFrames, FwfToDataFrame, HtmlToDataFrame, IcebergToDataFrame,
+            JsonLineToDataFrame, JsonToDataFrame, LogfmtToDataFrame, MarkdownToDataFrame,
+            ParquetToDataFrame, RegexToDataFrame, SasToDataFrame, SpssToDataFrame,
+            SqliteToDataFrames, StataToDataFrame, TomlToDataFrame, YamlToDataFrame,
         },
     },
+    misc::{metadata::TableMetadata, remote_load::Reader},
 };
 
-pub type NamedFrame = (String, DataFrame);
+/// A data frame read from a source, with the name it is given and the metadata found next
+/// to it.
+#[derive(Debug, Clone)]
+pub struct NamedFrame {
+    name: String,
+    data_frame: DataFrame,
+    metadata: TableMetadata,
+}
+
+impl NamedFrame {
+    pub fn new(name: impl Into<String>, data_frame: DataFrame) -> Self {
+        Self {
+            name: name.into(),
+            data_frame,
+            metadata: Default::default(),
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: TableMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn data_frame(&self) -> &DataFrame {
+        &self.data_frame
+    }
+
+    pub fn metadata(&self) -> &TableMetadata {
+        &self.metadata
+    }
+
+    pub fn into_parts(self) -> (String, DataFrame, TableMetadata) {
+        (self.name, self.data_frame, self.metadata)
+    }
+}
+
 pub type NamedFrames = Box<[NamedFrame]>;
 
 pub trait DataFrameReader {
@@ -23,46 +67,74 @@ pub trait DataFrameReader {
 }
 
 pub trait BuildReader {
-    fn build_reader(&self, path: impl AsRef<Path>) -> AppResult<Box<dyn DataFrameReader>>;
+    fn build_reader(&self, path: impl AsRef<Path>) -> AppResult<Box<dyn Reader>>;
 }
 
 impl BuildReader for Args {
-    fn build_reader(&self, path: impl AsRef<Path>) -> AppResult<Box<dyn DataFrameReader>> {
+    fn build_reader(&self, path: impl AsRef<Path>) -> AppResult<Box<dyn Reader>> {
         match self.format {
             Some(Format::Dsv) | Some(Format::Csv) => Ok(Box::new(CsvToDataFrame::from_args(self))),
             Some(Format::Tsv) => Ok(Box::new(
                 CsvToDataFrame::from_args(self).with_separator('\t'),
             )),
-            Some(Format::Parquet) => Ok(Box::new(ParquetToDataFrame)),
+            Some(Format::Parquet) => Ok(Box::new(ParquetToDataFrame::from_args(self))),
             Some(Format::Json) => Ok(Box::new(JsonToDataFrame::from_args(self))),
             Some(Format::Jsonl) => Ok(Box::new(JsonLineToDataFrame::from_args(self))),
-            Some(Format::Arrow) => Ok(Box::new(ArrowIpcToDataFrame)),
+            Some(Format::Arrow) => Ok(Box::new(ArrowIpcToDataFrame::from_args(self))),
             Some(Format::Fwf) => Ok(Box::new(FwfToDataFrame::from_args(self))),
             Some(Format::Sqlite) => Ok(Box::new(SqliteToDataFrames::from_args(self))),
+            #[cfg(feature = "duckdb")]
+            Some(Format::DuckDb) => Ok(Box::new(DuckDbToDataFrames::from_args(self))),
             Some(Format::Excel) => Ok(Box::new(ExcelToDataFrames::from_args(self))),
             Some(Format::Logfmt) => Ok(Box::new(LogfmtToDataFrame::from_args(self))),
             Some(Format::Avro) => Ok(Box::new(AvroToDataFrame)),
             Some(Format::Html) => Ok(Box::new(HtmlToDataFrame::from_args(self))),
             Some(Format::Markdown) => Ok(Box::new(MarkdownToDataFrame::from_args(self))),
+            Some(Format::Yaml) => Ok(Box::new(YamlToDataFrame::from_args(self))),
+            Some(Format::Toml) => Ok(Box::new(TomlToDataFrame::from_args(self))),
+            Some(Format::Sas) => Ok(Box::new(SasToDataFrame)),
+            Some(Format::Spss) => Ok(Box::new(SpssToDataFrame::from_args(self))),
+            Some(Format::Stata) => Ok(Box::new(StataToDataFrame::from_args(self))),
+            Some(Format::Delta) => Ok(Box::new(DeltaToDataFrame::from_args(self))),
+            Some(Format::Iceberg) => Ok(Box::new(IcebergToDataFrame::from_args(self))),
+            Some(Format::Regex) => Ok(Box::new(RegexToDataFrame::from_args(self))),
+            None if is_table_dir(path.as_ref(), "_delta_log") => {
+                Ok(Box::new(DeltaToDataFrame::from_args(self)))
+            }
+            None if is_table_dir(path.as_ref(), "metadata") => {
+                Ok(Box::new(IcebergToDataFrame::from_args(self)))
+            }
             None => match path.as_ref().extension().and_then(|ext| ext.to_str()) {
                 Some("tsv") => {
                     let reader = CsvToDataFrame::from_args(self).with_separator('\t');
                     Ok(Box::new(reader))
                 }
-                Some("parquet") | Some("pqt") => Ok(Box::new(ParquetToDataFrame)),
+                Some("parquet") | Some("pqt") => Ok(Box::new(ParquetToDataFrame::from_args(self))),
                 Some("json") => Ok(Box::new(JsonToDataFrame::from_args(self))),
                 Some("jsonl") => Ok(Box::new(JsonLineToDataFrame::from_args(self))),
-                Some("arrow") => Ok(Box::new(ArrowIpcToDataFrame)),
+                Some("arrow") => Ok(Box::new(ArrowIpcToDataFrame::from_args(self))),
                 Some("avro") => Ok(Box::new(AvroToDataFrame)),
                 Some("fwf") => Ok(Box::new(FwfToDataFrame::from_args(self))),
                 Some("db") | Some("sqlite") => Ok(Box::new(SqliteToDataFrames::from_args(self))),
+                #[cfg(feature = "duckdb")]
+                Some("duckdb") | Some("ddb") => Ok(Box::new(DuckDbToDataFrames::from_args(self))),
                 Some("xls") | Some("xlsx") | Some("xlsm") | Some("xlsb") => {
                     Ok(Box::new(ExcelToDataFrames::from_args(self)))
                 }
                 Some("html") | Some("htm") => Ok(Box::new(HtmlToDataFrame::from_args(self))),
                 Some("md") | Some("markdown") => Ok(Box::new(MarkdownToDataFrame::from_args(self))),
+                Some("yaml") | Some("yml") => Ok(Box::new(YamlToDataFrame::from_args(self))),
+                Some("toml") => Ok(Box::new(TomlToDataFrame::from_args(self))),
+                Some("sas7bdat") => Ok(Box::new(SasToDataFrame)),
+                Some("sav") | Some("zsav") => Ok(Box::new(SpssToDataFrame::from_args(self))),
+                Some("dta") => Ok(Box::new(StataToDataFrame::from_args(self))),
                 _ => Ok(Box::new(CsvToDataFrame::from_args(self))),
             },
         }
     }
 }
+
+/// Delta and Iceberg tables are directories with their log or metadata in a subdirectory.
+fn is_table_dir(path: &Path, subdir: &str) -> bool {
+    path.is_dir() && path.join(subdir).is_dir()
+}
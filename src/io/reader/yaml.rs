@@ -0,0 +1,205 @@
+use anyhow::anyhow;
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::{
+    AppResult,
+    args::Args,
+    io::reader::{
+        DataFrameReader, NamedFrame, NamedFrames, ReaderSource, json_line::records_to_data_frame,
+    },
+    misc::encoding::TextEncoding,
+};
+
+#[derive(Debug)]
+pub struct YamlToDataFrame {
+    ignore_errors: bool,
+    encoding: TextEncoding,
+}
+
+impl YamlToDataFrame {
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            ignore_errors: args.ignore_errors,
+            encoding: TextEncoding::from_args(args),
+        }
+    }
+
+    pub fn with_encoding(mut self, encoding: TextEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+}
+
+impl Default for YamlToDataFrame {
+    fn default() -> Self {
+        Self {
+            ignore_errors: true,
+            encoding: Default::default(),
+        }
+    }
+}
+
+impl DataFrameReader for YamlToDataFrame {
+    fn read_to_data_frames(&self, input: ReaderSource) -> AppResult<NamedFrames> {
+        let contents = input.read_to_string(self.encoding)?;
+
+        let docs = YamlLoader::load_from_str(&contents)?;
+        let mut tables = Vec::new();
+        for (idx, doc) in docs.iter().enumerate() {
+            let start = tables.len();
+            collect_tables(doc, None, &mut tables);
+            // the same key paths may come up in every document of a multi-document file
+            if docs.len() > 1 {
+                for (name, _) in tables[start..].iter_mut() {
+                    let name = name.get_or_insert_with(|| input.table_name());
+                    name.push_str(&format!("_{}", idx + 1));
+                }
+            }
+        }
+        if tables.is_empty() {
+            return Err(anyhow!("No sequence of records found in the YAML document"));
+        }
+
+        tables
+            .into_iter()
+            .map(|(name, items)| {
+                let df = records_to_data_frame(
+                    items.iter().map(yaml_to_json).collect(),
+                    self.ignore_errors,
+                )?;
+                Ok(NamedFrame::new(
+                    name.unwrap_or_else(|| input.table_name()),
+                    df,
+                ))
+            })
+            .collect::<AppResult<Vec<_>>>()
+            .map(Vec::into_boxed_slice)
+    }
+}
+
+/// Walks the document and collects every sequence that can be read as a table. A top-level
+/// sequence is always a table, nested ones only when all of their items are mappings. Nested
+/// tables are named after their dotted key path.
+fn collect_tables<'a>(
+    yaml: &'a Yaml,
+    path: Option<String>,
+    tables: &mut Vec<(Option<String>, &'a [Yaml])>,
+) {
+    match yaml {
+        Yaml::Array(items)
+            if path.is_none()
+                || (!items.is_empty() && items.iter().all(|item| item.as_hash().is_some())) =>
+        {
+            tables.push((path, items))
+        }
+        Yaml::Hash(hash) => {
+            for (key, value) in hash {
+                let key = yaml_key(key);
+                let path = match &path {
+                    Some(path) => format!("{path}.{key}"),
+                    None => key,
+                };
+                collect_tables(value, Some(path), tables);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn yaml_key(yaml: &Yaml) -> String {
+    match yaml {
+        Yaml::String(s) | Yaml::Real(s) => s.to_owned(),
+        Yaml::Integer(i) => i.to_string(),
+        Yaml::Boolean(b) => b.to_string(),
+        _ => String::from("null"),
+    }
+}
+
+fn yaml_to_json(yaml: &Yaml) -> serde_json::Value {
+    match yaml {
+        Yaml::Real(_) => yaml
+            .as_f64()
+            .and_then(serde_json::Number::from_f64)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        Yaml::Integer(i) => serde_json::Value::from(*i),
+        Yaml::String(s) => serde_json::Value::from(s.as_str()),
+        Yaml::Boolean(b) => serde_json::Value::from(*b),
+        Yaml::Array(items) => items.iter().map(yaml_to_json).collect(),
+        Yaml::Hash(hash) => serde_json::Value::Object(
+            hash.iter()
+                .map(|(key, value)| (yaml_key(key), yaml_to_json(value)))
+                .collect(),
+        ),
+        Yaml::Alias(_) | Yaml::Null | Yaml::BadValue => serde_json::Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use polars::prelude::AnyValue;
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    fn read(contents: &str) -> Vec<NamedFrame> {
+        let mut file = NamedTempFile::with_suffix(".yaml").unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        YamlToDataFrame::default()
+            .read_to_data_frames(ReaderSource::File(file.path().to_owned()))
+            .unwrap()
+            .into_vec()
+    }
+
+    fn names(frames: &[NamedFrame]) -> Vec<&str> {
+        frames.iter().map(NamedFrame::name).collect()
+    }
+
+    #[test]
+    fn test_nested_maps() {
+        let frames = read(
+            "shop:\n  name: corner\n  staff:\n    - name: ann\n      age: 31\n    - name: bob\n      age: 45\n  tags: [a, b]\n",
+        );
+        assert_eq!(names(&frames), ["shop.staff"]);
+        let df = frames[0].data_frame();
+        assert_eq!(df.shape(), (2, 2));
+        assert_eq!(
+            df.column("age").unwrap().get(1).unwrap(),
+            AnyValue::Int64(45)
+        );
+    }
+
+    #[test]
+    fn test_missing_keys() {
+        let frames = read("- a: 1\n  b: x\n- a: 2\n- b: y\n  c: true\n");
+        let df = frames[0].data_frame();
+        assert_eq!(df.shape(), (3, 3));
+        assert_eq!(df.column("a").unwrap().null_count(), 1);
+        assert_eq!(df.column("b").unwrap().null_count(), 1);
+        assert_eq!(df.column("c").unwrap().null_count(), 2);
+    }
+
+    #[test]
+    fn test_multiple_documents() {
+        let frames = read(
+            "- a: 1\n- a: 2\n---\n- a: 3\n---\nusers:\n  - name: ann\n---\nusers:\n  - name: bob\n",
+        );
+        let stem = frames[0].name().trim_end_matches("_1").to_owned();
+        assert_eq!(
+            names(&frames),
+            [
+                format!("{stem}_1"),
+                format!("{stem}_2"),
+                "users_3".to_owned(),
+                "users_4".to_owned()
+            ]
+        );
+        assert_eq!(frames[0].data_frame().height(), 2);
+        assert_eq!(frames[1].data_frame().height(), 1);
+
+        assert_eq!(names(&read("- a: 1\n")).len(), 1);
+        assert!(!read("- a: 1\n")[0].name().ends_with("_1"));
+    }
+}
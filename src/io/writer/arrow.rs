@@ -1,21 +1,64 @@
-use std::fs::File;
+use std::{fmt::Display, fs::File};
 
-use polars::{frame::DataFrame, io::SerWriter, prelude::IpcWriter};
+use polars::{
+    frame::DataFrame,
+    io::SerWriter,
+    prelude::{IpcCompression, IpcWriter},
+};
+use strum_macros::{EnumIter, IntoStaticStr};
 
 use crate::{AppResult, misc::osc52::CopyToClipboardOsc52};
 
 use super::traits::{Destination, WriteToFile};
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, EnumIter, IntoStaticStr)]
+pub enum IpcCodec {
+    #[default]
+    #[strum(serialize = "None")]
+    Uncompressed,
+    Lz4,
+    Zstd,
+}
+
+impl IpcCodec {
+    fn compression(&self) -> Option<IpcCompression> {
+        match self {
+            IpcCodec::Uncompressed => None,
+            IpcCodec::Lz4 => Some(IpcCompression::LZ4),
+            IpcCodec::Zstd => Some(IpcCompression::ZSTD(Default::default())),
+        }
+    }
+}
+
+impl Display for IpcCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Into::<&str>::into(self))
+    }
+}
+
 #[derive(Debug, Default)]
-pub struct WriteToArrow;
+pub struct WriteToArrow {
+    codec: IpcCodec,
+}
+
+impl WriteToArrow {
+    pub fn with_codec(mut self, codec: IpcCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+}
 
 impl WriteToFile for WriteToArrow {
     fn write_to_file(&self, dest: Destination, data_frame: &mut DataFrame) -> AppResult<()> {
         match dest {
-            Destination::File(path) => Ok(IpcWriter::new(File::create(path)?).finish(data_frame)?),
+            Destination::File(path) => Ok(IpcWriter::new(File::create(path)?)
+                .with_compression(self.codec.compression())
+                .finish(data_frame)?),
             Destination::Clipboard => {
                 let mut buf = Vec::new();
-                IpcWriter::new(&mut buf).finish(data_frame)?;
+                IpcWriter::new(&mut buf)
+                    .with_compression(self.codec.compression())
+                    .finish(data_frame)?;
                 buf.copy_to_clipboard_via_osc52();
                 Ok(())
             }
@@ -0,0 +1,115 @@
+use std::{fs::File, io::Write};
+
+use polars::frame::DataFrame;
+use ratatui::style::{Modifier, Style};
+
+use crate::{
+    AppResult,
+    misc::{
+        color_ext::ColorExt, config::theme, osc52::CopyToClipboardOsc52, polars_ext::AnyValueExt,
+    },
+};
+
+use super::traits::{Destination, WriteToFile};
+
+#[derive(Debug, Default)]
+pub struct WriteToHtml {
+    styled: bool,
+}
+
+impl WriteToHtml {
+    /// Adds inline styles taken from the active theme.
+    pub fn with_styled(mut self, styled: bool) -> Self {
+        self.styled = styled;
+        self
+    }
+
+    fn style_attr(&self, style: impl FnOnce() -> Style) -> String {
+        if !self.styled {
+            return String::new();
+        }
+        let style = style();
+        let declarations = [
+            style
+                .bg
+                .and_then(|color| color.css())
+                .map(|color| format!("background-color: {color}")),
+            style
+                .fg
+                .and_then(|color| color.css())
+                .map(|color| format!("color: {color}")),
+            style
+                .add_modifier
+                .contains(Modifier::BOLD)
+                .then(|| "font-weight: bold".to_owned()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        if declarations.is_empty() {
+            String::new()
+        } else {
+            format!(" style=\"{}\"", declarations.join("; "))
+        }
+    }
+
+    fn render(&self, df: &DataFrame) -> String {
+        let mut out = format!("<table{}>\n", self.style_attr(|| theme().text()));
+
+        out.push_str(&format!(
+            "  <thead>\n    <tr{}>\n",
+            self.style_attr(|| theme().table_header())
+        ));
+        for (idx, column) in df.columns().iter().enumerate() {
+            out.push_str(&format!(
+                "      <th{}>{}</th>\n",
+                self.style_attr(|| theme().header(idx)),
+                escape_html(column.name())
+            ));
+        }
+        out.push_str("    </tr>\n  </thead>\n  <tbody>\n");
+
+        for idx in 0..df.height() {
+            out.push_str(&format!(
+                "    <tr{}>\n",
+                self.style_attr(|| theme().row(idx))
+            ));
+            for column in df.columns() {
+                let value = column
+                    .get(idx)
+                    .map(|value| value.into_single_line())
+                    .unwrap_or_default();
+                out.push_str(&format!("      <td>{}</td>\n", escape_html(&value)));
+            }
+            out.push_str("    </tr>\n");
+        }
+        out.push_str("  </tbody>\n</table>\n");
+        out
+    }
+}
+
+impl WriteToFile for WriteToHtml {
+    fn write_to_file(&self, dest: Destination, data_frame: &mut DataFrame) -> AppResult<()> {
+        let buf = self.render(data_frame);
+        match dest {
+            Destination::File(path) => {
+                File::create(path)?.write_all(buf.as_bytes())?;
+                Ok(())
+            }
+            Destination::Clipboard => {
+                buf.into_bytes().copy_to_clipboard_via_osc52();
+                Ok(())
+            }
+        }
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+        .replace('\r', "")
+        .replace('\n', "<br>")
+}
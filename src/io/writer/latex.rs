@@ -0,0 +1,91 @@
+use std::{fs::File, io::Write};
+
+use polars::frame::DataFrame;
+
+use crate::{
+    AppResult,
+    misc::{osc52::CopyToClipboardOsc52, polars_ext::AnyValueExt},
+};
+
+use super::traits::{Destination, WriteToFile};
+
+/// Writes a `tabular` environment with `booktabs` rules.
+#[derive(Debug, Default)]
+pub struct WriteToLatex;
+
+impl WriteToFile for WriteToLatex {
+    fn write_to_file(&self, dest: Destination, data_frame: &mut DataFrame) -> AppResult<()> {
+        let buf = render_latex(data_frame);
+        match dest {
+            Destination::File(path) => {
+                File::create(path)?.write_all(buf.as_bytes())?;
+                Ok(())
+            }
+            Destination::Clipboard => {
+                buf.into_bytes().copy_to_clipboard_via_osc52();
+                Ok(())
+            }
+        }
+    }
+}
+
+fn render_latex(df: &DataFrame) -> String {
+    let columns = df.columns();
+    let alignment = columns
+        .iter()
+        .map(|column| {
+            if column.dtype().is_primitive_numeric() || column.dtype().is_decimal() {
+                'r'
+            } else {
+                'l'
+            }
+        })
+        .collect::<String>();
+
+    let mut out = format!("\\begin{{tabular}}{{{alignment}}}\n\\toprule\n");
+    out.push_str(
+        &columns
+            .iter()
+            .map(|column| escape_latex(column.name()))
+            .collect::<Vec<_>>()
+            .join(" & "),
+    );
+    out.push_str(" \\\\\n\\midrule\n");
+
+    for idx in 0..df.height() {
+        out.push_str(
+            &columns
+                .iter()
+                .map(|column| {
+                    column
+                        .get(idx)
+                        .map(|value| escape_latex(&value.into_single_line()))
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+                .join(" & "),
+        );
+        out.push_str(" \\\\\n");
+    }
+    out.push_str("\\bottomrule\n\\end{tabular}\n");
+    out
+}
+
+fn escape_latex(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\textbackslash{}"),
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\r' => (),
+            '\n' => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -1,15 +1,27 @@
 mod arrow;
 mod avro;
 mod csv;
+mod html;
 mod json;
+mod latex;
 mod markdown;
+mod org;
 mod parquet;
+mod sql_script;
+mod sqlite;
 mod traits;
+mod xlsx;
 
-pub use arrow::WriteToArrow;
+pub use arrow::{IpcCodec, WriteToArrow};
 pub use avro::WriteToAvro;
 pub use csv::WriteToCsv;
+pub use html::WriteToHtml;
 pub use json::{JsonFormat, WriteToJson};
+pub use latex::WriteToLatex;
 pub use markdown::WriteToMarkdown;
-pub use parquet::WriteToParquet;
+pub use org::WriteToOrg;
+pub use parquet::{ParquetCodec, WriteToParquet};
+pub use sql_script::{SqlDialect, WriteToSqlScript};
+pub use sqlite::{SqliteWriteMode, WriteToSqlite};
 pub use traits::{Destination, WriteToFile};
+pub use xlsx::WriteToXlsx;
@@ -0,0 +1,97 @@
+use std::{fs::File, io::Write};
+
+use polars::frame::DataFrame;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    AppResult,
+    misc::{osc52::CopyToClipboardOsc52, polars_ext::AnyValueExt},
+};
+
+use super::traits::{Destination, WriteToFile};
+
+#[derive(Debug, Default)]
+pub struct WriteToOrg;
+
+impl WriteToFile for WriteToOrg {
+    fn write_to_file(&self, dest: Destination, data_frame: &mut DataFrame) -> AppResult<()> {
+        let buf = render_org(data_frame);
+        match dest {
+            Destination::File(path) => {
+                File::create(path)?.write_all(buf.as_bytes())?;
+                Ok(())
+            }
+            Destination::Clipboard => {
+                buf.into_bytes().copy_to_clipboard_via_osc52();
+                Ok(())
+            }
+        }
+    }
+}
+
+fn render_org(df: &DataFrame) -> String {
+    let header = df
+        .columns()
+        .iter()
+        .map(|column| escape_cell(column.name()))
+        .collect::<Vec<_>>();
+    let rows = (0..df.height())
+        .map(|idx| {
+            df.columns()
+                .iter()
+                .map(|column| {
+                    column
+                        .get(idx)
+                        .map(|value| escape_cell(&value.into_single_line()))
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Org mode realigns tables anyway, but aligned output reads better outside of Emacs
+    let widths = header
+        .iter()
+        .enumerate()
+        .map(|(col, name)| {
+            rows.iter()
+                .map(|row| row[col].width())
+                .chain(std::iter::once(name.width()))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let mut out = String::new();
+    push_row(&mut out, &header, &widths);
+    out.push('|');
+    out.push_str(
+        &widths
+            .iter()
+            .map(|width| "-".repeat(width + 2))
+            .collect::<Vec<_>>()
+            .join("+"),
+    );
+    out.push_str("|\n");
+    for row in rows.iter() {
+        push_row(&mut out, row, &widths);
+    }
+    out
+}
+
+fn push_row(out: &mut String, cells: &[String], widths: &[usize]) {
+    out.push('|');
+    for (cell, width) in cells.iter().zip(widths) {
+        out.push(' ');
+        out.push_str(cell);
+        out.push_str(&" ".repeat(width.saturating_sub(cell.width())));
+        out.push_str(" |");
+    }
+    out.push('\n');
+}
+
+fn escape_cell(s: &str) -> String {
+    s.replace('|', "\\vert{}")
+        .replace('\r', "")
+        .replace('\n', " ")
+}
@@ -1,27 +1,270 @@
-use std::fs::File;
+use std::{
+    fmt::Display,
+    fs::{File, create_dir_all},
+    path::Path,
+};
 
-use polars::{frame::DataFrame, prelude::ParquetWriter};
+use anyhow::anyhow;
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+use polars::{
+    frame::DataFrame,
+    polars_utils::compression::{BrotliLevel, GzipLevel, ZstdLevel},
+    prelude::{ParquetCompression, ParquetWriter, StatisticsOptions},
+};
+use strum_macros::{EnumIter, IntoStaticStr};
 
-use crate::{AppResult, misc::osc52::CopyToClipboardOsc52};
+use crate::{
+    AppResult,
+    misc::{osc52::CopyToClipboardOsc52, polars_ext::AnyValueExt},
+};
 
 use super::traits::{Destination, WriteToFile};
 
-#[derive(Debug, Default)]
-pub struct WriteToParquet;
+const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Characters escaped in Hive partition directory names.
+const HIVE_ESCAPE: &AsciiSet = &CONTROLS
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'\'')
+    .add(b'*')
+    .add(b'/')
+    .add(b':')
+    .add(b'=')
+    .add(b'?')
+    .add(b'\\')
+    .add(b'[')
+    .add(b']')
+    .add(b'^')
+    .add(b'{')
+    .add(b'}');
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, EnumIter, IntoStaticStr)]
+pub enum ParquetCodec {
+    #[default]
+    Zstd,
+    Snappy,
+    Lz4,
+    Gzip,
+    Brotli,
+    #[strum(serialize = "None")]
+    Uncompressed,
+}
+
+impl ParquetCodec {
+    pub fn has_level(&self) -> bool {
+        matches!(
+            self,
+            ParquetCodec::Zstd | ParquetCodec::Gzip | ParquetCodec::Brotli
+        )
+    }
+
+    fn compression(&self, level: Option<u32>) -> AppResult<ParquetCompression> {
+        Ok(match self {
+            ParquetCodec::Zstd => ParquetCompression::Zstd(
+                level
+                    .map(|level| ZstdLevel::try_new(level as i32))
+                    .transpose()?,
+            ),
+            ParquetCodec::Snappy => ParquetCompression::Snappy,
+            ParquetCodec::Lz4 => ParquetCompression::Lz4Raw,
+            ParquetCodec::Gzip => ParquetCompression::Gzip(
+                level
+                    .map(|level| GzipLevel::try_new(level.try_into().unwrap_or(u8::MAX)))
+                    .transpose()?,
+            ),
+            ParquetCodec::Brotli => {
+                ParquetCompression::Brotli(level.map(BrotliLevel::try_new).transpose()?)
+            }
+            ParquetCodec::Uncompressed => ParquetCompression::Uncompressed,
+        })
+    }
+}
+
+impl Display for ParquetCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Into::<&str>::into(self))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WriteToParquet {
+    codec: ParquetCodec,
+    level: Option<u32>,
+    row_group_size: Option<usize>,
+    statistics: bool,
+    partition_by: Vec<String>,
+    max_rows_per_file: Option<usize>,
+    drop_partition_columns: bool,
+}
+
+impl WriteToParquet {
+    pub fn with_codec(mut self, codec: ParquetCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Compression level of the codec, or its default level if `None`.
+    pub fn with_level(mut self, level: Option<u32>) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn with_row_group_size(mut self, row_group_size: Option<usize>) -> Self {
+        self.row_group_size = row_group_size;
+        self
+    }
+
+    pub fn with_statistics(mut self, statistics: bool) -> Self {
+        self.statistics = statistics;
+        self
+    }
+
+    /// Writes a Hive-style directory tree, one directory level per partition column.
+    pub fn with_partition_by(mut self, partition_by: Vec<String>) -> Self {
+        self.partition_by = partition_by;
+        self
+    }
+
+    pub fn with_max_rows_per_file(mut self, max_rows_per_file: Option<usize>) -> Self {
+        self.max_rows_per_file = max_rows_per_file;
+        self
+    }
+
+    pub fn with_drop_partition_columns(mut self, drop_partition_columns: bool) -> Self {
+        self.drop_partition_columns = drop_partition_columns;
+        self
+    }
+
+    /// Writes into a new or empty directory only, as files left by an earlier export would be
+    /// read along with the new ones.
+    fn write_partitioned(&self, dir: &Path, data_frame: &DataFrame) -> AppResult<()> {
+        if dir
+            .read_dir()
+            .is_ok_and(|mut entries| entries.next().is_some())
+        {
+            return Err(anyhow!(
+                "'{}' is not empty, partitioned Parquet is exported into a new or empty directory",
+                dir.display()
+            ));
+        }
+        create_dir_all(dir)?;
+        for partition in
+            data_frame.partition_by_stable(self.partition_by.iter().map(String::as_str), true)?
+        {
+            let mut path = dir.to_path_buf();
+            for name in self.partition_by.iter() {
+                let value = partition.column(name)?.get(0)?;
+                let value = if value.is_null() {
+                    HIVE_DEFAULT_PARTITION.to_owned()
+                } else {
+                    utf8_percent_encode(&value.into_single_line(), HIVE_ESCAPE).to_string()
+                };
+                path.push(format!(
+                    "{}={value}",
+                    utf8_percent_encode(name, HIVE_ESCAPE)
+                ));
+            }
+            create_dir_all(&path)?;
+
+            let partition = if self.drop_partition_columns {
+                partition.drop_many(self.partition_by.iter().map(String::as_str))
+            } else {
+                partition
+            };
+            let rows = self.max_rows_per_file.unwrap_or(partition.height()).max(1);
+            for (idx, offset) in (0..partition.height()).step_by(rows).enumerate() {
+                self.write(
+                    File::create(path.join(format!("part-{idx}.parquet")))?,
+                    &mut partition.slice(offset as i64, rows),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write<W: std::io::Write>(&self, writer: W, data_frame: &mut DataFrame) -> AppResult<()> {
+        ParquetWriter::new(writer)
+            .with_compression(self.codec.compression(self.level)?)
+            .with_row_group_size(self.row_group_size)
+            .with_statistics(if self.statistics {
+                StatisticsOptions::default()
+            } else {
+                StatisticsOptions::empty()
+            })
+            .finish(data_frame)?;
+        Ok(())
+    }
+}
+
+impl Default for WriteToParquet {
+    fn default() -> Self {
+        Self {
+            codec: Default::default(),
+            level: None,
+            row_group_size: None,
+            statistics: true,
+            partition_by: Vec::new(),
+            max_rows_per_file: None,
+            drop_partition_columns: true,
+        }
+    }
+}
 
 impl WriteToFile for WriteToParquet {
     fn write_to_file(&self, dest: Destination, data_frame: &mut DataFrame) -> AppResult<()> {
         match dest {
-            Destination::File(path) => {
-                ParquetWriter::new(File::create(path)?).finish(data_frame)?;
-                Ok(())
+            Destination::File(path) if !self.partition_by.is_empty() => {
+                self.write_partitioned(&path, data_frame)
             }
+            Destination::File(path) => self.write(File::create(path)?, data_frame),
+            Destination::Clipboard if !self.partition_by.is_empty() => Err(anyhow!(
+                "Partitioned Parquet can only be exported to a directory"
+            )),
             Destination::Clipboard => {
                 let mut buf = Vec::new();
-                ParquetWriter::new(&mut buf).finish(data_frame)?;
+                self.write(&mut buf, data_frame)?;
                 buf.copy_to_clipboard_via_osc52();
                 Ok(())
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use polars::df;
+
+    use super::*;
+
+    #[test]
+    fn test_partitioned_export_into_used_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("sales");
+        let writer = WriteToParquet::default()
+            .with_partition_by(vec!["region".to_owned()])
+            .with_max_rows_per_file(Some(1));
+        let mut df = df!("region" => ["eu", "eu", "us"], "amount" => [1, 2, 3]).unwrap();
+        writer
+            .write_to_file(Destination::File(target.clone()), &mut df)
+            .unwrap();
+        assert!(target.join("region=eu/part-1.parquet").is_file());
+        assert!(target.join("region=us/part-0.parquet").is_file());
+
+        let mut smaller = df!("region" => ["eu"], "amount" => [4]).unwrap();
+        assert!(
+            writer
+                .write_to_file(Destination::File(target.clone()), &mut smaller)
+                .is_err()
+        );
+        assert!(target.join("region=eu/part-1.parquet").is_file());
+
+        let empty = dir.path().join("empty");
+        std::fs::create_dir(&empty).unwrap();
+        writer
+            .write_to_file(Destination::File(empty.clone()), &mut smaller)
+            .unwrap();
+        assert!(empty.join("region=eu/part-0.parquet").is_file());
+    }
+}
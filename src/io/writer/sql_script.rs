@@ -0,0 +1,377 @@
+use std::{fmt::Display, fs::File, io::Write};
+
+use polars::{
+    frame::DataFrame,
+    prelude::{AnyValue, Column, DataType},
+};
+use strum_macros::{EnumIter, IntoStaticStr};
+
+use crate::{AppResult, misc::osc52::CopyToClipboardOsc52};
+
+use super::traits::{Destination, WriteToFile};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, EnumIter, IntoStaticStr)]
+pub enum SqlDialect {
+    #[default]
+    #[strum(serialize = "SQLite")]
+    Sqlite,
+    #[strum(serialize = "PostgreSQL")]
+    PostgreSql,
+    #[strum(serialize = "MySQL")]
+    MySql,
+}
+
+impl Display for SqlDialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Into::<&str>::into(self))
+    }
+}
+
+/// Writes a `CREATE TABLE` statement followed by batched `INSERT` statements.
+#[derive(Debug)]
+pub struct WriteToSqlScript {
+    table: String,
+    dialect: SqlDialect,
+    batch_size: usize,
+}
+
+impl WriteToSqlScript {
+    pub fn with_table(mut self, table: impl Into<String>) -> Self {
+        self.table = table.into();
+        self
+    }
+
+    pub fn with_dialect(mut self, dialect: SqlDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    fn render(&self, df: &DataFrame) -> AppResult<String> {
+        let dialect = self.dialect;
+        let table = dialect.quote(&self.table);
+        let names = df
+            .columns()
+            .iter()
+            .map(|column| dialect.quote(column.name()))
+            .collect::<Vec<_>>();
+
+        let mut out = dialect.create_table(
+            &self.table,
+            df.columns()
+                .iter()
+                .map(|column| (column.name().as_str(), column.dtype())),
+        );
+        if df.width() == 0 {
+            return Ok(out);
+        }
+
+        let columns = df
+            .columns()
+            .iter()
+            .map(|column| dialect.literal_column(column))
+            .collect::<AppResult<Vec<_>>>()?;
+        for start in (0..df.height()).step_by(self.batch_size) {
+            out.push_str(&format!(
+                "\nINSERT INTO {table} ({}) VALUES\n",
+                names.join(", ")
+            ));
+            let end = (start + self.batch_size).min(df.height());
+            for idx in start..end {
+                let values = columns
+                    .iter()
+                    .map(|column| column.get(idx).map(|value| dialect.literal(value)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                out.push_str(&format!("    ({})", values.join(", ")));
+                out.push_str(if idx + 1 == end { ";\n" } else { ",\n" });
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl Default for WriteToSqlScript {
+    fn default() -> Self {
+        Self {
+            table: "data".to_owned(),
+            dialect: Default::default(),
+            batch_size: 500,
+        }
+    }
+}
+
+impl WriteToFile for WriteToSqlScript {
+    fn write_to_file(&self, dest: Destination, data_frame: &mut DataFrame) -> AppResult<()> {
+        let buf = self.render(data_frame)?;
+        match dest {
+            Destination::File(path) => {
+                File::create(path)?.write_all(buf.as_bytes())?;
+                Ok(())
+            }
+            Destination::Clipboard => {
+                buf.into_bytes().copy_to_clipboard_via_osc52();
+                Ok(())
+            }
+        }
+    }
+}
+
+impl SqlDialect {
+    /// A `CREATE TABLE` statement with the columns mapped to the types of the dialect.
+    pub fn create_table<'a>(
+        &self,
+        table: &str,
+        columns: impl IntoIterator<Item = (&'a str, &'a DataType)>,
+    ) -> String {
+        format!(
+            "CREATE TABLE {} (\n{}\n);\n",
+            self.quote(table),
+            columns
+                .into_iter()
+                .map(|(name, dtype)| format!(
+                    "    {} {}",
+                    self.quote(name),
+                    self.column_type(dtype)
+                ))
+                .collect::<Vec<_>>()
+                .join(",\n")
+        )
+    }
+
+    fn quote(&self, identifier: &str) -> String {
+        match self {
+            SqlDialect::MySql => format!("`{}`", identifier.replace('`', "``")),
+            SqlDialect::Sqlite | SqlDialect::PostgreSql => {
+                format!("\"{}\"", identifier.replace('"', "\"\""))
+            }
+        }
+    }
+
+    fn column_type(&self, dtype: &DataType) -> String {
+        match (self, dtype) {
+            (SqlDialect::Sqlite, DataType::Boolean) => "INTEGER".to_owned(),
+            (SqlDialect::Sqlite, dtype) if dtype.is_integer() => "INTEGER".to_owned(),
+            (SqlDialect::Sqlite, dtype) if dtype.is_float() => "REAL".to_owned(),
+            (SqlDialect::Sqlite, DataType::Decimal(_, _)) => "NUMERIC".to_owned(),
+            (SqlDialect::Sqlite, DataType::Binary) => "BLOB".to_owned(),
+            (SqlDialect::Sqlite, _) => "TEXT".to_owned(),
+
+            (SqlDialect::PostgreSql, DataType::Boolean) => "BOOLEAN".to_owned(),
+            (SqlDialect::PostgreSql, DataType::Int8 | DataType::Int16 | DataType::UInt8) => {
+                "SMALLINT".to_owned()
+            }
+            (SqlDialect::PostgreSql, DataType::Int32 | DataType::UInt16) => "INTEGER".to_owned(),
+            (SqlDialect::PostgreSql, DataType::Int64 | DataType::UInt32) => "BIGINT".to_owned(),
+            (SqlDialect::PostgreSql, dtype) if dtype.is_integer() => "NUMERIC".to_owned(),
+            (SqlDialect::PostgreSql, DataType::Float32) => "REAL".to_owned(),
+            (SqlDialect::PostgreSql, DataType::Float64) => "DOUBLE PRECISION".to_owned(),
+            (SqlDialect::PostgreSql, DataType::Decimal(precision, scale)) => {
+                format!("NUMERIC({precision}, {scale})")
+            }
+            (SqlDialect::PostgreSql, DataType::Binary) => "BYTEA".to_owned(),
+            (SqlDialect::PostgreSql, DataType::Date) => "DATE".to_owned(),
+            (SqlDialect::PostgreSql, DataType::Datetime(_, None)) => "TIMESTAMP".to_owned(),
+            (SqlDialect::PostgreSql, DataType::Datetime(_, Some(_))) => "TIMESTAMPTZ".to_owned(),
+            (SqlDialect::PostgreSql, DataType::Time) => "TIME".to_owned(),
+            (SqlDialect::PostgreSql, _) => "TEXT".to_owned(),
+
+            (SqlDialect::MySql, DataType::Boolean) => "BOOLEAN".to_owned(),
+            (SqlDialect::MySql, DataType::Int8) => "TINYINT".to_owned(),
+            (SqlDialect::MySql, DataType::Int16) => "SMALLINT".to_owned(),
+            (SqlDialect::MySql, DataType::Int32) => "INT".to_owned(),
+            (SqlDialect::MySql, DataType::Int64) => "BIGINT".to_owned(),
+            (SqlDialect::MySql, DataType::UInt8) => "TINYINT UNSIGNED".to_owned(),
+            (SqlDialect::MySql, DataType::UInt16) => "SMALLINT UNSIGNED".to_owned(),
+            (SqlDialect::MySql, DataType::UInt32) => "INT UNSIGNED".to_owned(),
+            (SqlDialect::MySql, DataType::UInt64) => "BIGINT UNSIGNED".to_owned(),
+            (SqlDialect::MySql, dtype) if dtype.is_integer() => "DECIMAL(39, 0)".to_owned(),
+            (SqlDialect::MySql, DataType::Float32) => "FLOAT".to_owned(),
+            (SqlDialect::MySql, DataType::Float64) => "DOUBLE".to_owned(),
+            (SqlDialect::MySql, DataType::Decimal(precision, scale)) => {
+                format!("DECIMAL({precision}, {scale})")
+            }
+            (SqlDialect::MySql, DataType::Binary) => "LONGBLOB".to_owned(),
+            (SqlDialect::MySql, DataType::Date) => "DATE".to_owned(),
+            (SqlDialect::MySql, DataType::Datetime(_, _)) => "DATETIME(6)".to_owned(),
+            (SqlDialect::MySql, DataType::Time) => "TIME(6)".to_owned(),
+            (SqlDialect::MySql, _) => "LONGTEXT".to_owned(),
+        }
+    }
+
+    fn literal(&self, value: AnyValue) -> String {
+        match value {
+            AnyValue::Null => "NULL".to_owned(),
+            AnyValue::Boolean(value) => match self {
+                SqlDialect::Sqlite => if value { "1" } else { "0" }.to_owned(),
+                SqlDialect::PostgreSql | SqlDialect::MySql => {
+                    if value { "TRUE" } else { "FALSE" }.to_owned()
+                }
+            },
+            AnyValue::Float32(value) => self.float_literal(value as f64),
+            AnyValue::Float64(value) => self.float_literal(value),
+            value if value.is_primitive_numeric() || matches!(value, AnyValue::Decimal(..)) => {
+                value.to_string()
+            }
+            AnyValue::String(value) => self.string_literal(value),
+            AnyValue::StringOwned(value) => self.string_literal(&value),
+            AnyValue::Binary(value) => self.binary_literal(value),
+            AnyValue::BinaryOwned(value) => self.binary_literal(&value),
+            value => self.string_literal(&value.to_string()),
+        }
+    }
+
+    fn float_literal(&self, value: f64) -> String {
+        match (self, value.is_finite()) {
+            (_, true) => value.to_string(),
+            (SqlDialect::PostgreSql, false) if value.is_nan() => "'NaN'".to_owned(),
+            (SqlDialect::PostgreSql, false) if value > 0.0 => "'Infinity'".to_owned(),
+            (SqlDialect::PostgreSql, false) => "'-Infinity'".to_owned(),
+            (SqlDialect::Sqlite | SqlDialect::MySql, false) => "NULL".to_owned(),
+        }
+    }
+
+    fn string_literal(&self, value: &str) -> String {
+        match self {
+            SqlDialect::MySql => format!(
+                "'{}'",
+                value
+                    .replace('\\', "\\\\")
+                    .replace('\'', "''")
+                    .replace('\0', "\\0")
+            ),
+            SqlDialect::Sqlite | SqlDialect::PostgreSql => {
+                format!("'{}'", value.replace('\'', "''"))
+            }
+        }
+    }
+
+    fn binary_literal(&self, value: &[u8]) -> String {
+        let hex = value
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        match self {
+            SqlDialect::Sqlite | SqlDialect::MySql => format!("X'{hex}'"),
+            SqlDialect::PostgreSql => format!("'\\x{hex}'"),
+        }
+    }
+
+    /// Casts the column to the representation its literals are written from: temporal values
+    /// become ISO 8601 strings, in UTC and without a time zone for MySQL, and categories
+    /// become their strings.
+    fn literal_column(&self, column: &Column) -> AppResult<Column> {
+        Ok(match (self, column.dtype()) {
+            (SqlDialect::MySql, DataType::Datetime(unit, Some(_))) => column
+                .cast(&DataType::Datetime(*unit, None))?
+                .cast(&DataType::String)?,
+            (_, dtype) if dtype.is_temporal() || dtype.is_categorical() || dtype.is_enum() => {
+                column.cast(&DataType::String)?
+            }
+            _ => column.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::{
+        df,
+        prelude::{IntoColumn, NamedFrom, TimeUnit, TimeZone},
+        series::Series,
+    };
+
+    use super::*;
+
+    fn literals(dialect: SqlDialect, column: Column) -> Vec<String> {
+        let column = dialect.literal_column(&column).unwrap();
+        (0..column.len())
+            .map(|idx| dialect.literal(column.get(idx).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_string_literals() {
+        let column = Column::new("s".into(), ["it's", "C:\\temp", "a\tb\nc", "nul\0"]);
+        assert_eq!(
+            literals(SqlDialect::MySql, column.clone()),
+            ["'it''s'", "'C:\\\\temp'", "'a\tb\nc'", "'nul\\0'"]
+        );
+        assert_eq!(
+            literals(SqlDialect::PostgreSql, column),
+            ["'it''s'", "'C:\\temp'", "'a\tb\nc'", "'nul\0'"]
+        );
+
+        let categories = Series::new("c".into(), ["a\tb", "x"])
+            .cast(&DataType::from_categories(
+                polars::prelude::Categories::global(),
+            ))
+            .unwrap()
+            .into_column();
+        assert_eq!(literals(SqlDialect::Sqlite, categories), ["'a\tb'", "'x'"]);
+    }
+
+    #[test]
+    fn test_binary_literals() {
+        let column = Column::new("b".into(), [&[0x00_u8, 0xff, 0x1a][..]]);
+        assert_eq!(
+            literals(SqlDialect::PostgreSql, column.clone()),
+            ["'\\x00ff1a'"]
+        );
+        assert_eq!(literals(SqlDialect::MySql, column), ["X'00ff1a'"]);
+    }
+
+    #[test]
+    fn test_float_literals() {
+        let column = Column::new(
+            "f".into(),
+            [1.5, f64::NAN, f64::INFINITY, f64::NEG_INFINITY],
+        );
+        assert_eq!(
+            literals(SqlDialect::PostgreSql, column.clone()),
+            ["1.5", "'NaN'", "'Infinity'", "'-Infinity'"]
+        );
+        assert_eq!(
+            literals(SqlDialect::MySql, column),
+            ["1.5", "NULL", "NULL", "NULL"]
+        );
+    }
+
+    #[test]
+    fn test_temporal_literals() {
+        let df = df!(
+            "date" => [chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()],
+            "time" => [chrono::NaiveTime::from_hms_opt(8, 30, 5).unwrap()],
+            "ts" => [chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+                .unwrap()
+                .and_hms_opt(23, 30, 0)
+                .unwrap()],
+        )
+        .unwrap();
+        let zoned = df
+            .column("ts")
+            .unwrap()
+            .cast(&DataType::Datetime(
+                TimeUnit::Microseconds,
+                TimeZone::opt_try_new(Some("Asia/Tokyo")).unwrap(),
+            ))
+            .unwrap();
+        assert_eq!(
+            literals(SqlDialect::MySql, zoned.clone()),
+            ["'2024-02-29 23:30:00.000000'"]
+        );
+        assert_eq!(
+            literals(SqlDialect::PostgreSql, zoned),
+            ["'2024-03-01 08:30:00.000000+09:00'"]
+        );
+        for dialect in [SqlDialect::MySql, SqlDialect::PostgreSql] {
+            let literal = |name| literals(dialect, df.column(name).unwrap().clone());
+            assert_eq!(literal("date"), ["'2024-02-29'"]);
+            assert_eq!(literal("time"), ["'08:30:05'"]);
+            assert_eq!(literal("ts"), ["'2024-02-29 23:30:00.000'"]);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use std::{fmt::Display, path::Path};
+
+use anyhow::anyhow;
+use polars::{
+    frame::DataFrame,
+    prelude::{AnyValue, Column, DataType},
+};
+use rusqlite::{Connection, params_from_iter, types::Value};
+use strum_macros::{EnumIter, IntoStaticStr};
+
+use crate::{AppResult, misc::polars_ext::AnyValueExt};
+
+use super::traits::{Destination, WriteToFile};
+
+/// What to do when the table already exists in the database.
+#[derive(Debug, Default, Clone, Copy, PartialEq, EnumIter, IntoStaticStr)]
+pub enum SqliteWriteMode {
+    #[default]
+    Create,
+    Replace,
+    Append,
+}
+
+impl Display for SqliteWriteMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Into::<&str>::into(self))
+    }
+}
+
+#[derive(Debug)]
+pub struct WriteToSqlite {
+    table: String,
+    mode: SqliteWriteMode,
+}
+
+impl WriteToSqlite {
+    pub fn with_table(mut self, table: impl Into<String>) -> Self {
+        self.table = table.into();
+        self
+    }
+
+    pub fn with_mode(mut self, mode: SqliteWriteMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Writes each data frame to its own table of one database, in a single transaction.
+    pub fn write_tables<'a>(
+        &self,
+        path: impl AsRef<Path>,
+        tables: impl IntoIterator<Item = (&'a str, &'a DataFrame)>,
+    ) -> AppResult<()> {
+        let mut conn = Connection::open(path)?;
+        let tx = conn.transaction()?;
+        for (name, df) in tables {
+            write_table(&tx, name, df, self.mode)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+impl Default for WriteToSqlite {
+    fn default() -> Self {
+        Self {
+            table: "data".to_owned(),
+            mode: Default::default(),
+        }
+    }
+}
+
+impl WriteToFile for WriteToSqlite {
+    fn write_to_file(&self, dest: Destination, data_frame: &mut DataFrame) -> AppResult<()> {
+        match dest {
+            Destination::File(path) => {
+                self.write_tables(path, [(self.table.as_str(), &*data_frame)])
+            }
+            Destination::Clipboard => {
+                Err(anyhow!("SQLite databases can only be exported to a file"))
+            }
+        }
+    }
+}
+
+fn write_table(
+    conn: &Connection,
+    name: &str,
+    df: &DataFrame,
+    mode: SqliteWriteMode,
+) -> AppResult<()> {
+    let table = quote(name);
+    let definition = df
+        .columns()
+        .iter()
+        .map(|column| format!("{} {}", quote(column.name()), sqlite_type(column.dtype())))
+        .collect::<Vec<_>>()
+        .join(", ");
+    match mode {
+        SqliteWriteMode::Create => {
+            conn.execute(&format!("CREATE TABLE {table} ({definition})"), [])?;
+        }
+        SqliteWriteMode::Replace => {
+            conn.execute(&format!("DROP TABLE IF EXISTS {table}"), [])?;
+            conn.execute(&format!("CREATE TABLE {table} ({definition})"), [])?;
+        }
+        SqliteWriteMode::Append => {
+            conn.execute(
+                &format!("CREATE TABLE IF NOT EXISTS {table} ({definition})"),
+                [],
+            )?;
+        }
+    }
+    if df.width() == 0 {
+        return Ok(());
+    }
+
+    let columns = df
+        .columns()
+        .iter()
+        .map(sqlite_column)
+        .collect::<AppResult<Vec<_>>>()?;
+    let mut stmt = conn.prepare(&format!(
+        "INSERT INTO {table} ({}) VALUES ({})",
+        df.columns()
+            .iter()
+            .map(|column| quote(column.name()))
+            .collect::<Vec<_>>()
+            .join(", "),
+        vec!["?"; df.width()].join(", ")
+    ))?;
+    for idx in 0..df.height() {
+        let values = columns
+            .iter()
+            .map(|column| column.get(idx).map(sqlite_value))
+            .collect::<Result<Vec<_>, _>>()?;
+        stmt.execute(params_from_iter(values))?;
+    }
+    Ok(())
+}
+
+fn sqlite_type(dtype: &DataType) -> &'static str {
+    match dtype {
+        DataType::Boolean => "INTEGER",
+        dtype if dtype.is_integer() => "INTEGER",
+        dtype if dtype.is_float() || dtype.is_decimal() => "REAL",
+        DataType::Binary => "BLOB",
+        _ => "TEXT",
+    }
+}
+
+/// Casts the column to the representation its values are stored with.
+fn sqlite_column(column: &Column) -> AppResult<Column> {
+    Ok(match column.dtype() {
+        DataType::Boolean => column.cast(&DataType::Int64)?,
+        // SQLite integers are signed 64-bit, so larger unsigned values are refused
+        dtype if dtype.is_integer() => column.strict_cast(&DataType::Int64).map_err(|_| {
+            anyhow!(
+                "Column '{}' holds integers too large for SQLite",
+                column.name()
+            )
+        })?,
+        dtype if dtype.is_float() || dtype.is_decimal() => column.cast(&DataType::Float64)?,
+        dtype if dtype.is_temporal() => column.cast(&DataType::String)?,
+        _ => column.clone(),
+    })
+}
+
+fn sqlite_value(value: AnyValue) -> Value {
+    match value {
+        AnyValue::Null => Value::Null,
+        AnyValue::Int64(n) => Value::Integer(n),
+        AnyValue::Float64(f) => Value::Real(f),
+        AnyValue::String(s) => Value::Text(s.to_owned()),
+        AnyValue::StringOwned(s) => Value::Text(s.to_string()),
+        AnyValue::Binary(b) => Value::Blob(b.to_owned()),
+        AnyValue::BinaryOwned(b) => Value::Blob(b),
+        value => Value::Text(value.into_single_line()),
+    }
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqlite_column_rejects_large_unsigned_integers() {
+        let column = Column::new("n".into(), [1_u64, u64::MAX]);
+        assert!(sqlite_column(&column).is_err());
+        let column = Column::new("n".into(), [1_u64, i64::MAX as u64]);
+        assert_eq!(sqlite_column(&column).unwrap().dtype(), &DataType::Int64);
+    }
+}
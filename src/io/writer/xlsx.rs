@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+
+use polars::{
+    frame::DataFrame,
+    prelude::{Column, DataType, TimeUnit},
+};
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+
+use crate::{
+    AppResult,
+    misc::{osc52::CopyToClipboardOsc52, polars_ext::DataFrameExt},
+};
+
+use super::traits::{Destination, WriteToFile};
+
+// Excel counts days from 1899-12-30, which is 25569 days before the Unix epoch
+const EXCEL_EPOCH_OFFSET: f64 = 25569.0;
+const MAX_SHEET_NAME_LEN: usize = 31;
+const MAX_STRING_LEN: usize = 32767;
+const MAX_COLUMN_WIDTH: usize = 60;
+
+#[derive(Debug, Default)]
+pub struct WriteToXlsx;
+
+impl WriteToXlsx {
+    /// Writes each data frame to a separate sheet of one workbook.
+    pub fn write_sheets<'a>(
+        &self,
+        dest: Destination,
+        sheets: impl IntoIterator<Item = (&'a str, &'a DataFrame)>,
+    ) -> AppResult<()> {
+        let mut workbook = Workbook::new();
+        let mut names = HashSet::new();
+        for (name, df) in sheets {
+            let worksheet = workbook.add_worksheet();
+            worksheet.set_name(unique_sheet_name(name, &mut names))?;
+            write_sheet(worksheet, df)?;
+        }
+        match dest {
+            Destination::File(path) => {
+                workbook.save(path)?;
+                Ok(())
+            }
+            Destination::Clipboard => {
+                workbook.save_to_buffer()?.copy_to_clipboard_via_osc52();
+                Ok(())
+            }
+        }
+    }
+}
+
+impl WriteToFile for WriteToXlsx {
+    fn write_to_file(&self, dest: Destination, data_frame: &mut DataFrame) -> AppResult<()> {
+        self.write_sheets(dest, [("Sheet1", &*data_frame)])
+    }
+}
+
+fn write_sheet(worksheet: &mut Worksheet, df: &DataFrame) -> AppResult<()> {
+    let header = Format::new().set_bold();
+    for (idx, (column, width)) in df.columns().iter().zip(df.widths()).enumerate() {
+        let col = idx as u16;
+        worksheet.write_string_with_format(0, col, column.name().as_str(), &header)?;
+        worksheet.set_column_width(col, (width.min(MAX_COLUMN_WIDTH) + 2) as f64)?;
+        write_column(worksheet, col, column)?;
+    }
+    worksheet.set_freeze_panes(1, 0)?;
+    Ok(())
+}
+
+fn write_column(worksheet: &mut Worksheet, col: u16, column: &Column) -> AppResult<()> {
+    let rows = (1_u32..).zip(0..column.len());
+    match column.dtype() {
+        DataType::Boolean => {
+            for ((row, _), value) in rows.zip(column.bool()?.iter()) {
+                if let Some(value) = value {
+                    worksheet.write_boolean(row, col, value)?;
+                }
+            }
+        }
+        DataType::Date => {
+            let format = Format::new().set_num_format("yyyy-mm-dd");
+            for ((row, _), days) in rows.zip(column.date()?.physical().iter()) {
+                if let Some(days) = days {
+                    let serial = days as f64 + EXCEL_EPOCH_OFFSET;
+                    worksheet.write_number_with_format(row, col, serial, &format)?;
+                }
+            }
+        }
+        DataType::Datetime(unit, _) => {
+            let format = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
+            let per_day = match unit {
+                TimeUnit::Milliseconds => 86_400_000.0,
+                TimeUnit::Microseconds => 86_400_000_000.0,
+                TimeUnit::Nanoseconds => 86_400_000_000_000.0,
+            };
+            for ((row, _), value) in rows.zip(column.datetime()?.physical().iter()) {
+                if let Some(value) = value {
+                    let serial = value as f64 / per_day + EXCEL_EPOCH_OFFSET;
+                    worksheet.write_number_with_format(row, col, serial, &format)?;
+                }
+            }
+        }
+        DataType::Time => {
+            let format = Format::new().set_num_format("hh:mm:ss");
+            for ((row, _), value) in rows.zip(column.to_physical_repr().i64()?.iter()) {
+                if let Some(value) = value {
+                    let serial = value as f64 / 86_400_000_000_000.0;
+                    worksheet.write_number_with_format(row, col, serial, &format)?;
+                }
+            }
+        }
+        dtype if dtype.is_primitive_numeric() || dtype.is_decimal() => {
+            let values = column.cast(&DataType::Float64)?;
+            for ((row, _), value) in rows.zip(values.f64()?.iter()) {
+                if let Some(value) = value {
+                    worksheet.write_number(row, col, value)?;
+                }
+            }
+        }
+        DataType::String => {
+            for ((row, _), value) in rows.zip(column.str()?.iter()) {
+                if let Some(value) = value {
+                    worksheet.write_string(row, col, truncate(value))?;
+                }
+            }
+        }
+        _ => {
+            for (row, idx) in rows {
+                let value = column.get(idx)?;
+                if !value.is_null() {
+                    worksheet.write_string(row, col, truncate(&value.to_string()))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Excel sheet names are at most 31 characters long, exclude `[]:*?/\` and are unique.
+fn unique_sheet_name(name: &str, names: &mut HashSet<String>) -> String {
+    let base = name
+        .chars()
+        .map(|c| if "[]:*?/\\".contains(c) { '_' } else { c })
+        .collect::<String>()
+        .trim_matches('\'')
+        .chars()
+        .take(MAX_SHEET_NAME_LEN)
+        .collect::<String>();
+    let base = if base.is_empty() {
+        "Sheet".to_owned()
+    } else {
+        base
+    };
+    let mut candidate = base.clone();
+    let mut suffix = 1;
+    while !names.insert(candidate.to_lowercase()) {
+        suffix += 1;
+        let suffix = format!(" ({suffix})");
+        candidate = base
+            .chars()
+            .take(MAX_SHEET_NAME_LEN - suffix.len())
+            .chain(suffix.chars())
+            .collect();
+    }
+    candidate
+}
+
+fn truncate(value: &str) -> &str {
+    match value.char_indices().nth(MAX_STRING_LEN) {
+        Some((idx, _)) => &value[..idx],
+        None => value,
+    }
+}
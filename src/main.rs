@@ -1,4 +1,4 @@
-use clap::{CommandFactory, Parser};
+use clap::{CommandFactory, Parser, error::ErrorKind};
 use indexmap::IndexMap;
 use polars::frame::DataFrame;
 use polars::prelude::Schema;
@@ -10,11 +10,14 @@ use tabiew::handler::event::{Event, read_event};
 use tabiew::handler::message::Message;
 use tabiew::io::DataSource;
 use tabiew::io::reader::ReaderSource;
-use tabiew::io::reader::{BuildReader, NamedFrames};
+use tabiew::io::reader::{BuildReader, NamedFrame, NamedFrames};
 use tabiew::misc::config::config;
 use tabiew::misc::download::download_to_temp;
+use tabiew::misc::encoding::TextEncoding;
 use tabiew::misc::osc52::flush_osc52_buffer;
-use tabiew::misc::sql::{TableSource, sql};
+use tabiew::misc::remote_load::Reader;
+use tabiew::misc::sql::{TableReader, TableSource, sql};
+use tabiew::misc::sql_script::run_script;
 use tabiew::misc::type_ext::UnwrapOrGracefulShutdown;
 use tabiew::misc::type_inferer::TypeInferer;
 use tabiew::tui::component::{Component, FocusState};
@@ -35,6 +38,9 @@ fn main() {
             Args::parse_from(args_os)
         }
     };
+    if let Err(err) = args.encoding.parse::<TextEncoding>() {
+        Args::command().error(ErrorKind::InvalidValue, err).exit();
+    }
 
     let _ = config().reload();
 
@@ -46,7 +52,8 @@ fn main() {
     // Load multiparts to data frames
     let mut multiparts = IndexMap::<Arc<Schema>, (String, DataFrame)>::new();
     for resource in args.multiparts.iter() {
-        for (name, new_df) in try_read_path(&args, resource).unwrap_or_graceful_shutdown() {
+        let (_, frames) = try_read_path(&args, resource).unwrap_or_graceful_shutdown();
+        for (name, new_df, _) in frames.into_iter().map(NamedFrame::into_parts) {
             let schema = new_df.schema().clone();
             if let Some((_, df)) = multiparts.get_mut(&schema) {
                 df.vstack_mut_owned(new_df).unwrap_or_graceful_shutdown();
@@ -64,39 +71,60 @@ fn main() {
 
     // Load files to data frames
     for resource in args.resources.iter() {
-        for (name, mut df) in try_read_path(&args, resource).unwrap_or_graceful_shutdown() {
+        let (reader, frames) = try_read_path(&args, resource).unwrap_or_graceful_shutdown();
+        for (frame, mut df, metadata) in frames.into_iter().map(NamedFrame::into_parts) {
             type_infer.update(&mut df);
-            let name = sql().register(&name, df.clone(), resource.clone());
+            let name = sql().register(&frame, df.clone(), resource.clone());
+            sql().set_metadata(&name, metadata);
+            sql().set_reader(
+                &name,
+                TableReader::new(reader.clone(), frame).with_type_inferer(type_infer),
+            );
             name_dfs.push((name, df))
         }
     }
 
     if name_dfs.is_empty() {
-        for (name, mut df) in args
+        for (name, mut df, metadata) in args
             .build_reader("")
             .unwrap_or_graceful_shutdown()
             .read_to_data_frames(ReaderSource::Stdin)
             .unwrap_or_graceful_shutdown()
+            .into_iter()
+            .map(NamedFrame::into_parts)
         {
             type_infer.update(&mut df);
             let name = sql().register(&name, df.clone(), TableSource::Stdin);
+            sql().set_metadata(&name, metadata);
             name_dfs.push((name, df))
         }
     }
 
+    let mut tabs = name_dfs
+        .into_iter()
+        .map(|(name, df)| Pane::new(df, TableDescription::Table(name)))
+        .collect::<Vec<_>>();
+
+    // Run the script against the loaded tables
+    if let Some(path) = args.script.as_ref() {
+        let script = std::fs::read_to_string(path).unwrap_or_graceful_shutdown();
+        let default = tabs.first().map(|pane| pane.table().data_frame().clone());
+        if let Some((query, df)) = run_script(&script, default)
+            .unwrap_or_graceful_shutdown()
+            .result
+        {
+            tabs.push(Pane::new(df, TableDescription::Query(query)));
+        }
+    }
+
     start_tui().unwrap_or_graceful_shutdown();
-    start_app(name_dfs).unwrap_or_graceful_shutdown();
+    start_app(tabs).unwrap_or_graceful_shutdown();
     let _ = stop_tui();
 }
 
-fn start_app(tabs: Vec<(String, DataFrame)>) -> AppResult<()> {
-    let tabs = tabs
-        .into_iter()
-        .map(|(name, df)| Pane::new(df, TableDescription::Table(name)))
-        .collect();
-
+fn start_app(tabs: Vec<Pane>) -> AppResult<()> {
     // Initialize the app
-    let mut app = App::new(tabs);
+    let mut app = App::new(tabs.into_iter().collect());
 
     // Main loop
     while app.running() {
@@ -137,18 +165,24 @@ fn start_app(tabs: Vec<(String, DataFrame)>) -> AppResult<()> {
     Ok(())
 }
 
-fn try_read_path(args: &Args, resource: &DataSource) -> AppResult<NamedFrames> {
+/// Reads the resource, returning the reader as well so that its tables can be reloaded.
+fn try_read_path(args: &Args, resource: &DataSource) -> AppResult<(Arc<dyn Reader>, NamedFrames)> {
     match resource {
-        DataSource::Stdin => args
-            .build_reader("")?
-            .read_to_data_frames(ReaderSource::Stdin),
-        DataSource::File(path_buf) => args
-            .build_reader(path_buf)?
-            .read_to_data_frames(ReaderSource::File(path_buf.clone())),
+        DataSource::Stdin => {
+            let reader = Arc::<dyn Reader>::from(args.build_reader("")?);
+            let frames = reader.read_to_data_frames(ReaderSource::Stdin)?;
+            Ok((reader, frames))
+        }
+        DataSource::File(path_buf) => {
+            let reader = Arc::<dyn Reader>::from(args.build_reader(path_buf)?);
+            let frames = reader.read_to_data_frames(ReaderSource::File(path_buf.clone()))?;
+            Ok((reader, frames))
+        }
         DataSource::Url(url) => {
             let file = download_to_temp(url)?;
-            args.build_reader(file.path())?
-                .read_to_data_frames(ReaderSource::File(file.path().to_owned()))
+            let reader = Arc::<dyn Reader>::from(args.build_reader(file.path())?);
+            let frames = reader.read_to_data_frames(ReaderSource::File(file.path().to_owned()))?;
+            Ok((reader, frames))
         }
     }
 }
@@ -2,6 +2,7 @@ use ratatui::style::Color;
 
 pub trait ColorExt {
     fn darken(&self) -> Self;
+    fn css(&self) -> Option<String>;
 }
 
 impl ColorExt for Color {
@@ -15,4 +16,27 @@ impl ColorExt for Color {
             _ => *self,
         }
     }
+
+    fn css(&self) -> Option<String> {
+        match self {
+            Color::Reset | Color::Indexed(_) => None,
+            Color::Rgb(r, g, b) => Some(format!("#{r:02x}{g:02x}{b:02x}")),
+            Color::Black => Some("black".to_owned()),
+            Color::Red => Some("darkred".to_owned()),
+            Color::Green => Some("green".to_owned()),
+            Color::Yellow => Some("olive".to_owned()),
+            Color::Blue => Some("navy".to_owned()),
+            Color::Magenta => Some("purple".to_owned()),
+            Color::Cyan => Some("teal".to_owned()),
+            Color::Gray => Some("silver".to_owned()),
+            Color::DarkGray => Some("gray".to_owned()),
+            Color::LightRed => Some("red".to_owned()),
+            Color::LightGreen => Some("lime".to_owned()),
+            Color::LightYellow => Some("yellow".to_owned()),
+            Color::LightBlue => Some("blue".to_owned()),
+            Color::LightMagenta => Some("fuchsia".to_owned()),
+            Color::LightCyan => Some("aqua".to_owned()),
+            Color::White => Some("white".to_owned()),
+        }
+    }
 }
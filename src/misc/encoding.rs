@@ -0,0 +1,147 @@
+use std::{borrow::Cow, fmt::Display, iter::once, str::FromStr};
+
+use chardetng::{EncodingDetector, Iso2022JpDetection, Utf8Detection};
+use encoding_rs::{
+    BIG5, EUC_JP, EUC_KR, Encoding, GB18030, GBK, ISO_8859_2, ISO_8859_5, ISO_8859_7, ISO_8859_15,
+    KOI8_R, SHIFT_JIS, UTF_8, UTF_16BE, UTF_16LE, WINDOWS_1250, WINDOWS_1251, WINDOWS_1252,
+    WINDOWS_1253, WINDOWS_1254, WINDOWS_1256,
+};
+
+use crate::args::Args;
+
+/// Bytes looked at when guessing an encoding that is not UTF-8.
+pub const SNIFF_LEN: usize = 1 << 20;
+
+/// Character encoding of a text input, transcoded to UTF-8 before parsing.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum TextEncoding {
+    /// Detected from the byte order mark, or guessed from the content.
+    #[default]
+    Auto,
+    Fixed(&'static Encoding),
+}
+
+impl TextEncoding {
+    /// The `--encoding` argument, which is checked once the arguments are parsed.
+    pub fn from_args(args: &Args) -> Self {
+        args.encoding.parse().unwrap_or_default()
+    }
+
+    /// Encodings offered by the importers, starting with `Auto`.
+    pub fn common() -> Vec<TextEncoding> {
+        let fixed = [
+            UTF_8,
+            UTF_16LE,
+            UTF_16BE,
+            WINDOWS_1252,
+            ISO_8859_15,
+            WINDOWS_1250,
+            ISO_8859_2,
+            WINDOWS_1251,
+            KOI8_R,
+            ISO_8859_5,
+            WINDOWS_1253,
+            ISO_8859_7,
+            WINDOWS_1254,
+            WINDOWS_1256,
+            SHIFT_JIS,
+            EUC_JP,
+            EUC_KR,
+            GBK,
+            GB18030,
+            BIG5,
+        ];
+        once(TextEncoding::Auto)
+            .chain(fixed.into_iter().map(TextEncoding::Fixed))
+            .collect()
+    }
+
+    /// Whether an input starting with `head` can be parsed as it is, being UTF-8 without a
+    /// byte order mark. `Auto` judges it from `head` alone.
+    pub fn is_plain_utf8(self, head: &[u8]) -> bool {
+        match self {
+            TextEncoding::Auto => {
+                // the head may end in the middle of a character
+                Encoding::for_bom(head).is_none()
+                    && str::from_utf8(head).map_or_else(|err| err.error_len().is_none(), |_| true)
+            }
+            TextEncoding::Fixed(encoding) => encoding == UTF_8 && Encoding::for_bom(head).is_none(),
+        }
+    }
+
+    /// Transcodes the bytes to UTF-8, dropping the byte order mark if any.
+    pub fn to_utf8(self, mut bytes: Vec<u8>) -> Vec<u8> {
+        let encoding = match self {
+            TextEncoding::Auto => detect(&bytes),
+            TextEncoding::Fixed(encoding) => encoding,
+        };
+        let bom_len = match encoding.decode_with_bom_removal(&bytes).0 {
+            Cow::Borrowed(text) => bytes.len() - text.len(),
+            Cow::Owned(text) => return text.into_bytes(),
+        };
+        bytes.drain(..bom_len);
+        bytes
+    }
+}
+
+impl Display for TextEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextEncoding::Auto => write!(f, "Auto"),
+            TextEncoding::Fixed(encoding) => write!(f, "{}", encoding.name()),
+        }
+    }
+}
+
+impl FromStr for TextEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            Ok(TextEncoding::Auto)
+        } else {
+            Encoding::for_label(s.as_bytes())
+                .map(TextEncoding::Fixed)
+                .ok_or(format!("Unknown encoding: {s}"))
+        }
+    }
+}
+
+/// Picks the encoding from the byte order mark, falling back to UTF-8 when the bytes are
+/// valid UTF-8, to UTF-16 when every other byte is zero, and to a statistical guess otherwise.
+fn detect(bytes: &[u8]) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return encoding;
+    }
+    if str::from_utf8(bytes).is_ok() {
+        return UTF_8;
+    }
+    let sample = &bytes[..bytes.len().min(SNIFF_LEN)];
+    if let Some(encoding) = detect_utf16(sample) {
+        return encoding;
+    }
+    let mut detector = EncodingDetector::new(Iso2022JpDetection::Deny);
+    detector.feed(sample, sample.len() == bytes.len());
+    detector.guess(None, Utf8Detection::Allow)
+}
+
+/// Text in UTF-16 without a byte order mark, mostly ASCII, has a zero in every other byte.
+fn detect_utf16(sample: &[u8]) -> Option<&'static Encoding> {
+    let pairs = sample.len() / 2;
+    if pairs == 0 {
+        return None;
+    }
+    let (even, odd) = sample.chunks_exact(2).fold((0, 0), |(even, odd), pair| {
+        (
+            even + (pair[0] == 0) as usize,
+            odd + (pair[1] == 0) as usize,
+        )
+    });
+    if odd * 2 > pairs && even * 10 < pairs {
+        Some(UTF_16LE)
+    } else if even * 2 > pairs && odd * 10 < pairs {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
@@ -40,10 +40,12 @@ pub fn edit_in_external_editor(mut df: DataFrame) -> AppResult<DataFrame> {
             .with_no_header(false)
             .with_quote_char('"')
             .with_separator(',')
+            .with_skip_lines(0)
+            .with_comment_prefix(None)
             .read_to_data_frames(ReaderSource::File(tempfile.path().to_owned()))?
             .into_iter()
             .next()
-            .map(|(_, df)| df)
+            .map(|frame| frame.into_parts().1)
             .ok_or(anyhow!("Failed to load data frame back from the editor"))?;
         let ti = TypeInferer::default().boolean().int().float();
         ti.update(&mut df);
@@ -1,34 +1,168 @@
-use std::sync::{Arc, LazyLock, Mutex};
+use std::{
+    fs,
+    ops::DerefMut,
+    sync::{LazyLock, Mutex},
+};
 
-type Amv<T> = Arc<Mutex<Vec<T>>>;
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
-pub struct History<T> {
-    len: usize,
-    vec: LazyLock<Amv<T>>,
+use crate::{AppResult, misc::paths::history_path};
+
+use super::type_ext::UnwrapOrGracefulShutdown;
+
+/// Entries kept for each kind of query.
+const HISTORY_LEN: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryKind {
+    Sql,
+    Select,
+    Filter,
+    Order,
 }
 
-impl<T> History<T>
-where
-    T: Clone,
-{
-    pub const fn new(len: usize) -> Self {
-        Self {
-            len,
-            vec: LazyLock::new(|| Arc::new(Mutex::new(Vec::default()))),
+/// Queries run in previous and current sessions, most recent first.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueryHistory {
+    sql: Vec<String>,
+    select: Vec<String>,
+    filter: Vec<String>,
+    order: Vec<String>,
+}
+
+impl QueryHistory {
+    pub fn load() -> AppResult<Self> {
+        let contents = fs::read_to_string(history_path()?)?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    pub fn store(&self) -> AppResult<()> {
+        let path = history_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(fs::write(path, toml::to_string_pretty(self)?)?)
+    }
+
+    pub fn entries(&self, kind: HistoryKind) -> &[String] {
+        match kind {
+            HistoryKind::Sql => &self.sql,
+            HistoryKind::Select => &self.select,
+            HistoryKind::Filter => &self.filter,
+            HistoryKind::Order => &self.order,
+        }
+    }
+
+    fn entries_mut(&mut self, kind: HistoryKind) -> &mut Vec<String> {
+        match kind {
+            HistoryKind::Sql => &mut self.sql,
+            HistoryKind::Select => &mut self.select,
+            HistoryKind::Filter => &mut self.filter,
+            HistoryKind::Order => &mut self.order,
+        }
+    }
+
+    /// Moves the query to the front, removing earlier copies of it.
+    pub fn push(&mut self, kind: HistoryKind, query: &str) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
         }
+        let entries = self.entries_mut(kind);
+        entries.retain(|entry| entry != query);
+        entries.insert(0, query.to_owned());
+        entries.truncate(HISTORY_LEN);
+    }
+}
+
+pub fn history() -> impl DerefMut<Target = QueryHistory> {
+    static HISTORY: LazyLock<Mutex<QueryHistory>> =
+        LazyLock::new(|| Mutex::new(QueryHistory::load().unwrap_or_default()));
+    HISTORY.lock().unwrap_or_graceful_shutdown()
+}
+
+/// Entries fuzzy matching the pattern, best match first and most recent first on ties.
+pub fn search<'a>(entries: &'a [String], pattern: &str) -> Vec<&'a str> {
+    let matcher = SkimMatcherV2::default();
+    entries
+        .iter()
+        .filter_map(|entry| {
+            matcher
+                .fuzzy_match(entry, pattern)
+                .map(|score| (score, entry.as_str()))
+        })
+        .enumerate()
+        .sorted_by_key(|(idx, (score, _))| (-score, *idx))
+        .map(|(_, (_, entry))| entry)
+        .collect()
+}
+
+/// Records the query and saves the history, keeping it in memory only if saving fails.
+pub fn remember(kind: HistoryKind, query: &str) {
+    let mut history = history();
+    history.push(kind, query);
+    let _ = history.store();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_deduplicates_and_keeps_most_recent_first() {
+        let mut history = QueryHistory::default();
+        history.push(HistoryKind::Sql, "SELECT a FROM t");
+        history.push(HistoryKind::Sql, "SELECT b FROM t");
+        history.push(HistoryKind::Sql, " SELECT a FROM t ");
+        history.push(HistoryKind::Filter, "a > 1");
+        history.push(HistoryKind::Sql, "  ");
+        assert_eq!(
+            history.entries(HistoryKind::Sql),
+            ["SELECT a FROM t", "SELECT b FROM t"]
+        );
+        assert_eq!(history.entries(HistoryKind::Filter), ["a > 1"]);
+        assert!(history.entries(HistoryKind::Order).is_empty());
     }
 
-    pub fn push(&self, val: T) {
-        if let Ok(mut v) = self.vec.lock() {
-            v.insert(0, val);
-            if v.len() > self.len {
-                v.pop();
-            }
+    #[test]
+    fn test_push_truncates() {
+        let mut history = QueryHistory::default();
+        for idx in 0..HISTORY_LEN + 10 {
+            history.push(HistoryKind::Order, &idx.to_string());
         }
+        assert_eq!(history.entries(HistoryKind::Order).len(), HISTORY_LEN);
+        assert_eq!(
+            history.entries(HistoryKind::Order)[0],
+            (HISTORY_LEN + 9).to_string()
+        );
+    }
+
+    #[test]
+    fn test_search_fuzzy() {
+        let mut history = QueryHistory::default();
+        history.push(HistoryKind::Sql, "SELECT price FROM houses");
+        history.push(HistoryKind::Sql, "SELECT * FROM users");
+        history.push(HistoryKind::Sql, "SELECT count(*) FROM orders");
+        assert_eq!(
+            search(history.entries(HistoryKind::Sql), "prc hous"),
+            ["SELECT price FROM houses"]
+        );
+        assert_eq!(
+            search(history.entries(HistoryKind::Sql), "").len(),
+            3,
+            "an empty pattern matches every entry"
+        );
     }
 
-    pub fn to_vec(&self) -> Vec<T> {
-        self.vec.lock().unwrap().clone()
+    #[test]
+    fn test_round_trip() {
+        let mut history = QueryHistory::default();
+        history.push(HistoryKind::Select, "a, b");
+        history.push(HistoryKind::Sql, "SELECT 'it''s' FROM \"t\"\nWHERE x = 1");
+        let contents = toml::to_string_pretty(&history).unwrap();
+        assert_eq!(toml::from_str::<QueryHistory>(&contents).unwrap(), history);
     }
 }
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use polars::{
+    error::PolarsResult,
+    prelude::{Categories, DataType, NamedFrom},
+    series::Series,
+};
+
+/// Metadata that some readers (e.g., SPSS, Stata and Delta) find next to the data itself.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableMetadata {
+    version: Option<String>,
+    fields: IndexMap<String, FieldMetadata>,
+}
+
+impl TableMetadata {
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    pub fn with_field(mut self, name: impl Into<String>, field: FieldMetadata) -> Self {
+        if !field.is_empty() {
+            self.fields.insert(name.into(), field);
+        }
+        self
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldMetadata> {
+        self.fields.get(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.version.is_none() && self.fields.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldMetadata {
+    label: Option<String>,
+    value_labels: Vec<(String, String)>,
+}
+
+impl FieldMetadata {
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        self.label = (!label.is_empty()).then_some(label);
+        self
+    }
+
+    pub fn with_value_labels(mut self, value_labels: Vec<(String, String)>) -> Self {
+        self.value_labels = value_labels;
+        self
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn value_labels(&self) -> &[(String, String)] {
+        &self.value_labels
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.label.is_none() && self.value_labels.is_empty()
+    }
+
+    /// Replaces the values of the series with their labels and returns it as a categorical
+    /// series. Values without a label are kept as they are.
+    pub fn apply_value_labels(&self, series: &Series) -> PolarsResult<Series> {
+        let labels = self
+            .value_labels
+            .iter()
+            .map(|(value, label)| (value.as_str(), label.as_str()))
+            .collect::<HashMap<_, _>>();
+        let relabel = |key: String| {
+            labels
+                .get(key.as_str())
+                .map(|label| label.to_string())
+                .unwrap_or(key)
+        };
+        let values = if series.dtype().is_primitive_numeric() {
+            series
+                .cast(&DataType::Float64)?
+                .f64()?
+                .iter()
+                .map(|value| value.map(value_label_key).map(relabel))
+                .collect::<Vec<_>>()
+        } else {
+            series
+                .cast(&DataType::String)?
+                .str()?
+                .iter()
+                .map(|value| value.map(str::to_owned).map(relabel))
+                .collect::<Vec<_>>()
+        };
+        Series::new(series.name().clone(), values)
+            .cast(&DataType::from_categories(Categories::global()))
+    }
+}
+
+/// Key under which a numeric value is looked up in the value labels, e.g., `1` and not `1.0`.
+pub fn value_label_key(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        (value as i64).to_string()
+    } else {
+        value.to_string()
+    }
+}
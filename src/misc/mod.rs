@@ -2,18 +2,24 @@ pub mod background;
 pub mod color_ext;
 pub mod config;
 pub mod download;
+pub mod encoding;
 pub mod external_editor;
 pub mod history;
 pub mod http;
 pub mod iter_ext;
+pub mod metadata;
 pub mod non_empty_stack;
 pub mod osc52;
 pub mod paths;
 pub mod polars_ext;
+pub mod query_job;
+pub mod query_plan;
 pub mod ragged_vec;
 pub mod remote_load;
 pub mod search;
+pub mod snippets;
 pub mod sql;
+pub mod sql_script;
 pub mod stdin;
 pub mod table_name_generator;
 pub mod type_ext;
@@ -10,6 +10,18 @@ pub fn theme_path() -> AppResult<PathBuf> {
         .ok_or(anyhow!("Home dir not found"))
 }
 
+pub fn history_path() -> AppResult<PathBuf> {
+    home::home_dir()
+        .map(|path| path.join(".config").join("tabiew").join("history.toml"))
+        .ok_or(anyhow!("Home dir not found"))
+}
+
+pub fn snippets_path() -> AppResult<PathBuf> {
+    home::home_dir()
+        .map(|path| path.join(".config").join("tabiew").join("snippets.toml"))
+        .ok_or(anyhow!("Home dir not found"))
+}
+
 pub fn config_path() -> AppResult<PathBuf> {
     home::home_dir()
         .map(|path| path.join(".config").join("tabiew").join("config.toml"))
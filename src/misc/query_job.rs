@@ -0,0 +1,167 @@
+use std::{
+    fmt::Debug,
+    thread,
+    time::{Duration, Instant},
+};
+
+use polars::{
+    error::PolarsResult,
+    frame::DataFrame,
+    prelude::{IntoLazy, LazyFrame},
+};
+
+use crate::{
+    misc::{
+        background::{BackgroundHandle, GetIsAlive, SetProgress, run_in_background},
+        query_plan::QueryPlan,
+        sql::{Definition, Prepared, sql},
+    },
+    tui::pane::TableDescription,
+};
+
+/// Interval between checks for the result or a cancellation.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Where the result of a query goes once collected.
+#[derive(Debug, Clone)]
+pub enum QueryTarget {
+    /// A new tab described by the query.
+    Tab(String),
+    /// The pane the query was started from, announced with a toast.
+    Pane(TableDescription, String),
+    /// A table defined by the statement once its query is collected.
+    Define(Definition),
+    /// A statement the backend already applied, announced with a toast.
+    Applied(String),
+    /// The plans of an `EXPLAIN`, shown with the timings profiled by the query if any.
+    Explain(Box<QueryPlan>),
+}
+
+/// A query parsed against the SQL backend, ready to be collected.
+#[derive(Clone)]
+pub struct PlannedQuery {
+    plan: LazyFrame,
+    target: QueryTarget,
+    profile: bool,
+}
+
+impl PlannedQuery {
+    /// Fails right away on queries the backend cannot parse or resolve. Statements defining
+    /// or changing tables override the target.
+    pub fn new(
+        query: &str,
+        default_table: impl Into<Option<DataFrame>>,
+        target: QueryTarget,
+    ) -> PolarsResult<Self> {
+        Ok(match sql().prepare(query, default_table)? {
+            Prepared::Query(plan) => Self {
+                plan,
+                target,
+                profile: false,
+            },
+            Prepared::Define(definition, plan) => Self {
+                plan,
+                target: QueryTarget::Define(definition),
+                profile: false,
+            },
+            Prepared::Explain { plan, profile } => Self {
+                target: QueryTarget::Explain(Box::new(QueryPlan::new(&plan)?)),
+                plan: if profile {
+                    plan
+                } else {
+                    DataFrame::empty().lazy()
+                },
+                profile,
+            },
+            Prepared::Applied(summary) => Self {
+                plan: DataFrame::empty().lazy(),
+                target: QueryTarget::Applied(summary),
+                profile: false,
+            },
+        })
+    }
+
+    pub fn start(self) -> QueryJob {
+        let (plan, profile) = (self.plan, self.profile);
+        QueryJob {
+            target: self.target,
+            started: Instant::now(),
+            hndl: run_in_background(move |alive, _: SetProgress<()>| {
+                if profile {
+                    self::profile(plan, alive)
+                } else {
+                    collect(plan, &alive)
+                }
+            }),
+        }
+    }
+}
+
+impl Debug for PlannedQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PlannedQuery")
+            .field("target", &self.target)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A planned query collected on a worker thread.
+#[derive(Debug)]
+pub struct QueryJob {
+    target: QueryTarget,
+    started: Instant,
+    hndl: BackgroundHandle<(), Option<PolarsResult<DataFrame>>>,
+}
+
+impl QueryJob {
+    pub fn target(&self) -> &QueryTarget {
+        &self.target
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.hndl.is_running()
+    }
+
+    /// Stops the query at the next opportunity; the worker exits without a result.
+    pub fn cancel(&self) {
+        self.hndl.cancel();
+    }
+
+    /// Waits for the worker, returning `None` if the query was cancelled.
+    pub fn finish(self) -> Option<(QueryTarget, PolarsResult<DataFrame>)> {
+        let target = self.target;
+        self.hndl.result().flatten().map(|result| (target, result))
+    }
+}
+
+/// Runs the query for its timings, which cannot be interrupted; a cancelled profile is
+/// dropped once it completes.
+fn profile(plan: LazyFrame, alive: GetIsAlive) -> Option<PolarsResult<DataFrame>> {
+    let result = plan.profile().map(|(_, timings)| timings);
+    alive.get().then_some(result)
+}
+
+/// Collects the plan, checking for a cancellation while it runs; `None` once cancelled.
+pub fn collect(plan: LazyFrame, alive: &GetIsAlive) -> Option<PolarsResult<DataFrame>> {
+    let query = match plan.collect_concurrently() {
+        Ok(query) => query,
+        Err(err) => return Some(Err(err)),
+    };
+    loop {
+        if let Some(result) = query.fetch() {
+            return Some(result.map(|mut df| {
+                df.rechunk_mut_par();
+                df
+            }));
+        }
+        if !alive.get() {
+            query.cancel();
+            return None;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use polars::{error::PolarsResult, frame::DataFrame, prelude::LazyFrame};
+
+/// The plans polars makes for a query, with the time spent in each node once profiled.
+#[derive(Debug, Clone)]
+pub struct QueryPlan {
+    unoptimized: Vec<PlanNode>,
+    optimized: Vec<PlanNode>,
+    timings: Option<Vec<NodeTiming>>,
+}
+
+/// A line of a plan, nested under the closest preceding line of smaller depth.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanNode {
+    depth: usize,
+    text: String,
+}
+
+/// A node executed while profiling, with its offsets from the start of the query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeTiming {
+    node: String,
+    start: Duration,
+    end: Duration,
+}
+
+impl QueryPlan {
+    pub fn new(plan: &LazyFrame) -> PolarsResult<Self> {
+        Ok(Self {
+            unoptimized: nodes(&plan.describe_plan()?),
+            optimized: nodes(&plan.describe_optimized_plan()?),
+            timings: None,
+        })
+    }
+
+    /// Reads the timings returned by [`LazyFrame::profile`], leaving the plan unprofiled when
+    /// the frame has none.
+    pub fn with_timings(self, timings: &DataFrame) -> Self {
+        let read = || -> PolarsResult<Vec<NodeTiming>> {
+            let nodes = timings.column("node")?.str()?;
+            let starts = timings.column("start")?.u64()?;
+            let ends = timings.column("end")?.u64()?;
+            Ok(nodes
+                .iter()
+                .zip(starts.iter())
+                .zip(ends.iter())
+                .map(|((node, start), end)| NodeTiming {
+                    node: node.unwrap_or_default().to_owned(),
+                    start: Duration::from_micros(start.unwrap_or_default()),
+                    end: Duration::from_micros(end.unwrap_or_default()),
+                })
+                .collect())
+        };
+        Self {
+            timings: read().ok(),
+            ..self
+        }
+    }
+
+    pub fn unoptimized(&self) -> &[PlanNode] {
+        &self.unoptimized
+    }
+
+    pub fn optimized(&self) -> &[PlanNode] {
+        &self.optimized
+    }
+
+    pub fn timings(&self) -> Option<&[NodeTiming]> {
+        self.timings.as_deref()
+    }
+}
+
+impl PlanNode {
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl NodeTiming {
+    pub fn node(&self) -> &str {
+        &self.node
+    }
+
+    pub fn start(&self) -> Duration {
+        self.start
+    }
+
+    pub fn end(&self) -> Duration {
+        self.end
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+/// Index past the last descendant of the node at `idx`.
+pub fn subtree_end(nodes: &[PlanNode], idx: usize) -> usize {
+    let depth = nodes[idx].depth;
+    nodes[idx + 1..]
+        .iter()
+        .position(|node| node.depth <= depth)
+        .map(|offset| idx + 1 + offset)
+        .unwrap_or(nodes.len())
+}
+
+/// Splits a plan described by polars into lines nested by their two space indentation.
+fn nodes(description: &str) -> Vec<PlanNode> {
+    description
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let text = line.trim_start();
+            PlanNode {
+                depth: (line.len() - text.len()) / 2,
+                text: text.to_owned(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::{df, prelude::IntoLazy};
+
+    use super::*;
+
+    #[test]
+    fn test_nodes_and_subtrees() {
+        let nodes = nodes("SORT BY [col(\"s\")]\n  AGGREGATE\n    FROM\n      DF\n  FILTER\n");
+        assert_eq!(
+            nodes.iter().map(PlanNode::depth).collect::<Vec<_>>(),
+            [0, 1, 2, 3, 1]
+        );
+        assert_eq!(subtree_end(&nodes, 0), 5);
+        assert_eq!(subtree_end(&nodes, 1), 4);
+        assert_eq!(subtree_end(&nodes, 3), 4);
+    }
+
+    #[test]
+    fn test_with_timings() {
+        let plan = QueryPlan::new(&df!("a" => [1, 2]).unwrap().lazy()).unwrap();
+        assert!(
+            plan.clone()
+                .with_timings(&DataFrame::empty())
+                .timings()
+                .is_none()
+        );
+
+        let timings = df!(
+            "node" => ["optimization", "sort(a)"],
+            "start" => [0_u64, 100],
+            "end" => [50_u64, 1600],
+        )
+        .unwrap();
+        let plan = plan.with_timings(&timings);
+        let timings = plan.timings().unwrap();
+        assert_eq!(timings[1].node(), "sort(a)");
+        assert_eq!(timings[1].duration(), Duration::from_micros(1500));
+    }
+}
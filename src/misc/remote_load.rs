@@ -14,8 +14,11 @@ use url::Url;
 
 use crate::{
     AppResult,
-    io::reader::{DataFrameReader, NamedFrames, ReaderSource},
-    misc::{download::download_size, http},
+    io::{
+        DataSource,
+        reader::{DataFrameReader, NamedFrames, ReaderSource},
+    },
+    misc::{download::download_size, http, sql::TableReader},
 };
 
 pub trait Reader: DataFrameReader + Debug + Send + Sync + 'static {}
@@ -26,6 +29,7 @@ impl<T> Reader for T where T: DataFrameReader + Debug + Send + Sync + 'static {}
 pub struct RemoteLoad {
     info: DownloadInfo,
     hndl: JoinHandle<AppResult<NamedFrames>>,
+    reloads: Option<String>,
 }
 
 impl RemoteLoad {
@@ -34,25 +38,32 @@ impl RemoteLoad {
         RemoteLoad {
             info: info.clone(),
             hndl: std::thread::spawn(move || {
-                if let Ok(size) = download_size(&url) {
-                    info.set_total(size);
-                }
-                let mut reader = http::get(&url).call()?.into_body().into_reader();
-                let mut temp = NamedTempFile::new()?;
-                let writer = temp.as_file_mut();
-                let mut buffer = [0_u8; 16_384];
-                loop {
-                    let n = reader.read(&mut buffer)?;
-                    if n == 0 {
-                        break;
-                    }
-                    info.add_progress(n as u64);
-                    writer.write_all(&buffer[..n])?;
-                }
+                let temp = download(&url, &info)?;
                 let nf =
                     df_reader.read_to_data_frames(ReaderSource::File(temp.path().to_owned()))?;
                 Ok(nf)
             }),
+            reloads: None,
+        }
+    }
+
+    /// Reads the table again from its file, or downloads it again from its URL, resulting in
+    /// its frame alone.
+    pub fn reload(name: impl Into<String>, source: DataSource, reader: TableReader) -> Self {
+        let info = DownloadInfo::default();
+        RemoteLoad {
+            info: info.clone(),
+            hndl: std::thread::spawn(move || {
+                let frame = match source {
+                    DataSource::File(path) => reader.read(path)?,
+                    DataSource::Url(url) => {
+                        reader.read(download(&url, &info)?.path().to_owned())?
+                    }
+                    DataSource::Stdin => bail!("Stdin cannot be read again"),
+                };
+                Ok(Box::new([frame]) as NamedFrames)
+            }),
+            reloads: Some(name.into()),
         }
     }
 
@@ -64,6 +75,11 @@ impl RemoteLoad {
         &self.info
     }
 
+    /// The name of the table read again, if the load is a reload.
+    pub fn reloads(&self) -> Option<&str> {
+        self.reloads.as_deref()
+    }
+
     pub fn join(self) -> AppResult<NamedFrames> {
         match self.hndl.join() {
             Ok(Ok(named_frame)) => Ok(named_frame),
@@ -73,6 +89,26 @@ impl RemoteLoad {
     }
 }
 
+/// Downloads the URL into a temporary file, reporting the progress to `info`.
+fn download(url: &Url, info: &DownloadInfo) -> AppResult<NamedTempFile> {
+    if let Ok(size) = download_size(url) {
+        info.set_total(size);
+    }
+    let mut reader = http::get(url).call()?.into_body().into_reader();
+    let mut temp = NamedTempFile::new()?;
+    let writer = temp.as_file_mut();
+    let mut buffer = [0_u8; 16_384];
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        info.add_progress(n as u64);
+        writer.write_all(&buffer[..n])?;
+    }
+    Ok(temp)
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct DownloadInfo {
     pg: Arc<AtomicU64>,
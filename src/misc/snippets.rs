@@ -0,0 +1,187 @@
+use std::{fmt::Display, fs};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AppResult, misc::paths::snippets_path};
+
+/// A named query saved in the snippets file, with optional `:param` placeholders.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snippet {
+    pub name: String,
+    pub sql: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SnippetsFile {
+    snippet: Vec<Snippet>,
+}
+
+impl Snippet {
+    /// Snippets from the snippets file, none if the file does not exist.
+    pub fn load_all() -> AppResult<Vec<Snippet>> {
+        let path = snippets_path()?;
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let contents = fs::read_to_string(path)?;
+        Ok(toml::from_str::<SnippetsFile>(&contents)?.snippet)
+    }
+
+    /// Placeholder names in order of first appearance, without duplicates.
+    pub fn params(&self) -> Vec<&str> {
+        let mut params = Vec::new();
+        for (_, name) in placeholders(&self.sql) {
+            if !params.contains(&name) {
+                params.push(name);
+            }
+        }
+        params
+    }
+
+    /// The query with each placeholder replaced by its value as a SQL literal.
+    pub fn render<'a>(&self, values: impl Fn(&str) -> Option<&'a str>) -> String {
+        let mut sql = String::with_capacity(self.sql.len());
+        let mut last = 0;
+        for (start, name) in placeholders(&self.sql) {
+            sql.push_str(&self.sql[last..start]);
+            match values(name) {
+                Some(value) => sql.push_str(&literal(value)),
+                None => sql.push_str(&self.sql[start..start + name.len() + 1]),
+            }
+            last = start + name.len() + 1;
+        }
+        sql.push_str(&self.sql[last..]);
+        sql
+    }
+}
+
+impl Display for Snippet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Byte offset and name of each `:name` outside of quotes, comments and `::` casts.
+fn placeholders(sql: &str) -> Vec<(usize, &str)> {
+    let bytes = sql.as_bytes();
+    let mut found = Vec::new();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            quote @ (b'\'' | b'"') => {
+                idx += 1;
+                while idx < bytes.len() && bytes[idx] != quote {
+                    idx += 1;
+                }
+            }
+            b'-' if bytes.get(idx + 1) == Some(&b'-') => {
+                while idx < bytes.len() && bytes[idx] != b'\n' {
+                    idx += 1;
+                }
+            }
+            b':' if bytes.get(idx + 1) == Some(&b':') => {
+                idx += 1;
+            }
+            b':' if idx == 0 || bytes[idx - 1] != b':' => {
+                let len = sql[idx + 1..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(sql.len() - idx - 1);
+                let name = &sql[idx + 1..idx + 1 + len];
+                if name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+                    found.push((idx, name));
+                }
+                idx += len;
+            }
+            _ => (),
+        }
+        idx += 1;
+    }
+    found
+}
+
+/// Numbers and booleans are kept as is, anything else becomes a quoted string. Signed numbers
+/// are parenthesized, so that `a -:x` with `-5` does not become the comment `a --5`.
+fn literal(value: &str) -> String {
+    let value = value.trim();
+    let is_number = value
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'))
+        && value.parse::<f64>().is_ok_and(f64::is_finite)
+        && !(value.len() > 1 && value.starts_with('0') && !value.starts_with("0."));
+    if is_number && value.starts_with(['-', '+']) {
+        format!("({value})")
+    } else if is_number || value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false")
+    {
+        value.to_owned()
+    } else {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn snippet(sql: &str) -> Snippet {
+        Snippet {
+            name: "test".to_owned(),
+            sql: sql.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_params_skip_quotes_comments_and_casts() {
+        let snippet = snippet(
+            "SELECT a::int, ':not' AS \":nor\" FROM t -- :comment\n\
+             WHERE b = :region AND c > :since AND d = :region",
+        );
+        assert_eq!(snippet.params(), ["region", "since"]);
+    }
+
+    #[test]
+    fn test_render_literals() {
+        let snippet =
+            snippet("SELECT * FROM t WHERE a = :name AND b > :min AND c = :flag AND d = :zip");
+        let values = HashMap::from([
+            ("name", "O'Brien"),
+            ("min", "-1.5e3"),
+            ("flag", "TRUE"),
+            ("zip", "007"),
+        ]);
+        assert_eq!(
+            snippet.render(|name| values.get(name).copied()),
+            "SELECT * FROM t WHERE a = 'O''Brien' AND b > (-1.5e3) AND c = TRUE AND d = '007'"
+        );
+    }
+
+    #[test]
+    fn test_render_keeps_unknown_placeholders() {
+        let snippet = snippet("SELECT :a, :b");
+        assert_eq!(
+            snippet.render(|name| (name == "a").then_some("1")),
+            "SELECT 1, :b"
+        );
+    }
+
+    #[test]
+    fn test_literal_rejects_injection() {
+        assert_eq!(literal("1; DROP TABLE t"), "'1; DROP TABLE t'");
+        assert_eq!(literal("x' OR '1'='1"), "'x'' OR ''1''=''1'");
+        assert_eq!(literal("inf"), "'inf'");
+    }
+
+    #[test]
+    fn test_literal_parenthesizes_signed_numbers() {
+        assert_eq!(literal("-5"), "(-5)");
+        assert_eq!(literal("+2.5"), "(+2.5)");
+        assert_eq!(literal("1e-3"), "1e-3");
+        assert_eq!(literal("--5"), "'--5'");
+        assert_eq!(
+            snippet("SELECT a -:x FROM t").render(|_| Some("-5")),
+            "SELECT a -(-5) FROM t"
+        );
+    }
+}
@@ -1,21 +1,42 @@
 use std::{
     borrow::Cow,
+    collections::HashMap,
     ops::DerefMut,
     path::PathBuf,
-    sync::{LazyLock, Mutex},
+    sync::{Arc, LazyLock, Mutex},
 };
 
+use anyhow::bail;
 use indexmap::IndexMap;
+use itertools::Itertools;
 use polars::{
-    error::PolarsResult,
+    df,
+    error::{PolarsResult, polars_bail},
     frame::DataFrame,
-    prelude::{AnyValue, DataType, IntoLazy, LazyFrame},
+    prelude::{AnyValue, DataType, IntoLazy, LazyFrame, Schema},
     series::Series,
 };
 use polars_sql::SQLContext;
+use sqlparser::{
+    ast::{AlterTableOperation, ObjectName, ObjectType, RenameTableNameKind, Statement},
+    dialect::GenericDialect,
+    parser::Parser,
+};
 use url::Url;
 
-use crate::{io::DataSource, misc::table_name_generator::TableNameGeneratorExt};
+use crate::{
+    AppResult,
+    io::{
+        DataSource,
+        reader::{NamedFrame, ReaderSource},
+    },
+    misc::{
+        metadata::{FieldMetadata, TableMetadata},
+        remote_load::Reader,
+        table_name_generator::TableNameGeneratorExt,
+        type_inferer::TypeInferer,
+    },
+};
 
 use super::polars_ext::AnyValueExt;
 use super::type_ext::UnwrapOrGracefulShutdown;
@@ -25,6 +46,48 @@ const DEFAULT_TABLE_NAME: &str = "_";
 pub struct SqlBackend {
     sql: SQLContext,
     schema: BackendSchema,
+    views: IndexMap<String, String>,
+    readers: HashMap<String, TableReader>,
+    tabs: IndexMap<String, DataFrame>,
+}
+
+/// The reader a table was loaded with, and the name of its frame among those read.
+#[derive(Debug, Clone)]
+pub struct TableReader {
+    reader: Arc<dyn Reader>,
+    frame: String,
+    type_inferer: TypeInferer,
+}
+
+impl TableReader {
+    pub fn new(reader: Arc<dyn Reader>, frame: impl Into<String>) -> Self {
+        Self {
+            reader,
+            frame: frame.into(),
+            type_inferer: Default::default(),
+        }
+    }
+
+    pub fn with_type_inferer(mut self, type_inferer: TypeInferer) -> Self {
+        self.type_inferer = type_inferer;
+        self
+    }
+
+    /// Reads the frame of the table from the file again, with the types inferred at load.
+    pub fn read(&self, path: PathBuf) -> AppResult<NamedFrame> {
+        let frames = self.reader.read_to_data_frames(ReaderSource::File(path))?;
+        let single = frames.len() == 1;
+        let Some((name, mut data_frame, metadata)) = frames
+            .into_vec()
+            .into_iter()
+            .find(|named| single || named.name() == self.frame)
+            .map(NamedFrame::into_parts)
+        else {
+            bail!("The source no longer holds a table named '{}'", self.frame)
+        };
+        self.type_inferer.update(&mut data_frame);
+        Ok(NamedFrame::new(name, data_frame).with_metadata(metadata))
+    }
 }
 
 impl SqlBackend {
@@ -32,6 +95,9 @@ impl SqlBackend {
         Self {
             sql: SQLContext::new(),
             schema: Default::default(),
+            views: Default::default(),
+            readers: Default::default(),
+            tabs: Default::default(),
         }
     }
 
@@ -45,16 +111,124 @@ impl SqlBackend {
         data_frame: DataFrame,
         input: impl Into<TableSource>,
     ) -> String {
+        let info = TableInfo::new(input.into(), &data_frame);
         let name = self.schema.available_name(name);
-        self.schema
-            .insert(name.clone(), TableInfo::new(input.into(), &data_frame));
+        self.schema.insert(name.clone(), info);
         self.sql.register(&name, data_frame.lazy());
+        self.refresh_views();
         name
     }
 
+    /// Remembers the reader of a registered table so that it can be reloaded from its source.
+    pub fn set_reader(&mut self, name: &str, reader: TableReader) {
+        self.readers.insert(name.to_owned(), reader);
+    }
+
+    pub fn set_metadata(&mut self, name: &str, metadata: TableMetadata) {
+        if let Some(info) = self.schema.get(name).cloned() {
+            self.schema
+                .insert(name.to_owned(), info.with_metadata(metadata));
+        }
+    }
+
     pub fn unregister(&mut self, name: &str) {
         self.schema.remove(name);
+        self.views.shift_remove(name);
+        self.readers.remove(name);
         self.sql.unregister(name);
+        self.refresh_views();
+    }
+
+    /// The file or URL the table was loaded from and the reader to read it again with.
+    pub fn reload_source(&self, name: &str) -> AppResult<(DataSource, TableReader)> {
+        match (
+            self.schema.get(name).map(TableInfo::source),
+            self.readers.get(name),
+        ) {
+            (Some(TableSource::File(path)), Some(reader)) => {
+                Ok((DataSource::File(path.clone()), reader.clone()))
+            }
+            (Some(TableSource::Url(url)), Some(reader)) => {
+                Ok((DataSource::Url(url.clone()), reader.clone()))
+            }
+            _ => bail!("Table '{name}' was not loaded from a file or a URL"),
+        }
+    }
+
+    /// Replaces the table with the frame read again from its source.
+    pub fn set_reloaded(&mut self, name: &str, frame: NamedFrame) -> AppResult<()> {
+        let Some(info) = self.schema.get(name) else {
+            bail!("Table '{name}' was removed or renamed while reloading")
+        };
+        let (_, data_frame, metadata) = frame.into_parts();
+        let info = TableInfo::new(info.source().clone(), &data_frame).with_metadata(metadata);
+        self.schema.insert(name.to_owned(), info);
+        self.sql.register(name, data_frame.lazy());
+        self.refresh_views();
+        Ok(())
+    }
+
+    /// Registers the collected result of a `CREATE TABLE ... AS` under its exact name,
+    /// replacing any table of that name, and returns a summary for the user.
+    pub fn define(&mut self, definition: Definition, data_frame: DataFrame) -> String {
+        let Definition { name, sql } = definition;
+        let info = TableInfo::new(TableSource::User, &data_frame).with_sql(sql);
+        self.unregister(&name);
+        self.schema.insert(name.clone(), info);
+        self.sql.register(&name, data_frame.lazy());
+        self.refresh_views();
+        format!("Table '{name}' created")
+    }
+
+    /// Registers a view under its exact name, replacing any table of that name. Only its
+    /// schema is resolved, its rows are collected whenever it is queried.
+    fn define_view(
+        &mut self,
+        name: String,
+        sql: String,
+        mut plan: LazyFrame,
+    ) -> PolarsResult<String> {
+        let info = TableInfo::view(sql.clone(), &*plan.collect_schema()?);
+        self.unregister(&name);
+        self.schema.insert(name.clone(), info);
+        self.views.insert(name.clone(), sql);
+        self.refresh_views();
+        Ok(format!("View '{name}' created"))
+    }
+
+    /// Exposes the top frame of every open tab as `tab1`, `tab2`, ... and under its title in
+    /// snake case, skipping the names taken by registered tables.
+    pub fn set_tabs<'a>(&mut self, tabs: impl IntoIterator<Item = (&'a str, DataFrame)>) {
+        for name in self.tabs.keys() {
+            if self.schema.get(name).is_none() {
+                self.sql.unregister(name);
+            }
+        }
+        let mut names = IndexMap::new();
+        for (idx, (title, data_frame)) in tabs.into_iter().enumerate() {
+            let free = |name: &str, names: &IndexMap<String, DataFrame>| {
+                name != DEFAULT_TABLE_NAME
+                    && self.schema.get(name).is_none()
+                    && !names.contains_key(name)
+            };
+            let index = format!("tab{}", idx + 1);
+            if free(&index, &names) {
+                names.insert(index, data_frame.clone());
+            }
+            let title = title.snake_case();
+            if let Some(name) = title.snake_case_names().find(|name| free(name, &names)) {
+                names.insert(name, data_frame);
+            }
+        }
+        for (name, data_frame) in names.iter() {
+            self.sql.register(name, data_frame.clone().lazy());
+        }
+        self.tabs = names;
+    }
+
+    /// Names the open tabs can be queried by, with their current frames.
+    pub fn tabs(&self) -> impl Iterator<Item = (&String, &DataFrame)> {
+        self.tabs.iter()
     }
 
     pub fn unset_default(&mut self) {
@@ -66,13 +240,211 @@ impl SqlBackend {
         query: &str,
         default_table: impl Into<Option<DataFrame>>,
     ) -> PolarsResult<DataFrame> {
-        if let Some(data_frame) = default_table.into() {
-            self.sql.register("_", data_frame.lazy());
-        }
-        let mut df = self.sql.execute(query).and_then(LazyFrame::collect)?;
+        let mut df = match self.prepare(query, default_table)? {
+            Prepared::Query(plan) => plan.collect()?,
+            Prepared::Define(definition, plan) => {
+                self.define(definition, plan.collect()?);
+                DataFrame::empty()
+            }
+            Prepared::Explain { plan, .. } => plan_lines(&plan)?,
+            Prepared::Applied(_) => DataFrame::empty(),
+        };
         df.rechunk_mut_par();
         Ok(df)
     }
+
+    /// Parses the statement without running any query, so it can be collected once the
+    /// backend is released. `DROP` and `ALTER TABLE ... RENAME` are applied right away.
+    pub fn prepare(
+        &mut self,
+        query: &str,
+        default_table: impl Into<Option<DataFrame>>,
+    ) -> PolarsResult<Prepared> {
+        if let Some(data_frame) = default_table.into() {
+            self.sql.register(DEFAULT_TABLE_NAME, data_frame.lazy());
+        }
+        self.refresh_views();
+
+        let Some(statement) = Parser::parse_sql(&GenericDialect {}, query)
+            .ok()
+            .and_then(|statements| statements.into_iter().exactly_one().ok())
+        else {
+            return Ok(Prepared::Query(self.sql.execute(query)?));
+        };
+        match statement {
+            Statement::Explain {
+                statement, analyze, ..
+            } => Ok(Prepared::Explain {
+                plan: self.sql.execute(&statement.to_string())?,
+                profile: analyze,
+            }),
+            Statement::CreateTable(create) if create.query.is_some() => {
+                let name = object_name(&create.name);
+                let query = create
+                    .query
+                    .map(|query| query.to_string())
+                    .unwrap_or_default();
+                if let Some(summary) =
+                    self.check_new_name(&name, create.or_replace, create.if_not_exists)?
+                {
+                    return Ok(Prepared::Applied(summary));
+                }
+                Ok(Prepared::Define(
+                    Definition {
+                        name,
+                        sql: query.clone(),
+                    },
+                    self.sql.execute(&query)?,
+                ))
+            }
+            Statement::CreateView(create) => {
+                let name = object_name(&create.name);
+                let query = create.query.to_string();
+                if let Some(summary) =
+                    self.check_new_name(&name, create.or_replace, create.if_not_exists)?
+                {
+                    return Ok(Prepared::Applied(summary));
+                }
+                let plan = self.sql.execute(&query)?;
+                Ok(Prepared::Applied(self.define_view(name, query, plan)?))
+            }
+            Statement::Drop {
+                object_type: ObjectType::Table | ObjectType::View,
+                if_exists,
+                names,
+                ..
+            } => {
+                let names = names.iter().map(object_name).collect_vec();
+                if let Some(missing) = names
+                    .iter()
+                    .find(|name| self.schema.get(name).is_none())
+                    .filter(|_| !if_exists)
+                {
+                    polars_bail!(SQLInterface: "relation '{}' was not found", missing);
+                }
+                let dropped = names
+                    .into_iter()
+                    .filter(|name| self.schema.get(name).is_some())
+                    .collect_vec();
+                for name in dropped.iter() {
+                    self.unregister(name);
+                }
+                let dropped = dropped.join(", ");
+                Ok(Prepared::Applied(if dropped.is_empty() {
+                    "Nothing to drop".to_owned()
+                } else {
+                    format!("Dropped {dropped}")
+                }))
+            }
+            Statement::AlterTable(alter) => match alter.operations.as_slice() {
+                [
+                    AlterTableOperation::RenameTable {
+                        table_name: RenameTableNameKind::To(new) | RenameTableNameKind::As(new),
+                    },
+                ] => {
+                    let (old, new) = (object_name(&alter.name), object_name(new));
+                    if self.schema.get(&old).is_none() {
+                        if alter.if_exists {
+                            return Ok(Prepared::Applied(format!("No table named '{old}'")));
+                        }
+                        polars_bail!(SQLInterface: "relation '{}' was not found", old);
+                    }
+                    self.rename(&old, &new)?;
+                    Ok(Prepared::Applied(format!("Renamed '{old}' to '{new}'")))
+                }
+                _ => Ok(Prepared::Query(self.sql.execute(query)?)),
+            },
+            _ => Ok(Prepared::Query(self.sql.execute(query)?)),
+        }
+    }
+
+    /// Fails on names already taken, unless the statement replaces them. Returns a summary
+    /// when `IF NOT EXISTS` turns the statement into a no-op.
+    fn check_new_name(
+        &self,
+        name: &str,
+        or_replace: bool,
+        if_not_exists: bool,
+    ) -> PolarsResult<Option<String>> {
+        if name == DEFAULT_TABLE_NAME {
+            polars_bail!(SQLInterface: "relation '{}' is reserved for the current table", name);
+        }
+        match self.schema.get(name) {
+            Some(_) if if_not_exists => Ok(Some(format!("'{name}' already exists"))),
+            Some(_) if !or_replace => {
+                polars_bail!(SQLInterface: "relation '{}' already exists", name)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Renames a table or view, moving it under the new name in the SQL context.
+    pub fn rename(&mut self, old: &str, new: &str) -> PolarsResult<()> {
+        if self.schema.get(old).is_none() {
+            polars_bail!(SQLInterface: "relation '{}' was not found", old);
+        }
+        if new.trim().is_empty() {
+            polars_bail!(SQLInterface: "'{}' is not a valid name", new);
+        }
+        self.check_new_name(new, false, false)?;
+        if let Some(reader) = self.readers.remove(old) {
+            self.readers.insert(new.to_owned(), reader);
+        }
+        if let Some(sql) = self.views.shift_remove(old) {
+            self.views.insert(new.to_owned(), sql);
+            self.refresh_views();
+        } else {
+            let plan = self
+                .sql
+                .execute(&format!("SELECT * FROM \"{}\"", old.replace('"', "\"\"")))?;
+            self.sql.register(new, plan);
+        }
+        self.sql.unregister(old);
+        self.schema.rename(old, new);
+        Ok(())
+    }
+
+    /// Plans the views again so that they follow the tables they are defined on, leaving
+    /// those whose tables are gone unavailable.
+    fn refresh_views(&mut self) {
+        for (name, query) in self.views.iter() {
+            let available = match self.sql.execute(query) {
+                Ok(plan) => {
+                    self.sql.register(name, plan);
+                    true
+                }
+                Err(_) => {
+                    self.sql.unregister(name);
+                    false
+                }
+            };
+            self.schema.set_available(name, available);
+        }
+    }
+}
+
+/// The optimized plan of the query, one line per row, as `EXPLAIN` results in outside the editor.
+pub fn plan_lines(plan: &LazyFrame) -> PolarsResult<DataFrame> {
+    df!("plan" => plan.describe_optimized_plan()?.lines().collect_vec())
+}
+
+/// A statement parsed against the backend.
+pub enum Prepared {
+    /// A query whose result is shown to the user.
+    Query(LazyFrame),
+    /// A `CREATE TABLE ... AS` to define once its query is collected.
+    Define(Definition, LazyFrame),
+    /// `EXPLAIN`, profiling the query when `ANALYZE` is given.
+    Explain { plan: LazyFrame, profile: bool },
+    /// A statement already applied, with a summary for the user.
+    Applied(String),
+}
+
+/// A table created by a SQL statement.
+#[derive(Debug, Clone)]
+pub struct Definition {
+    name: String,
+    sql: String,
 }
 
 impl Default for SqlBackend {
@@ -95,6 +467,19 @@ impl BackendSchema {
         self.schema.shift_remove(name);
     }
 
+    fn set_available(&mut self, name: &str, available: bool) {
+        if let Some(info) = self.schema.get_mut(name) {
+            info.available = available;
+        }
+    }
+
+    /// Renames the table in place, keeping its position.
+    pub fn rename(&mut self, old: &str, new: &str) {
+        if let Some((idx, _, info)) = self.schema.shift_remove_full(old) {
+            self.schema.shift_insert(idx, new.to_owned(), info);
+        }
+    }
+
     pub fn available_name(&self, preferred: &str) -> String {
         preferred
             .snake_case_names()
@@ -131,6 +516,10 @@ pub struct TableInfo {
     total_null: usize,
     total_est_size: usize,
     schema: TableSchema,
+    metadata: TableMetadata,
+    sql: Option<String>,
+    view: bool,
+    available: bool,
 }
 
 impl TableInfo {
@@ -143,9 +532,35 @@ impl TableInfo {
             total_null: schema.iter().map(|(_, info)| info.null_count()).sum(),
             total_est_size: schema.iter().map(|(_, info)| info.estimated_size()).sum(),
             schema,
+            metadata: Default::default(),
+            sql: None,
+            view: false,
+            available: true,
+        }
+    }
+
+    /// A view, described by the schema of its query alone as its rows are not collected.
+    pub fn view(sql: String, schema: &Schema) -> Self {
+        Self {
+            view: true,
+            ..Self::new(TableSource::User, &DataFrame::empty_with_schema(schema)).with_sql(sql)
         }
     }
 
+    pub fn with_metadata(mut self, metadata: TableMetadata) -> Self {
+        for (name, info) in self.schema.schema.iter_mut() {
+            info.metadata = metadata.field(name).cloned().unwrap_or_default();
+        }
+        self.metadata = metadata;
+        self
+    }
+
+    /// The query a table or view created from SQL is defined by.
+    pub fn with_sql(mut self, sql: String) -> Self {
+        self.sql = Some(sql);
+        self
+    }
+
     pub fn source(&self) -> &TableSource {
         &self.origin
     }
@@ -169,6 +584,23 @@ impl TableInfo {
     pub fn schema(&self) -> &TableSchema {
         &self.schema
     }
+
+    pub fn metadata(&self) -> &TableMetadata {
+        &self.metadata
+    }
+
+    pub fn sql(&self) -> Option<&str> {
+        self.sql.as_deref()
+    }
+
+    pub fn is_view(&self) -> bool {
+        self.view
+    }
+
+    /// False for a view whose tables are gone.
+    pub fn is_available(&self) -> bool {
+        self.available
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -242,6 +674,7 @@ pub struct FieldInfo {
     null_count: usize,
     min: String,
     max: String,
+    metadata: FieldMetadata,
 }
 
 impl FieldInfo {
@@ -253,8 +686,10 @@ impl FieldInfo {
             null_count: series.null_count(),
             min,
             max,
+            metadata: Default::default(),
         }
     }
+
     pub fn dtype(&self) -> &DataType {
         &self.dtype
     }
@@ -274,6 +709,10 @@ impl FieldInfo {
     pub fn max(&self) -> &str {
         &self.max
     }
+
+    pub fn metadata(&self) -> &FieldMetadata {
+        &self.metadata
+    }
 }
 
 pub fn sql() -> impl DerefMut<Target = SqlBackend> {
@@ -282,6 +721,14 @@ pub fn sql() -> impl DerefMut<Target = SqlBackend> {
     SQL_BACKEND.lock().unwrap_or_graceful_shutdown()
 }
 
+fn object_name(name: &ObjectName) -> String {
+    name.0
+        .last()
+        .and_then(|part| part.as_ident())
+        .map(|ident| ident.value.clone())
+        .unwrap_or_else(|| name.to_string())
+}
+
 fn min_max(series: &Series) -> (String, String) {
     let dtype = series.dtype();
     if dtype.is_primitive_numeric()
@@ -310,3 +757,170 @@ fn min_max(series: &Series) -> (String, String) {
         ("-".to_owned(), "-".to_owned())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use polars::df;
+
+    use super::*;
+
+    fn backend() -> SqlBackend {
+        let mut backend = SqlBackend::new();
+        backend.register(
+            "sales",
+            df!("region" => ["a", "b", "a"], "amount" => [1, 2, 3]).unwrap(),
+            TableSource::Stdin,
+        );
+        backend
+    }
+
+    #[test]
+    fn test_create_table_as() {
+        let mut backend = backend();
+        backend
+            .execute(
+                "CREATE TABLE totals AS SELECT region, sum(amount) AS total FROM sales GROUP BY region",
+                None,
+            )
+            .unwrap();
+        let info = backend.schema().get("totals").unwrap();
+        assert_eq!(info.source(), &TableSource::User);
+        assert_eq!(info.height(), 2);
+        assert!(info.sql().unwrap().starts_with("SELECT region"));
+        assert!(
+            backend
+                .execute("CREATE TABLE totals AS SELECT 1", None)
+                .is_err()
+        );
+        assert!(
+            backend
+                .execute("CREATE TABLE IF NOT EXISTS totals AS SELECT 1", None)
+                .is_ok()
+        );
+        assert_eq!(backend.schema().get("totals").unwrap().height(), 2);
+    }
+
+    #[test]
+    fn test_views_follow_their_tables() {
+        let mut backend = backend();
+        backend
+            .execute(
+                "CREATE VIEW big AS SELECT * FROM sales WHERE amount > 1",
+                None,
+            )
+            .unwrap();
+        let info = backend.schema().get("big").unwrap();
+        assert!(info.is_view() && info.is_available());
+        assert_eq!((info.height(), info.width()), (0, 2));
+        assert_eq!(
+            backend.execute("SELECT * FROM big", None).unwrap().height(),
+            2
+        );
+        backend.unregister("sales");
+        assert!(!backend.schema().get("big").unwrap().is_available());
+        backend.register(
+            "sales",
+            df!("region" => ["c"], "amount" => [5]).unwrap(),
+            TableSource::Stdin,
+        );
+        assert!(backend.schema().get("big").unwrap().is_available());
+        let df = backend.execute("SELECT region FROM big", None).unwrap();
+        assert_eq!(
+            df.column("region").unwrap().str().unwrap().get(0),
+            Some("c")
+        );
+    }
+
+    #[test]
+    fn test_drop_and_rename() {
+        let mut backend = backend();
+        backend
+            .execute("CREATE VIEW v AS SELECT * FROM sales", None)
+            .unwrap();
+        backend
+            .execute("ALTER TABLE sales RENAME TO s", None)
+            .unwrap();
+        assert_eq!(
+            backend
+                .schema()
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect_vec(),
+            ["s", "v"]
+        );
+        assert_eq!(
+            backend.execute("SELECT * FROM s", None).unwrap().height(),
+            3
+        );
+        backend.execute("DROP VIEW v", None).unwrap();
+        assert!(backend.schema().get("v").is_none());
+        assert!(backend.execute("SELECT * FROM v", None).is_err());
+        assert!(backend.execute("DROP TABLE v", None).is_err());
+        assert!(backend.execute("DROP TABLE IF EXISTS v", None).is_ok());
+    }
+
+    #[test]
+    fn test_tabs() {
+        let mut backend = backend();
+        let filtered = df!("region" => ["a"], "amount" => [1]).unwrap();
+        backend.set_tabs([
+            ("sales", filtered.clone()),
+            ("SELECT * FROM sales", filtered.clone()),
+        ]);
+        assert_eq!(
+            backend.tabs().map(|(name, _)| name.as_str()).collect_vec(),
+            ["tab1", "sales_2", "tab2", "select_from_sales"]
+        );
+        assert_eq!(
+            backend
+                .execute("SELECT * FROM sales_2", None)
+                .unwrap()
+                .height(),
+            1
+        );
+        assert_eq!(
+            backend
+                .execute("SELECT * FROM sales", None)
+                .unwrap()
+                .height(),
+            3
+        );
+
+        backend.set_tabs([("sales", filtered)]);
+        assert!(backend.execute("SELECT * FROM tab2", None).is_err());
+        assert!(backend.execute("SELECT * FROM tab1", None).is_ok());
+    }
+
+    #[test]
+    fn test_reload_renamed_table() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "a,b\n1,2\n").unwrap();
+        let reader: Arc<dyn Reader> = Arc::new(crate::io::reader::CsvToDataFrame::default());
+        let frames = reader
+            .read_to_data_frames(ReaderSource::File(file.path().to_owned()))
+            .unwrap();
+        let (frame, df, _) = frames.into_vec().remove(0).into_parts();
+
+        let mut backend = backend();
+        let name = backend.register(&frame, df, TableSource::File(file.path().to_owned()));
+        backend.set_reader(&name, TableReader::new(reader, frame));
+        backend.rename(&name, "numbers").unwrap();
+        assert!(backend.rename("numbers", "sales").is_err());
+        assert!(backend.rename("numbers", "_").is_err());
+
+        std::fs::write(file.path(), "a,b\n1,2\n3,4\n").unwrap();
+        let (source, reader) = backend.reload_source("numbers").unwrap();
+        assert!(matches!(&source, DataSource::File(path) if path == file.path()));
+        let frame = reader.read(file.path().to_owned()).unwrap();
+        backend.set_reloaded("numbers", frame).unwrap();
+        assert_eq!(backend.schema().get("numbers").unwrap().height(), 2);
+        assert_eq!(
+            backend
+                .execute("SELECT * FROM numbers", None)
+                .unwrap()
+                .height(),
+            2
+        );
+        assert!(backend.reload_source("sales").is_err());
+    }
+}
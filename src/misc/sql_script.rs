@@ -0,0 +1,376 @@
+use std::{
+    fmt::Display,
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+
+use polars::{error::PolarsResult, frame::DataFrame};
+use regex::Regex;
+use sqlparser::{
+    dialect::GenericDialect,
+    tokenizer::{Location, Token, Tokenizer, Whitespace},
+};
+
+use crate::misc::{
+    background::{BackgroundHandle, GetIsAlive, SetProgress, run_in_background},
+    query_job::collect,
+    sql::{Prepared, plan_lines, sql},
+};
+
+/// A statement of a script, with the one based line and column it starts at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptStatement {
+    sql: String,
+    line: usize,
+    column: usize,
+}
+
+/// A script read from a file, ready to run in the background.
+#[derive(Debug, Clone)]
+pub struct SqlScript {
+    name: String,
+    statements: Vec<ScriptStatement>,
+    default_table: Option<DataFrame>,
+}
+
+/// A script running on a worker thread, one statement after the other.
+#[derive(Debug)]
+pub struct ScriptJob {
+    name: String,
+    statements: usize,
+    started: Instant,
+    hndl: BackgroundHandle<usize, Option<Result<ScriptOutcome, ScriptError>>>,
+}
+
+/// What running a script left behind.
+#[derive(Debug)]
+pub struct ScriptOutcome {
+    pub statements: usize,
+    /// The final statement and its result, when it is a query.
+    pub result: Option<(String, DataFrame)>,
+}
+
+/// The statement a script stopped at, positioned within the script.
+#[derive(Debug)]
+pub struct ScriptError {
+    statement: usize,
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl ScriptStatement {
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Statement {} at line {}, column {}: {}",
+            self.statement, self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+impl SqlScript {
+    /// The script named after its file, with `default_table` as `_`.
+    pub fn new(name: impl Into<String>, script: &str, default_table: Option<DataFrame>) -> Self {
+        Self {
+            name: name.into(),
+            statements: statements(script),
+            default_table,
+        }
+    }
+
+    pub fn start(self) -> ScriptJob {
+        let Self {
+            name,
+            statements,
+            default_table,
+        } = self;
+        ScriptJob {
+            name,
+            statements: statements.len(),
+            started: Instant::now(),
+            hndl: run_in_background(move |alive, progress: SetProgress<usize>| {
+                run_statements(&statements, |idx, query| {
+                    progress.set(idx);
+                    execute(query, default_table.clone(), &alive)
+                })
+            }),
+        }
+    }
+}
+
+impl ScriptJob {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// The one based index of the running statement and the number of statements.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.hndl.progress() + 1, self.statements)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.hndl.is_running()
+    }
+
+    /// Stops the script at the next opportunity; the statements already run stay applied.
+    pub fn cancel(&self) {
+        self.hndl.cancel();
+    }
+
+    /// Waits for the worker, returning `None` if the script was cancelled.
+    pub fn finish(self) -> Option<Result<ScriptOutcome, ScriptError>> {
+        self.hndl.result().flatten()
+    }
+}
+
+/// Runs the statements of the script one after the other, with `default_table` as `_`.
+pub fn run_script(
+    script: &str,
+    default_table: Option<DataFrame>,
+) -> Result<ScriptOutcome, ScriptError> {
+    run_statements(&statements(script), |_, query| {
+        Some(sql().execute(query, default_table.clone()))
+    })
+    .expect("only scripts run in the background can be cancelled")
+}
+
+/// Runs each statement with `execute`, stopping at the first error; `None` once cancelled.
+fn run_statements(
+    statements: &[ScriptStatement],
+    mut execute: impl FnMut(usize, &str) -> Option<PolarsResult<DataFrame>>,
+) -> Option<Result<ScriptOutcome, ScriptError>> {
+    let mut result = None;
+    for (idx, statement) in statements.iter().enumerate() {
+        let df = match execute(idx, &statement.sql)? {
+            Ok(df) => df,
+            Err(err) => return Some(Err(statement.error(idx + 1, err.to_string()))),
+        };
+        result = (!df.columns().is_empty()).then(|| (statement.sql.clone(), df));
+    }
+    Some(Ok(ScriptOutcome {
+        statements: statements.len(),
+        result,
+    }))
+}
+
+/// Runs the statement as `SqlBackend::execute` does, holding the backend only to prepare it
+/// and to define its table, not while its query is collected.
+fn execute(
+    query: &str,
+    default_table: Option<DataFrame>,
+    alive: &GetIsAlive,
+) -> Option<PolarsResult<DataFrame>> {
+    let prepared = match sql().prepare(query, default_table) {
+        Ok(prepared) => prepared,
+        Err(err) => return Some(Err(err)),
+    };
+    match prepared {
+        Prepared::Query(plan) => collect(plan, alive),
+        Prepared::Define(definition, plan) => collect(plan, alive).map(|result| {
+            result.map(|df| {
+                sql().define(definition, df);
+                DataFrame::empty()
+            })
+        }),
+        Prepared::Explain { plan, .. } => Some(plan_lines(&plan)),
+        Prepared::Applied(_) => Some(Ok(DataFrame::empty())),
+    }
+}
+
+/// Splits the script on the semicolons outside strings, identifiers and comments, dropping
+/// the statements with nothing but comments.
+pub fn statements(script: &str) -> Vec<ScriptStatement> {
+    let mut tokens = Vec::new();
+    let _ = Tokenizer::new(&GenericDialect {}, script).tokenize_with_location_into_buf(&mut tokens);
+    let chars = script.chars().collect::<Vec<_>>();
+    let line_starts = std::iter::once(0)
+        .chain(
+            chars
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| **c == '\n')
+                .map(|(idx, _)| idx + 1),
+        )
+        .collect::<Vec<_>>();
+    let offset = |location: Location| {
+        line_starts
+            .get((location.line as usize).saturating_sub(1))
+            .map(|start| start + (location.column as usize).saturating_sub(1))
+            .unwrap_or(chars.len())
+            .min(chars.len())
+    };
+
+    let mut statements = Vec::new();
+    let mut start: Option<Location> = None;
+    let mut push = |start: Location, end: usize| {
+        let sql = chars[offset(start)..end]
+            .iter()
+            .collect::<String>()
+            .trim()
+            .to_owned();
+        statements.push(ScriptStatement {
+            sql,
+            line: start.line as usize,
+            column: start.column as usize,
+        });
+    };
+    for token in tokens.iter() {
+        match &token.token {
+            Token::SemiColon => {
+                if let Some(start) = start.take() {
+                    push(start, offset(token.span.start));
+                }
+            }
+            Token::Whitespace(
+                Whitespace::Space
+                | Whitespace::Newline
+                | Whitespace::Tab
+                | Whitespace::SingleLineComment { .. }
+                | Whitespace::MultiLineComment(_),
+            ) => (),
+            _ => {
+                start.get_or_insert(token.span.start);
+            }
+        }
+    }
+    // a statement without a semicolon, or cut short by a token the tokenizer rejected
+    if let Some(start) = start {
+        push(start, chars.len());
+    }
+    statements
+}
+
+impl ScriptStatement {
+    /// Positions the error within the script, from the location the parser reported within
+    /// the statement if any.
+    fn error(&self, statement: usize, message: String) -> ScriptError {
+        static LOCATION: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"Line: (\d+), Column: (\d+)").unwrap());
+
+        let (line, column) = LOCATION
+            .captures(&message)
+            .and_then(|captures| {
+                Some((
+                    captures[1].parse::<usize>().ok()?,
+                    captures[2].parse::<usize>().ok()?,
+                ))
+            })
+            .map(|(line, column)| match line {
+                0 | 1 => (self.line, self.column + column.saturating_sub(1)),
+                line => (self.line + line - 1, column),
+            })
+            .unwrap_or((self.line, self.column));
+        ScriptError {
+            statement,
+            line,
+            column,
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statements() {
+        let script =
+            "-- cleaning\nCREATE TABLE a AS SELECT 'x;y' AS v;\n\n  SELECT *\n  FROM a; -- done\n";
+        let statements = statements(script);
+        assert_eq!(
+            statements,
+            [
+                ScriptStatement {
+                    sql: "CREATE TABLE a AS SELECT 'x;y' AS v".to_owned(),
+                    line: 2,
+                    column: 1,
+                },
+                ScriptStatement {
+                    sql: "SELECT *\n  FROM a".to_owned(),
+                    line: 4,
+                    column: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_error_position() {
+        let statement = ScriptStatement {
+            sql: String::new(),
+            line: 4,
+            column: 3,
+        };
+        let error = statement.error(
+            2,
+            "sql parser error: Expected X at Line: 2, Column: 7".into(),
+        );
+        assert_eq!((error.line, error.column), (5, 7));
+        let error = statement.error(2, "Line: 1, Column: 8".into());
+        assert_eq!((error.line, error.column), (4, 10));
+        let error = statement.error(2, "relation 'b' was not found".into());
+        assert_eq!(
+            error.to_string(),
+            "Statement 2 at line 4, column 3: relation 'b' was not found"
+        );
+    }
+
+    #[test]
+    fn test_run_script() {
+        let outcome = run_script(
+            "CREATE TABLE script_totals AS SELECT 1 AS a UNION ALL SELECT 2 AS a;\nSELECT sum(a) AS total FROM script_totals;",
+            None,
+        )
+        .unwrap();
+        assert_eq!(outcome.statements, 2);
+        let (query, df) = outcome.result.unwrap();
+        assert_eq!(query, "SELECT sum(a) AS total FROM script_totals");
+        assert_eq!(df.height(), 1);
+        assert!(sql().schema().get("script_totals").is_some());
+
+        let error = run_script("SELECT 1;\nSELECT * FROM script_missing;", None).unwrap_err();
+        assert_eq!((error.statement, error.line, error.column), (2, 2, 1));
+    }
+
+    #[test]
+    fn test_script_job() {
+        let job = SqlScript::new(
+            "totals.sql",
+            "CREATE TABLE script_job_totals AS SELECT 1 AS a;\nSELECT a + 1 AS b FROM script_job_totals;\nEXPLAIN SELECT * FROM script_job_totals;",
+            None,
+        )
+        .start();
+        assert_eq!(job.name(), "totals.sql");
+        let outcome = job.finish().unwrap().unwrap();
+        assert_eq!(outcome.statements, 3);
+        let (query, df) = outcome.result.unwrap();
+        assert_eq!(query, "EXPLAIN SELECT * FROM script_job_totals");
+        assert_eq!(df.get_column_names(), ["plan"]);
+        assert!(sql().schema().get("script_job_totals").is_some());
+
+        let error = SqlScript::new(
+            "missing.sql",
+            "SELECT 1;\nSELECT * FROM script_job_missing;",
+            None,
+        )
+        .start()
+        .finish()
+        .unwrap()
+        .unwrap_err();
+        assert_eq!((error.statement, error.line, error.column), (2, 2, 1));
+    }
+}
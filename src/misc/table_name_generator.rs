@@ -22,12 +22,58 @@ impl Iterator for TableNameGenerator<'_> {
     }
 }
 
+/// Longest name [`TableNameGeneratorExt::snake_case`] makes out of a title.
+const MAX_SNAKE_CASE_LEN: usize = 32;
+
 pub trait TableNameGeneratorExt {
     fn snake_case_names(&self) -> TableNameGenerator<'_>;
+
+    /// Lowercase name of the text, with runs of other characters than letters and digits
+    /// turned into underscores, usable in SQL without quotes.
+    fn snake_case(&self) -> String;
 }
 
 impl TableNameGeneratorExt for str {
     fn snake_case_names(&self) -> TableNameGenerator<'_> {
         TableNameGenerator::with(self)
     }
+
+    fn snake_case(&self) -> String {
+        let mut name = String::new();
+        for word in self
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+        {
+            let word = word.to_lowercase();
+            if name.is_empty() {
+                name = word.chars().take(MAX_SNAKE_CASE_LEN).collect();
+            } else if name.chars().count() + word.chars().count() < MAX_SNAKE_CASE_LEN {
+                name.push('_');
+                name.push_str(&word);
+            } else {
+                break;
+            }
+        }
+        match name.chars().next() {
+            None => "tab".to_owned(),
+            Some(c) if c.is_numeric() => format!("tab_{name}"),
+            Some(_) => name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!("Sales 2024.csv".snake_case(), "sales_2024_csv");
+        assert_eq!(
+            "SELECT * FROM sales WHERE amount > 100".snake_case(),
+            "select_from_sales_where_amount"
+        );
+        assert_eq!("2024".snake_case(), "tab_2024");
+        assert_eq!("--".snake_case(), "tab");
+    }
 }
@@ -5,7 +5,7 @@ use polars::frame::DataFrame;
 use crate::misc::sql::sql;
 
 /// Gather all unique column names: first from the given DataFrame (the `_`
-/// table), then from every registered table in the SQL backend.
+/// table), then from every registered table and open tab in the SQL backend.
 pub fn collect_all_columns(dataframe: Option<&DataFrame>) -> Vec<String> {
     let mut columns: Vec<String> = Vec::new();
     let mut seen = HashSet::new();
@@ -27,6 +27,14 @@ pub fn collect_all_columns(dataframe: Option<&DataFrame>) -> Vec<String> {
             }
         }
     }
+    for (_, dataframe) in backend.tabs() {
+        for name in dataframe.get_column_names() {
+            let name = name.to_string();
+            if seen.insert(name.clone()) {
+                columns.push(name);
+            }
+        }
+    }
 
     columns
 }
@@ -46,13 +54,19 @@ pub fn get_table_columns(table: &str, default_dataframe: Option<&DataFrame>) ->
     let backend = sql();
     if let Some(info) = backend.schema().get(table) {
         info.schema().iter().map(|(name, _)| name.clone()).collect()
+    } else if let Some((_, dataframe)) = backend.tabs().find(|(name, _)| *name == table) {
+        dataframe
+            .get_column_names()
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect()
     } else {
         Vec::new()
     }
 }
 
 /// Return all known table names (including `_` when a default DataFrame is
-/// present).
+/// present), followed by the names of the open tabs.
 pub fn get_table_names(has_default: bool) -> Vec<String> {
     let backend = sql();
     let mut names: Vec<String> = Vec::new();
@@ -62,6 +76,9 @@ pub fn get_table_names(has_default: bool) -> Vec<String> {
     for (name, _) in backend.schema().iter() {
         names.push(name.clone());
     }
+    for (name, _) in backend.tabs() {
+        names.push(name.clone());
+    }
     names
 }
 
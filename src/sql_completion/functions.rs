@@ -0,0 +1,274 @@
+use ratatui::text::{Line, Span};
+use sqlparser::{
+    dialect::GenericDialect,
+    tokenizer::{Token, Tokenizer},
+};
+
+use crate::misc::config::theme;
+
+/// A function supported by the polars SQL engine.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SqlFunction {
+    pub name: &'static str,
+    /// Argument names; optional ones are bracketed and a trailing `...` repeats the last.
+    pub args: &'static [&'static str],
+    pub description: &'static str,
+}
+
+impl SqlFunction {
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, self.args.join(", "))
+    }
+}
+
+/// The function whose arguments are being typed, and the argument under the cursor.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SignatureHint {
+    pub function: &'static SqlFunction,
+    pub argument: usize,
+}
+
+impl SignatureHint {
+    /// The signature with the current argument emphasized, followed by the description.
+    pub fn line(&self) -> Line<'static> {
+        let args = self.function.args;
+        let current = match args.last() {
+            Some(&"...") if self.argument >= args.len() - 1 => args.len().saturating_sub(2),
+            _ => self.argument,
+        };
+        let mut spans = vec![Span::styled(
+            format!(" {}(", self.function.name),
+            theme().text(),
+        )];
+        for (idx, arg) in args.iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::styled(", ", theme().text()));
+            }
+            spans.push(if idx == current {
+                Span::styled(*arg, theme().header(0))
+            } else {
+                Span::styled(*arg, theme().text())
+            });
+        }
+        spans.push(Span::styled(") ", theme().text()));
+        spans.push(Span::styled(
+            format!("{} ", self.function.description),
+            theme().subtext(),
+        ));
+        Line::from(spans)
+    }
+}
+
+/// Case insensitive lookup in [`SQL_FUNCTIONS`].
+pub fn find_function(name: &str) -> Option<&'static SqlFunction> {
+    SQL_FUNCTIONS
+        .iter()
+        .find(|function| function.name.eq_ignore_ascii_case(name))
+}
+
+/// The innermost call left open before the cursor, when it is a known function.
+pub fn signature_hint(value: &str, cursor: usize, sql_prefix: &str) -> Option<SignatureHint> {
+    let before = format!(
+        "{sql_prefix}{}",
+        &value[..super::extraction::cursor_byte_offset(value, cursor)]
+    );
+    let mut tokens = Vec::new();
+    let _ =
+        Tokenizer::new(&GenericDialect {}, &before).tokenize_with_location_into_buf(&mut tokens);
+
+    // open brackets, with the function they call and the commas seen inside
+    let mut calls: Vec<(Option<&'static SqlFunction>, usize)> = Vec::new();
+    let mut previous: Option<&Token> = None;
+    for token in tokens
+        .iter()
+        .map(|token| &token.token)
+        .filter(|token| !matches!(token, Token::Whitespace(_)))
+    {
+        match token {
+            Token::LParen => calls.push((
+                match previous {
+                    Some(Token::Word(word)) if word.quote_style.is_none() => {
+                        find_function(&word.value)
+                    }
+                    _ => None,
+                },
+                0,
+            )),
+            Token::RParen => {
+                calls.pop();
+            }
+            Token::Comma => {
+                if let Some((_, commas)) = calls.last_mut() {
+                    *commas += 1;
+                }
+            }
+            _ => (),
+        }
+        previous = Some(token);
+    }
+    let (function, argument) = calls.pop()?;
+    Some(SignatureHint {
+        function: function?,
+        argument,
+    })
+}
+
+macro_rules! functions {
+    ($($name:literal ($($arg:literal),*) $description:literal;)*) => {
+        &[$(SqlFunction {
+            name: $name,
+            args: &[$($arg),*],
+            description: $description,
+        }),*]
+    };
+}
+
+pub const SQL_FUNCTIONS: &[SqlFunction] = functions! {
+    // math
+    "abs"("x") "Absolute value";
+    "cbrt"("x") "Cube root";
+    "ceil"("x") "Rounds up to the nearest integer";
+    "div"("x", "y") "Integer division";
+    "exp"("x") "Exponential";
+    "floor"("x") "Rounds down to the nearest integer";
+    "ln"("x") "Natural logarithm";
+    "log"("x", "[base]") "Logarithm, base 10 by default";
+    "log10"("x") "Base 10 logarithm";
+    "log1p"("x") "Natural logarithm of one plus x";
+    "log2"("x") "Base 2 logarithm";
+    "mod"("x", "y") "Remainder of the division";
+    "pi"() "The constant pi";
+    "pow"("x", "y") "x raised to the power of y";
+    "round"("x", "[decimals]") "Rounds to the given number of decimals";
+    "sign"("x") "Sign of the number as -1, 0 or 1";
+    "sqrt"("x") "Square root";
+    "trunc"("x", "[decimals]") "Truncates to the given number of decimals";
+    // trigonometry
+    "acos"("x") "Arc cosine in radians";
+    "acosd"("x") "Arc cosine in degrees";
+    "asin"("x") "Arc sine in radians";
+    "asind"("x") "Arc sine in degrees";
+    "atan"("x") "Arc tangent in radians";
+    "atan2"("y", "x") "Arc tangent of y / x in radians";
+    "atan2d"("y", "x") "Arc tangent of y / x in degrees";
+    "atand"("x") "Arc tangent in degrees";
+    "cos"("x") "Cosine of radians";
+    "cosd"("x") "Cosine of degrees";
+    "cot"("x") "Cotangent of radians";
+    "cotd"("x") "Cotangent of degrees";
+    "degrees"("x") "Converts radians to degrees";
+    "radians"("x") "Converts degrees to radians";
+    "sin"("x") "Sine of radians";
+    "sind"("x") "Sine of degrees";
+    "tan"("x") "Tangent of radians";
+    "tand"("x") "Tangent of degrees";
+    // bitwise
+    "bit_and"("x", "y") "Bitwise and";
+    "bit_count"("x") "Number of set bits";
+    "bit_not"("x") "Bitwise not";
+    "bit_or"("x", "y") "Bitwise or";
+    "bit_xor"("x", "y") "Bitwise exclusive or";
+    // conditional
+    "coalesce"("value", "...") "First value that is not null";
+    "greatest"("value", "...") "Largest of the values";
+    "if"("condition", "then", "else") "Picks a value by condition";
+    "ifnull"("value", "fallback") "Fallback when the value is null";
+    "least"("value", "...") "Smallest of the values";
+    "nullif"("value", "other") "Null when both values are equal";
+    // temporal
+    "date"("value", "[format]") "Converts to a date, parsing strings with the format";
+    "date_part"("part", "value") "Extracts a part such as 'year' or 'hour'";
+    "strftime"("value", "format") "Formats a date or time as a string";
+    "strptime"("value", "format") "Parses a string into a datetime";
+    "time"("value", "[format]") "Converts to a time, parsing strings with the format";
+    "timestamp"("value", "[format]") "Converts to a datetime, parsing strings with the format";
+    // strings
+    "bit_length"("string") "Length in bits";
+    "concat"("value", "...") "Concatenates values, skipping nulls";
+    "concat_ws"("separator", "value", "...") "Concatenates values with a separator";
+    "ends_with"("string", "suffix") "Whether the string ends with the suffix";
+    "left"("string", "n") "First n characters";
+    "length"("string") "Length in characters";
+    "lower"("string") "Lowercase";
+    "lpad"("string", "length", "[fill]") "Pads on the left to the length";
+    "ltrim"("string", "[characters]") "Removes leading characters, spaces by default";
+    "normalize"("string", "[form]") "Unicode normalization, NFC by default";
+    "octet_length"("string") "Length in bytes";
+    "regexp_like"("string", "pattern", "[flags]") "Whether the string matches the regex";
+    "replace"("string", "from", "to") "Replaces every occurrence of a substring";
+    "reverse"("string") "Reverses the characters";
+    "right"("string", "n") "Last n characters";
+    "rpad"("string", "length", "[fill]") "Pads on the right to the length";
+    "rtrim"("string", "[characters]") "Removes trailing characters, spaces by default";
+    "split_part"("string", "separator", "index") "The part at the one based index after splitting";
+    "starts_with"("string", "prefix") "Whether the string starts with the prefix";
+    "string_to_array"("string", "separator") "Splits the string into an array";
+    "strpos"("string", "substring") "One based position of the substring, 0 if absent";
+    "substr"("string", "start", "[length]") "Substring from the one based start";
+    "upper"("string") "Uppercase";
+    // aggregates
+    "avg"("x") "Mean of the values";
+    "corr"("x", "y") "Pearson correlation";
+    "count"("x") "Number of values that are not null, or rows with *";
+    "covar_pop"("x", "y") "Population covariance";
+    "covar_samp"("x", "y") "Sample covariance";
+    "first"("x") "First value";
+    "last"("x") "Last value";
+    "max"("x") "Largest value";
+    "median"("x") "Median of the values";
+    "min"("x") "Smallest value";
+    "quantile_cont"("x", "quantile") "Interpolated quantile";
+    "quantile_disc"("x", "quantile") "Quantile picked from the values";
+    "stddev"("x") "Sample standard deviation";
+    "string_agg"("string", "[separator]") "Concatenates the strings of the group";
+    "sum"("x") "Sum of the values";
+    "total"("x") "Sum of the values, 0 when there are none";
+    "variance"("x") "Sample variance";
+    // arrays
+    "array_agg"("x") "Collects the values into an array";
+    "array_contains"("array", "value") "Whether the array contains the value";
+    "array_get"("array", "index") "Element at the one based index";
+    "array_length"("array") "Number of elements";
+    "array_lower"("array") "Smallest element";
+    "array_mean"("array") "Mean of the elements";
+    "array_reverse"("array") "Reverses the elements";
+    "array_sum"("array") "Sum of the elements";
+    "array_to_string"("array", "separator") "Joins the elements into a string";
+    "array_unique"("array") "Distinct elements";
+    "array_upper"("array") "Largest element";
+    "unnest"("array") "One row per element";
+    // windows
+    "dense_rank"() "Rank within the window, without gaps";
+    "first_value"("x") "First value of the window";
+    "lag"("x", "[offset]") "Value offset rows before in the window";
+    "last_value"("x") "Last value of the window";
+    "lead"("x", "[offset]") "Value offset rows after in the window";
+    "rank"() "Rank within the window, with gaps after ties";
+    "row_number"() "Row number within the window";
+    // selection
+    "columns"("pattern") "Every column whose name matches the regex";
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hint(value: &str) -> Option<(&'static str, usize)> {
+        signature_hint(value, value.chars().count(), "")
+            .map(|hint| (hint.function.name, hint.argument))
+    }
+
+    #[test]
+    fn test_signature_hint() {
+        assert_eq!(hint("SELECT round("), Some(("round", 0)));
+        assert_eq!(hint("SELECT ROUND(price, "), Some(("round", 1)));
+        assert_eq!(hint("SELECT round(abs(x), 2"), Some(("round", 1)));
+        assert_eq!(
+            hint("SELECT coalesce((a + b), 'x, y', "),
+            Some(("coalesce", 2))
+        );
+        assert_eq!(hint("SELECT round(x)"), None);
+        assert_eq!(hint("SELECT (a"), None);
+        assert_eq!(hint("SELECT nope("), None);
+    }
+}
@@ -1,6 +1,7 @@
 mod context;
 mod data;
 mod extraction;
+mod functions;
 mod suggestion;
 
 use polars::frame::DataFrame;
@@ -8,7 +9,8 @@ use polars::frame::DataFrame;
 pub use context::CompletionContext;
 pub use data::{collect_all_columns, filter_by_prefix, get_table_columns, get_table_names};
 pub use extraction::extract_token_and_context;
-pub use suggestion::SqlSuggestion;
+pub use functions::{SQL_FUNCTIONS, SignatureHint, SqlFunction, find_function, signature_hint};
+pub use suggestion::{CompletionTarget, SqlSuggestion};
 
 const SQL_KEYWORDS: &[&str] = &[
     "AS",
@@ -71,8 +73,16 @@ pub fn suggestions(
 
     let token_lower = token.to_lowercase();
 
+    if context == CompletionContext::Column {
+        return filter_by_prefix(all_columns.iter(), &token_lower)
+            .into_iter()
+            .map(SqlSuggestion::new)
+            .chain(function_suggestions(&token_lower))
+            .collect();
+    }
+
     let strings = match &context {
-        CompletionContext::Column => filter_by_prefix(all_columns.iter(), &token_lower),
+        CompletionContext::Column => Vec::new(),
         CompletionContext::Keyword => keyword_suggestions(&token_lower),
         CompletionContext::QualifiedColumn(table) => {
             let columns = get_table_columns(table, dataframe);
@@ -99,10 +109,22 @@ fn keyword_suggestions(token_lower: &str) -> Vec<String> {
         .collect()
 }
 
+fn function_suggestions(token_lower: &str) -> impl Iterator<Item = SqlSuggestion> {
+    SQL_FUNCTIONS
+        .iter()
+        .filter(move |function| {
+            function.name.starts_with(token_lower) && function.name != token_lower
+        })
+        .map(SqlSuggestion::function)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tui::pickers::text_picker_with_suggestion::Suggestion;
+    use crate::tui::{
+        component::Component, pickers::text_picker_with_suggestion::Suggestion,
+        widgets::input::Input,
+    };
 
     fn suggestion_titles(suggestions: Vec<SqlSuggestion>) -> Vec<String> {
         suggestions
@@ -132,6 +154,29 @@ mod tests {
         assert!(suggestions.is_empty());
     }
 
+    #[test]
+    fn suggests_functions_after_columns_in_expressions() {
+        let columns = ["store".to_string()];
+        let titles = suggestion_titles(suggestions("st", 2, "SELECT ", &columns, None));
+
+        assert_eq!(titles[0], "store");
+        assert!(titles.contains(&"stddev(x)  Sample standard deviation".to_string()));
+        assert!(suggestions("sel", 3, "", &columns, None).len() == 1);
+    }
+
+    #[test]
+    fn completes_functions_with_an_opening_bracket() {
+        let mut input = Input::default().with_value("SELECT rou x".to_owned());
+        for _ in 0..2 {
+            input.handle(crossterm::event::KeyEvent::from(
+                crossterm::event::KeyCode::Left,
+            ));
+        }
+        suggestions(input.value(), input.cursor(), "", &[], None)[0].complete(&mut input);
+
+        assert_eq!(input.value(), "SELECT round( x");
+    }
+
     #[test]
     fn does_not_suggest_unsupported_keywords() {
         let suggestions = suggestions("al", 2, "", &[], None);
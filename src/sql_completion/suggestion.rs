@@ -1,27 +1,88 @@
-use crate::tui::{pickers::text_picker_with_suggestion::Suggestion, widgets::input::Input};
+use crate::tui::{
+    pickers::text_picker_with_suggestion::Suggestion,
+    widgets::{input::Input, text_area::TextArea},
+};
 
-use super::extraction::{cursor_byte_offset, is_separator};
+use super::{
+    extraction::{cursor_byte_offset, is_separator},
+    functions::SqlFunction,
+};
 
 /// A SQL completion suggestion that replaces the partial token before the cursor
 /// with the completed text.
 #[derive(Debug, Clone)]
 pub struct SqlSuggestion {
     text: String,
+    title: String,
+    call: bool,
+}
+
+/// Text edited with a cursor counted in characters, which suggestions complete.
+pub trait CompletionTarget {
+    fn value(&self) -> String;
+    fn cursor(&self) -> usize;
+    fn insert(&mut self, c: char);
+    fn delete_prev(&mut self);
+}
+
+impl CompletionTarget for Input {
+    fn value(&self) -> String {
+        Input::value(self).to_owned()
+    }
+
+    fn cursor(&self) -> usize {
+        Input::cursor(self)
+    }
+
+    fn insert(&mut self, c: char) {
+        Input::insert(self, c);
+    }
+
+    fn delete_prev(&mut self) {
+        Input::delete_prev(self);
+    }
+}
+
+impl CompletionTarget for TextArea {
+    fn value(&self) -> String {
+        TextArea::value(self)
+    }
+
+    fn cursor(&self) -> usize {
+        TextArea::cursor(self)
+    }
+
+    fn insert(&mut self, c: char) {
+        TextArea::insert(self, c);
+    }
+
+    fn delete_prev(&mut self) {
+        TextArea::delete_prev(self);
+    }
 }
 
 impl SqlSuggestion {
     pub fn new(text: String) -> Self {
-        Self { text }
+        Self {
+            title: text.clone(),
+            text,
+            call: false,
+        }
     }
-}
 
-impl Suggestion for SqlSuggestion {
-    fn title(&self) -> &str {
-        &self.text
+    /// Completes the function name followed by an opening bracket, listed with its signature
+    /// and description.
+    pub fn function(function: &SqlFunction) -> Self {
+        Self {
+            text: function.name.to_owned(),
+            title: format!("{}  {}", function.signature(), function.description),
+            call: true,
+        }
     }
 
-    fn apply_to(&self, input: &mut Input) {
-        let value = input.value().to_owned();
+    /// Replaces the partial token before the cursor with the suggestion.
+    pub fn complete(&self, input: &mut impl CompletionTarget) {
+        let value = input.value();
         let cursor = cursor_byte_offset(&value, input.cursor());
         let before_cursor = &value[..cursor];
         let at_cursor = value[cursor..].chars().next();
@@ -42,7 +103,12 @@ impl Suggestion for SqlSuggestion {
         }
 
         // Insert the completed text.
-        if self.text.contains(' ') {
+        if self.call {
+            for character in self.text.chars().chain(['(']) {
+                input.insert(character);
+            }
+            return;
+        } else if self.text.contains(' ') {
             for character in format!("\"{}\"", self.text).chars() {
                 input.insert(character);
             }
@@ -58,3 +124,13 @@ impl Suggestion for SqlSuggestion {
         }
     }
 }
+
+impl Suggestion for SqlSuggestion {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn apply_to(&self, input: &mut Input) {
+        self.complete(input);
+    }
+}
@@ -22,8 +22,9 @@ use crate::{
         plots::{histogram_plot::HistogramPlot, scatter_plot::ScatterPlot},
         popups::{
             column_caster::ColumnCaster,
+            column_hider::ColumnHider,
             data_frame_info::DataFrameInfo,
-            exporter::Exporter,
+            exporter::{self, Exporter},
             go_to_line::GoToLine,
             histogram_builder::{self, HistogramBuilder},
             inline_query_picker::{InlineQueryPicker, QueryType},
@@ -106,10 +107,15 @@ impl Pane {
     fn show_data_frame_info(&mut self) {
         match &self.dstack.last() {
             TableDescription::Table(desc) => {
-                if let Some(input) = sql().schema().get(desc).map(|info| info.source()).cloned() {
+                if let Some((input, metadata)) = sql()
+                    .schema()
+                    .get(desc)
+                    .map(|info| (info.source().clone(), info.metadata().clone()))
+                {
                     self.modal = Some(Modal::DataFrameInfo(DataFrameInfo::new(
                         self.tstack.last().data_frame(),
                         input,
+                        metadata,
                     )))
                 }
             }
@@ -123,6 +129,7 @@ impl Pane {
                 self.modal = Some(Modal::DataFrameInfo(DataFrameInfo::new(
                     self.tstack.last().data_frame(),
                     TableSource::User,
+                    Default::default(),
                 )))
             }
         }
@@ -160,9 +167,9 @@ impl Pane {
     }
 
     fn show_exporter(&mut self) {
-        self.modal = Some(Modal::Exporter(Exporter::new(
-            self.tstack.last().data_frame().clone().into(),
-        )))
+        self.modal = Some(Modal::Exporter(Exporter::new(exporter::State::new(
+            self.tstack.last(),
+        ))))
     }
 
     fn show_histogram(&mut self, col: &str, buckets: usize) -> AppResult<()> {
@@ -199,7 +206,13 @@ impl Pane {
         )))
     }
 
-    fn push_data_frame(&mut self, df: DataFrame, description: TableDescription) {
+    fn show_column_hider(&mut self) {
+        self.modal = Some(Modal::ColumnHider(ColumnHider::new(
+            self.tstack.last().shown_column_names(),
+        )))
+    }
+
+    pub fn push_data_frame(&mut self, df: DataFrame, description: TableDescription) {
         self.tstack
             .push(self.tstack.last().clone_with_data_frame(df));
         self.dstack.push(description);
@@ -345,6 +358,12 @@ impl Component for Pane {
                     .render(area, buf, FocusState::NotFocused);
                 state.render(area, buf, focus_state);
             }
+            Some(Modal::ColumnHider(state)) => {
+                self.tstack
+                    .last_mut()
+                    .render(area, buf, FocusState::NotFocused);
+                state.render(area, buf, focus_state);
+            }
             None => self.tstack.last_mut().render(area, buf, focus_state),
         }
     }
@@ -368,6 +387,7 @@ impl Component for Pane {
                     scatter_plot_builder.handle(event)
                 }
                 Modal::ColumnCaster(column_caster) => column_caster.handle(event),
+                Modal::ColumnHider(column_hider) => column_hider.handle(event),
             };
             true
         } else {
@@ -437,7 +457,7 @@ impl Component for Pane {
                     (KeyCode::Char('q'), KeyModifiers::NONE)
                         if self.tstack.len_without_base() > 0 =>
                     {
-                        self.pop_data_frame();
+                        Message::PanePopDataFrame.enqueue();
                         true
                     }
                     _ => false,
@@ -485,6 +505,19 @@ impl Component for Pane {
             Message::PaneTableSelect(idx) if focus_state.is_focused() => self.select(*idx),
             Message::PaneShowTableInfo if focus_state.is_focused() => self.show_data_frame_info(),
             Message::PaneShowColumnCaster if focus_state.is_focused() => self.show_column_caster(),
+            Message::PaneShowColumnHider if focus_state.is_focused() => self.show_column_hider(),
+            Message::PaneHideColumn(name) if focus_state.is_focused() => {
+                self.tstack
+                    .last_mut()
+                    .hide_column(name)
+                    .unwrap_or_enqueue_error();
+            }
+            Message::PaneUnhideColumns if focus_state.is_focused() => {
+                self.tstack.last_mut().unhide_columns()
+            }
+            Message::PaneUnmarkRows if focus_state.is_focused() => {
+                self.tstack.last_mut().unmark_rows()
+            }
             Message::PaneShowSearch if focus_state.is_focused() => {
                 self.show_exact_search();
             }
@@ -530,6 +563,7 @@ impl Component for Pane {
             Some(Modal::ScatterPlotBuilder(_)) => (),
             Some(Modal::TableRegisterer(_)) => (),
             Some(Modal::ColumnCaster(_)) => (),
+            Some(Modal::ColumnHider(_)) => (),
             None => (),
         }
     }
@@ -549,6 +583,7 @@ pub enum Modal {
     ScatterPlotBuilder(ScatterPlotBuilder),
     TableRegisterer(TableRegisterer),
     ColumnCaster(ColumnCaster),
+    ColumnHider(ColumnHider),
 }
 
 impl Modal {
@@ -566,6 +601,7 @@ impl Modal {
             Modal::ScatterPlotBuilder(scatter_plot_builder) => scatter_plot_builder,
             Modal::TableRegisterer(table_registerer) => table_registerer,
             Modal::ColumnCaster(column_caster) => column_caster,
+            Modal::ColumnHider(column_hider) => column_hider,
         }
     }
 }
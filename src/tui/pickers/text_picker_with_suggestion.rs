@@ -5,11 +5,12 @@ use ratatui::{
         border::{ROUNDED, Set},
         line::{VERTICAL_LEFT, VERTICAL_RIGHT},
     },
+    text::Line,
     widgets::{Borders, Clear, List, ListItem, ListState, StatefulWidget, Widget},
 };
 
 use crate::{
-    misc::{color_ext::ColorExt, config::theme},
+    misc::{color_ext::ColorExt, config::theme, history},
     tui::{
         component::Component,
         widgets::{block::Block, input::Input},
@@ -25,6 +26,16 @@ pub struct TextPickerWithSuggestion<P: Provider> {
     items: Vec<P::Suggestion>,
     provider: P,
     darken_bg: bool,
+    history: Vec<String>,
+    recalled: Option<usize>,
+    search: Option<Box<HistorySearch>>,
+}
+
+/// Fuzzy search through the history, replacing the suggestions while active.
+#[derive(Debug, Default)]
+struct HistorySearch {
+    pattern: Option<String>,
+    matches: Vec<String>,
 }
 
 impl<P> TextPickerWithSuggestion<P>
@@ -40,9 +51,17 @@ where
             items: provider.suggestions("", 0),
             provider,
             darken_bg: true,
+            history: Vec::new(),
+            recalled: None,
+            search: None,
         }
     }
 
+    /// Previous values, most recent first, recalled with Up and Down and searched with Ctrl+R.
+    pub fn with_history(self, history: Vec<String>) -> Self {
+        Self { history, ..self }
+    }
+
     pub fn with_title(self, title: impl Into<String>) -> Self {
         Self {
             title: title.into(),
@@ -69,7 +88,10 @@ where
     }
 
     pub fn has_suggestions(&self) -> bool {
-        !self.items.is_empty()
+        match &self.search {
+            Some(search) => !search.matches.is_empty(),
+            None => !self.items.is_empty(),
+        }
     }
 
     pub fn selected_suggestion(&self) -> Option<&<P as Provider>::Suggestion> {
@@ -81,10 +103,46 @@ where
     }
 
     pub fn apply_selected_suggestion(&mut self) {
-        if let Some(suggestion) = self.list.selected().and_then(|idx| self.items.get(idx)) {
+        if let Some(search) = self.search.take() {
+            if let Some(entry) = self.list.selected().and_then(|idx| search.matches.get(idx)) {
+                self.input.set_value(entry.to_owned());
+            }
+            self.clear_suggestion_selection();
+        } else if let Some(suggestion) = self.list.selected().and_then(|idx| self.items.get(idx)) {
             suggestion.apply_to(&mut self.input);
         }
     }
+
+    /// Whether the input is empty or still holds the recalled entry, so Up and Down walk
+    /// through the history rather than the suggestions.
+    fn is_recalling(&self) -> bool {
+        self.search.is_none()
+            && match self.recalled {
+                Some(idx) => self
+                    .history
+                    .get(idx)
+                    .is_some_and(|entry| entry == self.input.value()),
+                None => self.input.value().is_empty(),
+            }
+    }
+
+    fn recall(&mut self, idx: Option<usize>) {
+        self.recalled = idx;
+        self.input.set_value(
+            idx.and_then(|idx| self.history.get(idx))
+                .cloned()
+                .unwrap_or_default(),
+        );
+        self.clear_suggestion_selection();
+    }
+
+    fn toggle_search(&mut self) {
+        self.search = match self.search {
+            Some(_) => None,
+            None => Some(Default::default()),
+        };
+        self.clear_suggestion_selection();
+    }
 }
 
 impl<P> Component for TextPickerWithSuggestion<P>
@@ -104,13 +162,33 @@ where
             }
         }
 
-        if self.args.0 != self.input.value() || self.args.1 != self.input.cursor() {
+        if let Some(search) = self.search.as_mut() {
+            if search.pattern.as_deref() != Some(self.input.value()) {
+                search.pattern = Some(self.input.value().to_owned());
+                search.matches = history::search(&self.history, self.input.value())
+                    .into_iter()
+                    .map(str::to_owned)
+                    .collect();
+            }
+        } else if self.args.0 != self.input.value() || self.args.1 != self.input.cursor() {
             self.args = (self.input.value().to_owned(), self.input.cursor());
             self.items = self
                 .provider
                 .suggestions(self.input.value(), self.input.cursor());
         }
 
+        let titles = match &self.search {
+            Some(search) => search
+                .matches
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+            None => self.items.iter().map(Suggestion::title).collect(),
+        };
+        let hint = match self.search {
+            Some(_) => None,
+            None => self.provider.hint(self.input.value(), self.input.cursor()),
+        };
         let list = List::default()
             .style(theme().text())
             .highlight_style(theme().row_highlighted())
@@ -121,13 +199,10 @@ where
                         top_right: VERTICAL_LEFT,
                         ..ROUNDED
                     })
+                    .title(hint.unwrap_or_default())
                     .into_widget(),
             )
-            .items(
-                self.items
-                    .iter()
-                    .map(|suggestion| ListItem::new(suggestion.title())),
-            );
+            .items(titles.into_iter().map(ListItem::new));
 
         let width = 80;
         let height = list.len().saturating_add(4).min(25) as u16;
@@ -143,9 +218,13 @@ where
             Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(area);
 
         let input_area = {
+            let title = match self.search {
+                Some(_) => format!("{} (History Search)", self.title),
+                None => self.title.clone(),
+            };
             let block = Block::default()
                 .borders(Borders::LEFT | Borders::RIGHT | Borders::TOP)
-                .title(self.title.as_str());
+                .title(title);
             let input_inner = block.inner(input_area);
             Widget::render(block, area, buf);
             input_inner
@@ -155,19 +234,44 @@ where
     }
 
     fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        let len = match &self.search {
+            Some(search) => search.matches.len(),
+            None => self.items.len(),
+        };
         self.input.handle(event)
             || match (event.code, event.modifiers) {
+                (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
+                    self.toggle_search();
+                    true
+                }
+                (KeyCode::Esc, KeyModifiers::NONE) if self.search.is_some() => {
+                    self.toggle_search();
+                    true
+                }
+                (KeyCode::Up, KeyModifiers::NONE)
+                    if self.is_recalling()
+                        && self.recalled.map_or(0, |idx| idx + 1) < self.history.len() =>
+                {
+                    self.recall(Some(self.recalled.map_or(0, |idx| idx + 1)));
+                    true
+                }
+                (KeyCode::Down, KeyModifiers::NONE)
+                    if self.is_recalling() && self.recalled.is_some() =>
+                {
+                    self.recall(self.recalled.and_then(|idx| idx.checked_sub(1)));
+                    true
+                }
                 (KeyCode::Up, KeyModifiers::NONE) | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
                     if self.list.selected() != Some(0) {
                         self.list.select_previous();
                     } else {
-                        self.list.select(Some(self.items.len().saturating_sub(1)));
+                        self.list.select(Some(len.saturating_sub(1)));
                     }
                     true
                 }
                 (KeyCode::Down, KeyModifiers::NONE)
                 | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
-                    if self.list.selected() != Some(self.items.len().saturating_sub(1)) {
+                    if self.list.selected() != Some(len.saturating_sub(1)) {
                         self.list.select_next();
                     } else {
                         self.list.select_first();
@@ -187,6 +291,11 @@ pub trait Suggestion {
 pub trait Provider {
     type Suggestion: Suggestion;
     fn suggestions(&self, query: &str, cursor: usize) -> Vec<Self::Suggestion>;
+
+    /// A line shown above the suggestions, such as the signature of the function being called.
+    fn hint(&self, _query: &str, _cursor: usize) -> Option<Line<'static>> {
+        None
+    }
 }
 
 impl<S> Provider for fn(&str, usize) -> Vec<S>
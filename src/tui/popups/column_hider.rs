@@ -0,0 +1,48 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::{
+    handler::message::Message,
+    tui::{component::Component, pickers::search_picker::SearchPicker},
+};
+
+#[derive(Debug)]
+pub struct ColumnHider {
+    picker: SearchPicker<String>,
+}
+
+impl ColumnHider {
+    pub fn new(columns: Vec<String>) -> Self {
+        Self {
+            picker: SearchPicker::new(columns).with_title("Hide Column"),
+        }
+    }
+}
+
+impl Component for ColumnHider {
+    fn render(
+        &mut self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        focus_state: crate::tui::component::FocusState,
+    ) {
+        self.picker.render(area, buf, focus_state);
+    }
+
+    fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        self.picker.handle(event)
+            || match (event.code, event.modifiers) {
+                (KeyCode::Enter, KeyModifiers::NONE) => {
+                    if let Some(name) = self.picker.selected_str() {
+                        Message::PaneHideColumn(name.to_owned()).enqueue();
+                    }
+                    Message::PaneDismissModal.enqueue();
+                    true
+                }
+                (KeyCode::Esc, KeyModifiers::NONE) => {
+                    Message::PaneDismissModal.enqueue();
+                    true
+                }
+                _ => false,
+            }
+    }
+}
@@ -62,6 +62,7 @@ impl Component for CommandPalette {
                     if let Some(item) = self.picker.selected_item() {
                         match item {
                             Command::Cast => Message::PaneShowColumnCaster.enqueue(),
+                            Command::Hide => Message::PaneShowColumnHider.enqueue(),
                             Command::Info => Message::PaneShowTableInfo.enqueue(),
                             Command::Export => Message::PaneShowExporter.enqueue(),
                             Command::Filter => Message::PaneShowInlineFilter.enqueue(),
@@ -73,8 +74,16 @@ impl Component for CommandPalette {
                             Command::Register => Message::PaneShowTableRegisterer.enqueue(),
                             Command::ScatterPlot => Message::PaneShowScatterPlotBuilder.enqueue(),
                             Command::Schema => Message::AppShowSchema.enqueue(),
+                            Command::Script => Message::AppShowScriptRunner.enqueue(),
                             Command::Select => Message::PaneShowInlineSelect.enqueue(),
+                            Command::Snippets => Message::AppShowSnippetRunner.enqueue(),
+                            Command::Explain => Message::AppShowExplain.enqueue(),
+                            Command::SqlEditor => {
+                                Message::AppShowSqlEditor(String::new()).enqueue()
+                            }
                             Command::Sort => Message::PaneShowInlineOrder.enqueue(),
+                            Command::Unhide => Message::PaneUnhideColumns.enqueue(),
+                            Command::Unmark => Message::PaneUnmarkRows.enqueue(),
                             Command::ThemeSelector => Message::AppShowThemeSelector.enqueue(),
                             Command::ToggleBorders => {
                                 config().toggle_show_table_borders();
@@ -106,9 +115,11 @@ impl Component for CommandPalette {
 enum Command {
     Cast,
     Edit,
+    Explain,
     Export,
     Filter,
     FuzzySearch,
+    Hide,
     Histogram,
     Import,
     Info,
@@ -120,11 +131,16 @@ enum Command {
     ScatterPlot,
     Search,
     Schema,
+    Script,
     Select,
+    Snippets,
     Sort,
+    SqlEditor,
     ThemeSelector,
     ToggleBorders,
     ToggleRowNumbers,
+    Unhide,
+    Unmark,
 }
 
 impl Command {
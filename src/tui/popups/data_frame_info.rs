@@ -4,7 +4,10 @@ use ratatui::layout::Margin;
 
 use crate::{
     handler::message::Message,
-    misc::sql::TableInfo,
+    misc::{
+        metadata::TableMetadata,
+        sql::{TableInfo, TableSource},
+    },
     tui::{component::Component, schema::data_frame_info},
 };
 
@@ -14,9 +17,11 @@ pub struct DataFrameInfo {
 }
 
 impl DataFrameInfo {
-    pub fn new(df: &DataFrame, input: crate::misc::sql::TableSource) -> Self {
+    pub fn new(df: &DataFrame, input: TableSource, metadata: TableMetadata) -> Self {
         Self {
-            data_frame_info: data_frame_info::DataFrameInfo::new(TableInfo::new(input, df)),
+            data_frame_info: data_frame_info::DataFrameInfo::new(
+                TableInfo::new(input, df).with_metadata(metadata),
+            ),
         }
     }
 }
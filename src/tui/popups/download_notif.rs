@@ -38,9 +38,13 @@ impl Component for DownloadNotification {
                 .percent(percent)
                 .render(area, buf);
         } else {
-            Paragraph::new("Downloading...")
-                .block(Block::default().title(self.title.as_str()).into_widget())
-                .render(area, buf);
+            Paragraph::new(if self.dl.reloads().is_some() {
+                "Reloading..."
+            } else {
+                "Downloading..."
+            })
+            .block(Block::default().title(self.title.as_str()).into_widget())
+            .render(area, buf);
         }
     }
 }
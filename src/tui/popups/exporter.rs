@@ -1,15 +1,22 @@
-use std::fmt::Display;
+use std::{fmt::Display, ops::Range};
 
-use polars::frame::DataFrame;
+use polars::{frame::DataFrame, prelude::IdxCa};
 use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, IntoStaticStr};
 
-use crate::tui::{
-    component::Component,
-    pickers::search_picker::SearchPicker,
-    popups::{
-        exporters::{arrow, avro, csv, json, jsonl, markdown, parquet, tsv},
-        multi_step_overlay::{MultiStepOverlay, OverlayStep},
+use crate::{
+    handler::message::Message,
+    tui::{
+        component::Component,
+        pickers::{list_picker::ListPicker, search_picker::SearchPicker, text_picker::TextPicker},
+        popups::{
+            exporters::{
+                arrow, avro, csv, html, json, jsonl, latex, markdown, org, parquet, sql_script,
+                sqlite, tsv, xlsx,
+            },
+            multi_step_overlay::{MultiStepOverlay, OverlayStep},
+        },
+        table::Table,
     },
 };
 
@@ -17,8 +24,25 @@ pub type Exporter = MultiStepOverlay<State>;
 
 #[derive(Debug)]
 pub enum State {
+    PickRows {
+        df: DataFrame,
+        view: TableView,
+        picker: ListPicker<Rows>,
+    },
+    PickRowRange {
+        df: DataFrame,
+        view: TableView,
+        picker: TextPicker,
+    },
+    PickColumns {
+        df: DataFrame,
+        view: TableView,
+        subset: bool,
+        picker: ListPicker<Columns>,
+    },
     PickFormat {
         df: DataFrame,
+        subset: bool,
         picker: SearchPicker<Format>,
     },
     Arrow {
@@ -30,45 +54,177 @@ pub enum State {
     Csv {
         state: csv::State,
     },
+    Html {
+        state: html::State,
+    },
     Json {
         state: json::State,
     },
     JsonL {
         state: jsonl::State,
     },
+    Latex {
+        state: latex::State,
+    },
     Markdown {
         state: markdown::State,
     },
+    Org {
+        state: org::State,
+    },
     Parquet {
         state: parquet::State,
     },
+    SqlScript {
+        state: sql_script::State,
+    },
+    Sqlite {
+        state: sqlite::State,
+    },
     Tsv {
         state: tsv::State,
     },
+    Xlsx {
+        state: xlsx::State,
+    },
 }
 
-impl From<DataFrame> for State {
-    fn from(value: DataFrame) -> Self {
+impl State {
+    /// Starts by picking the rows and columns of the table to export.
+    pub fn new(table: &Table) -> Self {
+        State::PickRows {
+            df: table.data_frame().clone(),
+            view: TableView {
+                marked_rows: table.marked_rows().iter().copied().collect(),
+                shown_columns: table.shown_column_names(),
+            },
+            picker: ListPicker::new(Rows::iter().collect()).with_title("Rows"),
+        }
+    }
+
+    fn pick_columns(df: DataFrame, view: TableView, subset: bool) -> Self {
+        if view.shown_columns.len() == df.width() {
+            State::pick_format(df, subset)
+        } else {
+            State::PickColumns {
+                df,
+                view,
+                subset,
+                picker: ListPicker::new(Columns::iter().collect()).with_title("Columns"),
+            }
+        }
+    }
+
+    fn pick_format(df: DataFrame, subset: bool) -> Self {
         State::PickFormat {
-            df: value,
+            df,
+            subset,
             picker: SearchPicker::new(Format::iter().collect()).with_title("Format"),
         }
     }
 }
 
+impl From<DataFrame> for State {
+    fn from(value: DataFrame) -> Self {
+        State::pick_format(value, false)
+    }
+}
+
 impl OverlayStep for State {
     fn next(self) -> Self {
         match self {
-            State::PickFormat { df, picker } => match picker.selected_item() {
+            State::PickRows { df, view, picker } => match picker.selected_item() {
+                Some(Rows::All) => State::pick_columns(df, view, false),
+                Some(Rows::Marked) if view.marked_rows.is_empty() => {
+                    Message::AppShowToast("No row is marked, mark rows with Space".to_owned())
+                        .enqueue();
+                    State::PickRows { df, view, picker }
+                }
+                Some(Rows::Marked) => {
+                    let marked = IdxCa::from_vec(
+                        "marked".into(),
+                        view.marked_rows.iter().map(|idx| *idx as _).collect(),
+                    );
+                    match df.take(&marked) {
+                        Ok(marked) => State::pick_columns(marked, view, true),
+                        Err(err) => {
+                            Message::AppShowError(err.to_string()).enqueue();
+                            State::PickRows { df, view, picker }
+                        }
+                    }
+                }
+                Some(Rows::Range) => State::PickRowRange {
+                    df,
+                    view,
+                    picker: TextPicker::default()
+                        .with_title("Row Range")
+                        .with_hint("First and last row, e.g., 10-250"),
+                },
+                None => State::PickRows { df, view, picker },
+            },
+            State::PickRowRange { df, view, picker } => {
+                match parse_row_range(picker.value(), df.height()) {
+                    Some(rows) => {
+                        State::pick_columns(df.slice(rows.start as i64, rows.len()), view, true)
+                    }
+                    None => {
+                        Message::AppShowToast(format!(
+                            "Invalid row range, rows are numbered 1 to {}",
+                            df.height()
+                        ))
+                        .enqueue();
+                        State::PickRowRange { df, view, picker }
+                    }
+                }
+            }
+            State::PickColumns {
+                df,
+                view,
+                subset,
+                picker,
+            } => match picker.selected_item() {
+                Some(Columns::All) => State::pick_format(df, subset),
+                Some(Columns::Shown) => match df.select(view.shown_columns.iter().cloned()) {
+                    Ok(shown) => State::pick_format(shown, true),
+                    Err(err) => {
+                        Message::AppShowError(err.to_string()).enqueue();
+                        State::PickColumns {
+                            df,
+                            view,
+                            subset,
+                            picker,
+                        }
+                    }
+                },
+                None => State::PickColumns {
+                    df,
+                    view,
+                    subset,
+                    picker,
+                },
+            },
+            State::PickFormat { df, subset, picker } => match picker.selected_item() {
                 Some(Format::Arrow) => State::Arrow { state: df.into() },
                 Some(Format::Avro) => State::Avro { state: df.into() },
                 Some(Format::Csv) => State::Csv { state: df.into() },
+                Some(Format::Html) => State::Html { state: df.into() },
                 Some(Format::Json) => State::Json { state: df.into() },
                 Some(Format::JsonL) => State::JsonL { state: df.into() },
+                Some(Format::Latex) => State::Latex { state: df.into() },
                 Some(Format::Markdown) => State::Markdown { state: df.into() },
+                Some(Format::Org) => State::Org { state: df.into() },
                 Some(Format::Parquet) => State::Parquet { state: df.into() },
+                Some(Format::SqlScript) => State::SqlScript { state: df.into() },
+                Some(Format::Sqlite) if subset => State::Sqlite {
+                    state: sqlite::State::single(df),
+                },
+                Some(Format::Sqlite) => State::Sqlite { state: df.into() },
                 Some(Format::Tsv) => State::Tsv { state: df.into() },
-                None => State::PickFormat { df, picker },
+                Some(Format::Xlsx) if subset => State::Xlsx {
+                    state: xlsx::State::single(df),
+                },
+                Some(Format::Xlsx) => State::Xlsx { state: df.into() },
+                None => State::PickFormat { df, subset, picker },
             },
             State::Arrow { state } => State::Arrow {
                 state: state.next(),
@@ -79,35 +235,79 @@ impl OverlayStep for State {
             State::Csv { state } => State::Csv {
                 state: state.next(),
             },
+            State::Html { state } => State::Html {
+                state: state.next(),
+            },
             State::Json { state } => State::Json {
                 state: state.next(),
             },
             State::JsonL { state } => State::JsonL {
                 state: state.next(),
             },
+            State::Latex { state } => State::Latex {
+                state: state.next(),
+            },
             State::Markdown { state } => State::Markdown {
                 state: state.next(),
             },
+            State::Org { state } => State::Org {
+                state: state.next(),
+            },
             State::Parquet { state } => State::Parquet {
                 state: state.next(),
             },
+            State::SqlScript { state } => State::SqlScript {
+                state: state.next(),
+            },
+            State::Sqlite { state } => State::Sqlite {
+                state: state.next(),
+            },
             State::Tsv { state } => State::Tsv {
                 state: state.next(),
             },
+            State::Xlsx { state } => State::Xlsx {
+                state: state.next(),
+            },
         }
     }
 
     fn responder(&mut self) -> &mut dyn Component {
         match self {
-            State::PickFormat { df: _, picker } => picker,
+            State::PickRows {
+                df: _,
+                view: _,
+                picker,
+            } => picker,
+            State::PickRowRange {
+                df: _,
+                view: _,
+                picker,
+            } => picker,
+            State::PickColumns {
+                df: _,
+                view: _,
+                subset: _,
+                picker,
+            } => picker,
+            State::PickFormat {
+                df: _,
+                subset: _,
+                picker,
+            } => picker,
             State::Arrow { state } => state.responder(),
             State::Avro { state } => state.responder(),
             State::Csv { state } => state.responder(),
+            State::Html { state } => state.responder(),
             State::Json { state } => state.responder(),
             State::JsonL { state } => state.responder(),
+            State::Latex { state } => state.responder(),
             State::Markdown { state } => state.responder(),
+            State::Org { state } => state.responder(),
             State::Parquet { state } => state.responder(),
+            State::SqlScript { state } => state.responder(),
+            State::Sqlite { state } => state.responder(),
             State::Tsv { state } => state.responder(),
+            State::Xlsx { state } => state.responder(),
         }
     }
 }
@@ -120,8 +320,16 @@ pub enum Format {
     Json,
     JsonL,
     Markdown,
+    Html,
+    #[strum(serialize = "LaTeX")]
+    Latex,
+    Org,
     Arrow,
     Avro,
+    Xlsx,
+    Sqlite,
+    #[strum(serialize = "SQL Script")]
+    SqlScript,
 }
 
 impl Display for Format {
@@ -129,3 +337,54 @@ impl Display for Format {
         write!(f, "{}", Into::<&str>::into(self))
     }
 }
+
+/// Marked rows and shown columns of the table when the exporter was opened.
+#[derive(Debug)]
+pub struct TableView {
+    marked_rows: Vec<usize>,
+    shown_columns: Vec<String>,
+}
+
+#[derive(Debug, IntoStaticStr, EnumIter, PartialEq)]
+pub enum Rows {
+    #[strum(serialize = "All Rows")]
+    All,
+    #[strum(serialize = "Marked Rows")]
+    Marked,
+    #[strum(serialize = "Row Range")]
+    Range,
+}
+
+impl Display for Rows {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Into::<&str>::into(self))
+    }
+}
+
+#[derive(Debug, IntoStaticStr, EnumIter, PartialEq)]
+pub enum Columns {
+    #[strum(serialize = "All Columns")]
+    All,
+    #[strum(serialize = "Shown Columns")]
+    Shown,
+}
+
+impl Display for Columns {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Into::<&str>::into(self))
+    }
+}
+
+/// Parses an inclusive, one-based range such as `10-250`, `10-` or `10`.
+fn parse_row_range(value: &str, height: usize) -> Option<Range<usize>> {
+    let (start, end) = value.split_once('-').unwrap_or((value, value));
+    let start = match start.trim() {
+        "" => 1,
+        start => start.parse().ok()?,
+    };
+    let end = match end.trim() {
+        "" => height,
+        end => end.parse::<usize>().ok()?.min(height),
+    };
+    (1..=end).contains(&start).then(|| start - 1..end)
+}
@@ -1,25 +1,35 @@
 use polars::frame::DataFrame;
+use strum::IntoEnumIterator;
 
 use crate::{
     handler::message::Message,
-    io::writer::{Destination, WriteToArrow, WriteToFile},
+    io::writer::{Destination, IpcCodec, WriteToArrow, WriteToFile},
     misc::type_ext::UnwrapOrEnqueueError,
     tui::{
         component::Component,
+        pickers::list_picker::ListPicker,
         popups::{file_picker::FilePicker, multi_step_overlay::OverlayStep},
     },
 };
 
 #[derive(Debug)]
 pub enum State {
-    PickOutputPath { df: DataFrame, picker: FilePicker },
+    PickCodec {
+        df: DataFrame,
+        picker: ListPicker<IpcCodec>,
+    },
+    PickOutputPath {
+        df: DataFrame,
+        codec: IpcCodec,
+        picker: FilePicker,
+    },
 }
 
 impl From<DataFrame> for State {
     fn from(value: DataFrame) -> Self {
-        Self::PickOutputPath {
+        Self::PickCodec {
             df: value,
-            picker: Default::default(),
+            picker: ListPicker::new(IpcCodec::iter().collect()).with_title("Compression"),
         }
     }
 }
@@ -27,8 +37,21 @@ impl From<DataFrame> for State {
 impl OverlayStep for State {
     fn next(self) -> Self {
         match self {
-            State::PickOutputPath { mut df, picker } => {
-                WriteToArrow
+            State::PickCodec { df, picker } => match picker.selected_item() {
+                Some(codec) => State::PickOutputPath {
+                    df,
+                    codec: *codec,
+                    picker: Default::default(),
+                },
+                None => State::PickCodec { df, picker },
+            },
+            State::PickOutputPath {
+                mut df,
+                codec,
+                picker,
+            } => {
+                WriteToArrow::default()
+                    .with_codec(codec)
                     .write_to_file(Destination::File(picker.path()), &mut df)
                     .unwrap_or_enqueue_error()
                     .then(|| {
@@ -43,14 +66,19 @@ impl OverlayStep for State {
                         ))
                         .enqueue();
                     });
-                Self::PickOutputPath { df, picker }
+                Self::PickOutputPath { df, codec, picker }
             }
         }
     }
 
     fn responder(&mut self) -> &mut dyn Component {
         match self {
-            State::PickOutputPath { df: _, picker } => picker,
+            State::PickCodec { df: _, picker } => picker,
+            State::PickOutputPath {
+                df: _,
+                codec: _,
+                picker,
+            } => picker,
         }
     }
 }
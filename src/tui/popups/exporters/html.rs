@@ -0,0 +1,123 @@
+use polars::frame::DataFrame;
+
+use crate::{
+    handler::message::Message,
+    io::writer::{Destination, WriteToFile, WriteToHtml},
+    misc::type_ext::UnwrapOrEnqueueError,
+    tui::{
+        component::Component,
+        popups::{
+            export_target_picker::{ExportTargetPicker, Target},
+            file_picker::FilePicker,
+            multi_step_overlay::OverlayStep,
+            yes_no_picker::YesNoPicker,
+        },
+    },
+};
+
+#[derive(Debug)]
+pub enum State {
+    PickStyled {
+        df: DataFrame,
+        picker: YesNoPicker,
+    },
+    PickOutputTarget {
+        df: DataFrame,
+        styled: bool,
+        picker: ExportTargetPicker,
+    },
+    PickOutputPath {
+        df: DataFrame,
+        styled: bool,
+        picker: FilePicker,
+    },
+}
+
+impl From<DataFrame> for State {
+    fn from(value: DataFrame) -> Self {
+        Self::PickStyled {
+            df: value,
+            picker: YesNoPicker::default().with_title("Include Theme Styles"),
+        }
+    }
+}
+
+impl OverlayStep for State {
+    fn next(self) -> State {
+        match self {
+            State::PickStyled { df, picker } => match picker.value() {
+                Some(styled) => State::PickOutputTarget {
+                    df,
+                    styled,
+                    picker: Default::default(),
+                },
+                None => State::PickStyled { df, picker },
+            },
+            State::PickOutputTarget {
+                mut df,
+                styled,
+                picker,
+            } => match picker.selected() {
+                Some(Target::File) => State::PickOutputPath {
+                    df,
+                    styled,
+                    picker: Default::default(),
+                },
+                Some(Target::Clipboard) => {
+                    WriteToHtml::default()
+                        .with_styled(styled)
+                        .write_to_file(Destination::Clipboard, &mut df)
+                        .unwrap_or_enqueue_error()
+                        .then(|| {
+                            Message::PaneDismissModal.enqueue();
+                            Message::AppShowToast(
+                                "Data frame exported to clipboard in HTML format".to_owned(),
+                            )
+                            .enqueue();
+                        });
+                    State::PickOutputTarget { df, styled, picker }
+                }
+                None => State::PickOutputTarget { df, styled, picker },
+            },
+            State::PickOutputPath {
+                mut df,
+                styled,
+                picker,
+            } => {
+                WriteToHtml::default()
+                    .with_styled(styled)
+                    .write_to_file(Destination::File(picker.path()), &mut df)
+                    .unwrap_or_enqueue_error()
+                    .then(|| {
+                        Message::PaneDismissModal.enqueue();
+                        Message::AppShowToast(format!(
+                            "Data frame exported to '{}' in HTML format",
+                            picker
+                                .path()
+                                .file_name()
+                                .unwrap_or_default()
+                                .to_string_lossy()
+                        ))
+                        .enqueue();
+                    });
+                State::PickOutputPath { df, styled, picker }
+            }
+        }
+    }
+
+    fn responder(&mut self) -> &mut dyn Component {
+        match self {
+            State::PickStyled { picker, df: _ } => picker,
+            State::PickOutputTarget {
+                picker,
+                df: _,
+                styled: _,
+            } => picker,
+            State::PickOutputPath {
+                picker,
+                df: _,
+                styled: _,
+            } => picker,
+        }
+    }
+}
@@ -1,8 +1,14 @@
 pub mod arrow;
 pub mod avro;
 pub mod csv;
+pub mod html;
 pub mod json;
 pub mod jsonl;
+pub mod latex;
 pub mod markdown;
+pub mod org;
 pub mod parquet;
+pub mod sql_script;
+pub mod sqlite;
 pub mod tsv;
+pub mod xlsx;
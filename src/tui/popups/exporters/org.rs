@@ -0,0 +1,88 @@
+use polars::frame::DataFrame;
+
+use crate::{
+    handler::message::Message,
+    io::writer::{Destination, WriteToFile, WriteToOrg},
+    misc::type_ext::UnwrapOrEnqueueError,
+    tui::{
+        component::Component,
+        popups::{
+            export_target_picker::{ExportTargetPicker, Target},
+            file_picker::FilePicker,
+            multi_step_overlay::OverlayStep,
+        },
+    },
+};
+
+#[derive(Debug)]
+pub enum State {
+    PickOutputTarget {
+        df: DataFrame,
+        picker: ExportTargetPicker,
+    },
+    PickOutputPath {
+        df: DataFrame,
+        picker: FilePicker,
+    },
+}
+
+impl From<DataFrame> for State {
+    fn from(value: DataFrame) -> Self {
+        Self::PickOutputTarget {
+            df: value,
+            picker: Default::default(),
+        }
+    }
+}
+
+impl OverlayStep for State {
+    fn next(self) -> State {
+        match self {
+            State::PickOutputTarget { mut df, picker } => match picker.selected() {
+                Some(Target::File) => State::PickOutputPath {
+                    df,
+                    picker: Default::default(),
+                },
+                Some(Target::Clipboard) => {
+                    WriteToOrg
+                        .write_to_file(Destination::Clipboard, &mut df)
+                        .unwrap_or_enqueue_error()
+                        .then(|| {
+                            Message::PaneDismissModal.enqueue();
+                            Message::AppShowToast(
+                                "Data frame exported to clipboard in Org format".to_owned(),
+                            )
+                            .enqueue();
+                        });
+                    State::PickOutputTarget { df, picker }
+                }
+                None => State::PickOutputTarget { picker, df },
+            },
+            State::PickOutputPath { mut df, picker } => {
+                WriteToOrg
+                    .write_to_file(Destination::File(picker.path()), &mut df)
+                    .unwrap_or_enqueue_error()
+                    .then(|| {
+                        Message::PaneDismissModal.enqueue();
+                        Message::AppShowToast(format!(
+                            "Data frame exported to '{}' in Org format",
+                            picker
+                                .path()
+                                .file_name()
+                                .unwrap_or_default()
+                                .to_string_lossy()
+                        ))
+                        .enqueue();
+                    });
+                State::PickOutputPath { df, picker }
+            }
+        }
+    }
+
+    fn responder(&mut self) -> &mut dyn Component {
+        match self {
+            State::PickOutputTarget { picker, df: _ } => picker,
+            State::PickOutputPath { picker, df: _ } => picker,
+        }
+    }
+}
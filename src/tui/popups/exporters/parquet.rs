@@ -1,25 +1,68 @@
 use polars::frame::DataFrame;
+use strum::IntoEnumIterator;
 
 use crate::{
     handler::message::Message,
-    io::writer::{Destination, WriteToFile, WriteToParquet},
+    io::writer::{Destination, ParquetCodec, WriteToFile, WriteToParquet},
     misc::type_ext::UnwrapOrEnqueueError,
     tui::{
         component::Component,
-        popups::{file_picker::FilePicker, multi_step_overlay::OverlayStep},
+        pickers::{list_picker::ListPicker, text_picker::TextPicker},
+        popups::{
+            file_picker::FilePicker, multi_step_overlay::OverlayStep, yes_no_picker::YesNoPicker,
+        },
+        widgets::input::InputType,
     },
 };
 
 #[derive(Debug)]
 pub enum State {
-    PickOutputPath { df: DataFrame, picker: FilePicker },
+    PickCodec {
+        df: DataFrame,
+        picker: ListPicker<ParquetCodec>,
+    },
+    PickLevel {
+        df: DataFrame,
+        writer: WriteToParquet,
+        picker: TextPicker,
+    },
+    PickRowGroupSize {
+        df: DataFrame,
+        writer: WriteToParquet,
+        picker: TextPicker,
+    },
+    PickStatistics {
+        df: DataFrame,
+        writer: WriteToParquet,
+        picker: YesNoPicker,
+    },
+    PickPartitionColumns {
+        df: DataFrame,
+        writer: WriteToParquet,
+        picker: TextPicker,
+    },
+    PickMaxRowsPerFile {
+        df: DataFrame,
+        writer: WriteToParquet,
+        picker: TextPicker,
+    },
+    PickDropPartitionColumns {
+        df: DataFrame,
+        writer: WriteToParquet,
+        picker: YesNoPicker,
+    },
+    PickOutputPath {
+        df: DataFrame,
+        writer: WriteToParquet,
+        picker: FilePicker,
+    },
 }
 
 impl From<DataFrame> for State {
     fn from(value: DataFrame) -> Self {
-        Self::PickOutputPath {
+        Self::PickCodec {
             df: value,
-            picker: Default::default(),
+            picker: ListPicker::new(ParquetCodec::iter().collect()).with_title("Compression"),
         }
     }
 }
@@ -27,8 +70,105 @@ impl From<DataFrame> for State {
 impl OverlayStep for State {
     fn next(self) -> Self {
         match self {
-            State::PickOutputPath { mut df, picker } => {
-                WriteToParquet
+            State::PickCodec { df, picker } => match picker.selected_item() {
+                Some(codec) if codec.has_level() => State::PickLevel {
+                    df,
+                    writer: WriteToParquet::default().with_codec(*codec),
+                    picker: TextPicker::default()
+                        .with_title("Compression Level")
+                        .with_hint("Leave empty for the default level")
+                        .with_input_type(InputType::Numeric),
+                },
+                Some(codec) => State::PickRowGroupSize {
+                    df,
+                    writer: WriteToParquet::default().with_codec(*codec),
+                    picker: row_group_size_picker(),
+                },
+                None => State::PickCodec { df, picker },
+            },
+            State::PickLevel { df, writer, picker } => match parse_optional(picker.value()) {
+                Some(level) => State::PickRowGroupSize {
+                    df,
+                    writer: writer.with_level(level),
+                    picker: row_group_size_picker(),
+                },
+                None => State::PickLevel { df, writer, picker },
+            },
+            State::PickRowGroupSize { df, writer, picker } => {
+                match parse_optional(picker.value()) {
+                    Some(row_group_size) => State::PickStatistics {
+                        df,
+                        writer: writer.with_row_group_size(row_group_size),
+                        picker: YesNoPicker::default().with_title("Write Statistics"),
+                    },
+                    None => State::PickRowGroupSize { df, writer, picker },
+                }
+            }
+            State::PickStatistics { df, writer, picker } => match picker.value() {
+                Some(statistics) => State::PickPartitionColumns {
+                    df,
+                    writer: writer.with_statistics(statistics),
+                    picker: TextPicker::default()
+                        .with_title("Partition Columns")
+                        .with_hint("Comma-separated, leave empty to export a single file"),
+                },
+                None => State::PickStatistics { df, writer, picker },
+            },
+            State::PickPartitionColumns { df, writer, picker } => {
+                let columns = picker
+                    .value()
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_owned)
+                    .collect::<Vec<_>>();
+                if columns.is_empty() {
+                    State::PickOutputPath {
+                        df,
+                        writer,
+                        picker: Default::default(),
+                    }
+                } else if let Some(missing) = columns
+                    .iter()
+                    .find(|name| df.column(name.as_str()).is_err())
+                {
+                    Message::AppShowToast(format!("Column '{missing}' not found")).enqueue();
+                    State::PickPartitionColumns { df, writer, picker }
+                } else {
+                    State::PickMaxRowsPerFile {
+                        df,
+                        writer: writer.with_partition_by(columns),
+                        picker: TextPicker::default()
+                            .with_title("Max Rows per File")
+                            .with_hint("Leave empty for one file per partition")
+                            .with_input_type(InputType::Numeric),
+                    }
+                }
+            }
+            State::PickMaxRowsPerFile { df, writer, picker } => {
+                match parse_optional(picker.value()) {
+                    Some(max_rows) => State::PickDropPartitionColumns {
+                        df,
+                        writer: writer.with_max_rows_per_file(max_rows),
+                        picker: YesNoPicker::default().with_title("Drop Partition Columns"),
+                    },
+                    None => State::PickMaxRowsPerFile { df, writer, picker },
+                }
+            }
+            State::PickDropPartitionColumns { df, writer, picker } => match picker.value() {
+                Some(drop) => State::PickOutputPath {
+                    df,
+                    writer: writer.with_drop_partition_columns(drop),
+                    picker: FilePicker::default().with_title("Output Directory"),
+                },
+                None => State::PickDropPartitionColumns { df, writer, picker },
+            },
+            State::PickOutputPath {
+                mut df,
+                writer,
+                picker,
+            } => {
+                writer
                     .write_to_file(Destination::File(picker.path()), &mut df)
                     .unwrap_or_enqueue_error()
                     .then(|| {
@@ -43,14 +183,70 @@ impl OverlayStep for State {
                         ))
                         .enqueue();
                     });
-                State::PickOutputPath { df, picker }
+                State::PickOutputPath { df, writer, picker }
             }
         }
     }
 
     fn responder(&mut self) -> &mut dyn Component {
         match self {
-            State::PickOutputPath { df: _, picker } => picker,
+            State::PickCodec { df: _, picker } => picker,
+            State::PickLevel {
+                df: _,
+                writer: _,
+                picker,
+            } => picker,
+            State::PickRowGroupSize {
+                df: _,
+                writer: _,
+                picker,
+            } => picker,
+            State::PickStatistics {
+                df: _,
+                writer: _,
+                picker,
+            } => picker,
+            State::PickPartitionColumns {
+                df: _,
+                writer: _,
+                picker,
+            } => picker,
+            State::PickMaxRowsPerFile {
+                df: _,
+                writer: _,
+                picker,
+            } => picker,
+            State::PickDropPartitionColumns {
+                df: _,
+                writer: _,
+                picker,
+            } => picker,
+            State::PickOutputPath {
+                df: _,
+                writer: _,
+                picker,
+            } => picker,
+        }
+    }
+}
+
+fn row_group_size_picker() -> TextPicker {
+    TextPicker::default()
+        .with_title("Row Group Size")
+        .with_hint("Leave empty for the default size")
+        .with_input_type(InputType::Numeric)
+}
+
+/// `Some(None)` for an empty value, `None` after reporting an invalid one.
+fn parse_optional<T: std::str::FromStr<Err: std::fmt::Display>>(value: &str) -> Option<Option<T>> {
+    if value.is_empty() {
+        return Some(None);
+    }
+    match value.parse() {
+        Ok(value) => Some(Some(value)),
+        Err(err) => {
+            Message::AppShowToast(err.to_string()).enqueue();
+            None
         }
     }
 }
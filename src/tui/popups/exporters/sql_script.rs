@@ -0,0 +1,180 @@
+use polars::frame::DataFrame;
+use strum::IntoEnumIterator;
+
+use crate::{
+    handler::message::Message,
+    io::writer::{Destination, SqlDialect, WriteToFile, WriteToSqlScript},
+    misc::type_ext::UnwrapOrEnqueueError,
+    tui::{
+        component::Component,
+        pickers::{list_picker::ListPicker, text_picker::TextPicker},
+        popups::{
+            export_target_picker::{ExportTargetPicker, Target},
+            file_picker::FilePicker,
+            multi_step_overlay::OverlayStep,
+        },
+    },
+};
+
+#[derive(Debug)]
+pub enum State {
+    PickDialect {
+        df: DataFrame,
+        picker: ListPicker<SqlDialect>,
+    },
+    PickTableName {
+        df: DataFrame,
+        dialect: SqlDialect,
+        picker: TextPicker,
+    },
+    PickOutputTarget {
+        df: DataFrame,
+        dialect: SqlDialect,
+        table: String,
+        picker: ExportTargetPicker,
+    },
+    PickOutputPath {
+        df: DataFrame,
+        dialect: SqlDialect,
+        table: String,
+        picker: FilePicker,
+    },
+}
+
+impl From<DataFrame> for State {
+    fn from(value: DataFrame) -> Self {
+        Self::PickDialect {
+            df: value,
+            picker: ListPicker::new(SqlDialect::iter().collect()).with_title("Dialect"),
+        }
+    }
+}
+
+impl OverlayStep for State {
+    fn next(self) -> Self {
+        match self {
+            State::PickDialect { df, picker } => match picker.selected_item() {
+                Some(dialect) => State::PickTableName {
+                    df,
+                    dialect: *dialect,
+                    picker: TextPicker::default()
+                        .with_title("Table Name")
+                        .with_value("data".to_owned()),
+                },
+                None => State::PickDialect { df, picker },
+            },
+            State::PickTableName {
+                df,
+                dialect,
+                picker,
+            } => {
+                if picker.value().trim().is_empty() {
+                    State::PickTableName {
+                        df,
+                        dialect,
+                        picker,
+                    }
+                } else {
+                    State::PickOutputTarget {
+                        df,
+                        dialect,
+                        table: picker.value().trim().to_owned(),
+                        picker: ExportTargetPicker::default(),
+                    }
+                }
+            }
+            State::PickOutputTarget {
+                mut df,
+                dialect,
+                table,
+                picker,
+            } => match picker.selected() {
+                Some(Target::Clipboard) => {
+                    WriteToSqlScript::default()
+                        .with_dialect(dialect)
+                        .with_table(&table)
+                        .write_to_file(Destination::Clipboard, &mut df)
+                        .unwrap_or_enqueue_error()
+                        .then(|| {
+                            Message::PaneDismissModal.enqueue();
+                            Message::AppShowToast(format!(
+                                "Data frame exported to clipboard in {dialect} SQL format"
+                            ))
+                            .enqueue();
+                        });
+                    State::PickOutputTarget {
+                        df,
+                        dialect,
+                        table,
+                        picker,
+                    }
+                }
+                Some(Target::File) => State::PickOutputPath {
+                    df,
+                    dialect,
+                    table,
+                    picker: FilePicker::default(),
+                },
+                None => State::PickOutputTarget {
+                    df,
+                    dialect,
+                    table,
+                    picker,
+                },
+            },
+            State::PickOutputPath {
+                mut df,
+                dialect,
+                table,
+                picker,
+            } => {
+                WriteToSqlScript::default()
+                    .with_dialect(dialect)
+                    .with_table(&table)
+                    .write_to_file(Destination::File(picker.path()), &mut df)
+                    .unwrap_or_enqueue_error()
+                    .then(|| {
+                        Message::PaneDismissModal.enqueue();
+                        Message::AppShowToast(format!(
+                            "Data frame exported to '{}' in {dialect} SQL format",
+                            picker
+                                .path()
+                                .file_name()
+                                .unwrap_or_default()
+                                .to_string_lossy()
+                        ))
+                        .enqueue();
+                    });
+                State::PickOutputPath {
+                    df,
+                    dialect,
+                    table,
+                    picker,
+                }
+            }
+        }
+    }
+
+    fn responder(&mut self) -> &mut dyn Component {
+        match self {
+            State::PickDialect { df: _, picker } => picker,
+            State::PickTableName {
+                df: _,
+                dialect: _,
+                picker,
+            } => picker,
+            State::PickOutputTarget {
+                df: _,
+                dialect: _,
+                table: _,
+                picker,
+            } => picker,
+            State::PickOutputPath {
+                df: _,
+                dialect: _,
+                table: _,
+                picker,
+            } => picker,
+        }
+    }
+}
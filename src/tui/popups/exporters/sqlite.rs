@@ -0,0 +1,183 @@
+use polars::frame::DataFrame;
+use strum::IntoEnumIterator;
+
+use crate::{
+    AppResult,
+    handler::message::Message,
+    io::writer::{Destination, SqliteWriteMode, WriteToFile, WriteToSqlite},
+    misc::{sql::sql, type_ext::UnwrapOrEnqueueError},
+    tui::{
+        component::Component,
+        pickers::{list_picker::ListPicker, text_picker::TextPicker},
+        popups::{
+            file_picker::FilePicker, multi_step_overlay::OverlayStep, yes_no_picker::YesNoPicker,
+        },
+    },
+};
+
+#[derive(Debug)]
+pub enum State {
+    PickAllTables {
+        df: DataFrame,
+        picker: YesNoPicker,
+    },
+    PickTableName {
+        df: DataFrame,
+        picker: TextPicker,
+    },
+    PickMode {
+        df: DataFrame,
+        table: Option<String>,
+        picker: ListPicker<SqliteWriteMode>,
+    },
+    PickOutputPath {
+        df: DataFrame,
+        table: Option<String>,
+        mode: SqliteWriteMode,
+        picker: FilePicker,
+    },
+}
+
+impl From<DataFrame> for State {
+    fn from(value: DataFrame) -> Self {
+        Self::PickAllTables {
+            df: value,
+            picker: YesNoPicker::default().with_title("Export Every Registered Table"),
+        }
+    }
+}
+
+impl State {
+    /// Skips the choice of exporting every registered table, for a subset of one table.
+    pub fn single(df: DataFrame) -> Self {
+        Self::PickTableName {
+            df,
+            picker: TextPicker::default()
+                .with_title("Table Name")
+                .with_value("data".to_owned()),
+        }
+    }
+}
+
+impl OverlayStep for State {
+    fn next(self) -> Self {
+        match self {
+            State::PickAllTables { df, picker } => match picker.value() {
+                Some(true) => State::PickMode {
+                    df,
+                    table: None,
+                    picker: mode_picker(),
+                },
+                Some(false) => State::PickTableName {
+                    df,
+                    picker: TextPicker::default()
+                        .with_title("Table Name")
+                        .with_value("data".to_owned()),
+                },
+                None => State::PickAllTables { df, picker },
+            },
+            State::PickTableName { df, picker } => {
+                if picker.value().trim().is_empty() {
+                    State::PickTableName { df, picker }
+                } else {
+                    State::PickMode {
+                        df,
+                        table: Some(picker.value().trim().to_owned()),
+                        picker: mode_picker(),
+                    }
+                }
+            }
+            State::PickMode { df, table, picker } => match picker.selected_item() {
+                Some(mode) => State::PickOutputPath {
+                    df,
+                    table,
+                    mode: *mode,
+                    picker: FilePicker::default(),
+                },
+                None => State::PickMode { df, table, picker },
+            },
+            State::PickOutputPath {
+                mut df,
+                table,
+                mode,
+                picker,
+            } => {
+                let result = match &table {
+                    Some(table) => WriteToSqlite::default()
+                        .with_table(table)
+                        .with_mode(mode)
+                        .write_to_file(Destination::File(picker.path()), &mut df),
+                    None => registered_tables().and_then(|tables| {
+                        WriteToSqlite::default().with_mode(mode).write_tables(
+                            picker.path(),
+                            tables.iter().map(|(name, df)| (name.as_str(), df)),
+                        )
+                    }),
+                };
+                result.unwrap_or_enqueue_error().then(|| {
+                    Message::PaneDismissModal.enqueue();
+                    Message::AppShowToast(format!(
+                        "{} exported to '{}' in SQLite format",
+                        if table.is_some() {
+                            "Data frame"
+                        } else {
+                            "Registered tables"
+                        },
+                        picker
+                            .path()
+                            .file_name()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                    ))
+                    .enqueue();
+                });
+                State::PickOutputPath {
+                    df,
+                    table,
+                    mode,
+                    picker,
+                }
+            }
+        }
+    }
+
+    fn responder(&mut self) -> &mut dyn Component {
+        match self {
+            State::PickAllTables { df: _, picker } => picker,
+            State::PickTableName { df: _, picker } => picker,
+            State::PickMode {
+                df: _,
+                table: _,
+                picker,
+            } => picker,
+            State::PickOutputPath {
+                df: _,
+                table: _,
+                mode: _,
+                picker,
+            } => picker,
+        }
+    }
+}
+
+fn mode_picker() -> ListPicker<SqliteWriteMode> {
+    ListPicker::new(SqliteWriteMode::iter().collect()).with_title("Write Mode")
+}
+
+fn registered_tables() -> AppResult<Vec<(String, DataFrame)>> {
+    let names = sql()
+        .schema()
+        .iter()
+        .map(|(name, _)| name.to_owned())
+        .collect::<Vec<_>>();
+    names
+        .into_iter()
+        .map(|name| {
+            let df = sql().execute(
+                &format!("SELECT * FROM \"{}\"", name.replace('"', "\"\"")),
+                None,
+            )?;
+            Ok((name, df))
+        })
+        .collect()
+}
@@ -0,0 +1,134 @@
+use polars::frame::DataFrame;
+
+use crate::{
+    handler::message::Message,
+    io::writer::{Destination, WriteToFile, WriteToXlsx},
+    misc::type_ext::UnwrapOrEnqueueError,
+    tui::{
+        component::Component,
+        popups::{
+            export_target_picker::{ExportTargetPicker, Target},
+            file_picker::FilePicker,
+            multi_step_overlay::OverlayStep,
+            yes_no_picker::YesNoPicker,
+        },
+    },
+};
+
+#[derive(Debug)]
+pub enum State {
+    PickAllTabs {
+        df: DataFrame,
+        picker: YesNoPicker,
+    },
+    PickOutputTarget {
+        df: DataFrame,
+        picker: ExportTargetPicker,
+    },
+    PickOutputPath {
+        df: DataFrame,
+        all_tabs: bool,
+        picker: FilePicker,
+    },
+}
+
+impl From<DataFrame> for State {
+    fn from(value: DataFrame) -> Self {
+        Self::PickAllTabs {
+            df: value,
+            picker: YesNoPicker::default().with_title("Export Every Open Tab"),
+        }
+    }
+}
+
+impl State {
+    /// Skips the choice of exporting every open tab, for a subset of one table.
+    pub fn single(df: DataFrame) -> Self {
+        Self::PickOutputTarget {
+            df,
+            picker: ExportTargetPicker::default(),
+        }
+    }
+}
+
+impl OverlayStep for State {
+    fn next(self) -> Self {
+        match self {
+            State::PickAllTabs { df, picker } => match picker.value() {
+                Some(true) => State::PickOutputPath {
+                    df,
+                    all_tabs: true,
+                    picker: FilePicker::default(),
+                },
+                Some(false) => State::PickOutputTarget {
+                    df,
+                    picker: ExportTargetPicker::default(),
+                },
+                None => State::PickAllTabs { df, picker },
+            },
+            State::PickOutputTarget { mut df, picker } => match picker.selected() {
+                Some(Target::Clipboard) => {
+                    WriteToXlsx
+                        .write_to_file(Destination::Clipboard, &mut df)
+                        .unwrap_or_enqueue_error()
+                        .then(|| {
+                            Message::PaneDismissModal.enqueue();
+                            Message::AppShowToast(
+                                "Data frame exported to clipboard in Excel format".to_owned(),
+                            )
+                            .enqueue();
+                        });
+                    State::PickOutputTarget { df, picker }
+                }
+                Some(Target::File) => State::PickOutputPath {
+                    df,
+                    all_tabs: false,
+                    picker: FilePicker::default(),
+                },
+                None => State::PickOutputTarget { df, picker },
+            },
+            State::PickOutputPath {
+                mut df,
+                all_tabs,
+                picker,
+            } => {
+                if all_tabs {
+                    Message::TabsExportXlsx(picker.path()).enqueue();
+                } else {
+                    WriteToXlsx
+                        .write_to_file(Destination::File(picker.path()), &mut df)
+                        .unwrap_or_enqueue_error()
+                        .then(|| {
+                            Message::PaneDismissModal.enqueue();
+                            Message::AppShowToast(format!(
+                                "Data frame exported to '{}' in Excel format",
+                                picker
+                                    .path()
+                                    .file_name()
+                                    .unwrap_or_default()
+                                    .to_string_lossy()
+                            ))
+                            .enqueue();
+                        });
+                }
+                State::PickOutputPath {
+                    df,
+                    all_tabs,
+                    picker,
+                }
+            }
+        }
+    }
+
+    fn responder(&mut self) -> &mut dyn Component {
+        match self {
+            State::PickAllTabs { df: _, picker } => picker,
+            State::PickOutputTarget { df: _, picker } => picker,
+            State::PickOutputPath {
+                df: _,
+                all_tabs: _,
+                picker,
+            } => picker,
+        }
+    }
+}
@@ -90,6 +90,10 @@ impl Component for Help {
             Span::styled("G", theme().text().bold()),
             Span::raw("              Move to last row"),
         ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Space", theme().text().bold()),
+            Span::raw("                  Mark / unmark row"),
+        ]));
         lines.push(Line::raw(""));
 
         // View Actions
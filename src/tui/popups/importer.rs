@@ -3,11 +3,14 @@ use std::fmt::Display;
 use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, IntoStaticStr};
 
+#[cfg(feature = "duckdb")]
+use crate::tui::popups::importers::duckdb;
 use crate::tui::{
     pickers::search_picker::SearchPicker,
     popups::{
         importers::{
-            arrow, avro, csv, excel, fwf, html, json, jsonl, logfmt, markdown, parquet, sqlite, tsv,
+            arrow, avro, csv, delta, excel, fwf, html, iceberg, json, jsonl, logfmt, markdown,
+            parquet, regex, sas, spss, sqlite, stata, toml, tsv, yaml,
         },
         multi_step_overlay::{MultiStepOverlay, OverlayStep},
     },
@@ -17,20 +20,76 @@ pub type Importer = MultiStepOverlay<State>;
 
 #[derive(Debug)]
 pub enum State {
-    PickFormat { picker: SearchPicker<Format> },
-    Arrow { arrow: arrow::State },
-    Avro { avro: avro::State },
-    Csv { csv: csv::State },
-    Excel { excel: excel::State },
-    Fwf { fwf: fwf::State },
-    Json { json: json::State },
-    JsonL { jsonl: jsonl::State },
-    Parquet { parquet: parquet::State },
-    Sqlite { sqlite: sqlite::State },
-    Tsv { tsv: tsv::State },
-    Logfmt { logfmt: logfmt::State },
-    Html { html: html::State },
-    Markdown { markdown: markdown::State },
+    PickFormat {
+        picker: SearchPicker<Format>,
+    },
+    Arrow {
+        arrow: arrow::State,
+    },
+    Avro {
+        avro: avro::State,
+    },
+    Csv {
+        csv: csv::State,
+    },
+    Excel {
+        excel: excel::State,
+    },
+    Fwf {
+        fwf: fwf::State,
+    },
+    Json {
+        json: json::State,
+    },
+    JsonL {
+        jsonl: jsonl::State,
+    },
+    Parquet {
+        parquet: parquet::State,
+    },
+    Sqlite {
+        sqlite: sqlite::State,
+    },
+    #[cfg(feature = "duckdb")]
+    DuckDb {
+        duckdb: duckdb::State,
+    },
+    Tsv {
+        tsv: tsv::State,
+    },
+    Logfmt {
+        logfmt: logfmt::State,
+    },
+    Html {
+        html: html::State,
+    },
+    Markdown {
+        markdown: markdown::State,
+    },
+    Yaml {
+        yaml: yaml::State,
+    },
+    Toml {
+        toml: toml::State,
+    },
+    Sas {
+        sas: sas::State,
+    },
+    Spss {
+        spss: spss::State,
+    },
+    Stata {
+        stata: stata::State,
+    },
+    Delta {
+        delta: delta::State,
+    },
+    Iceberg {
+        iceberg: iceberg::State,
+    },
+    Regex {
+        regex: regex::State,
+    },
 }
 
 impl OverlayStep for State {
@@ -64,6 +123,10 @@ impl OverlayStep for State {
                 Some(Format::Sqlite) => Self::Sqlite {
                     sqlite: Default::default(),
                 },
+                #[cfg(feature = "duckdb")]
+                Some(Format::DuckDb) => Self::DuckDb {
+                    duckdb: Default::default(),
+                },
                 Some(Format::Tsv) => Self::Tsv {
                     tsv: Default::default(),
                 },
@@ -76,6 +139,30 @@ impl OverlayStep for State {
                 Some(Format::Markdown) => Self::Markdown {
                     markdown: Default::default(),
                 },
+                Some(Format::Yaml) => Self::Yaml {
+                    yaml: Default::default(),
+                },
+                Some(Format::Toml) => Self::Toml {
+                    toml: Default::default(),
+                },
+                Some(Format::Sas) => Self::Sas {
+                    sas: Default::default(),
+                },
+                Some(Format::Spss) => Self::Spss {
+                    spss: Default::default(),
+                },
+                Some(Format::Stata) => Self::Stata {
+                    stata: Default::default(),
+                },
+                Some(Format::Delta) => Self::Delta {
+                    delta: Default::default(),
+                },
+                Some(Format::Iceberg) => Self::Iceberg {
+                    iceberg: Default::default(),
+                },
+                Some(Format::Regex) => Self::Regex {
+                    regex: Default::default(),
+                },
                 None => State::PickFormat { picker },
             },
             State::Arrow { arrow } => State::Arrow {
@@ -97,6 +184,10 @@ impl OverlayStep for State {
             State::Sqlite { sqlite } => State::Sqlite {
                 sqlite: sqlite.next(),
             },
+            #[cfg(feature = "duckdb")]
+            State::DuckDb { duckdb } => State::DuckDb {
+                duckdb: duckdb.next(),
+            },
             State::Tsv { tsv } => State::Tsv { tsv: tsv.next() },
             State::Logfmt { logfmt } => State::Logfmt {
                 logfmt: logfmt.next(),
@@ -105,6 +196,22 @@ impl OverlayStep for State {
             State::Markdown { markdown } => State::Markdown {
                 markdown: markdown.next(),
             },
+            State::Yaml { yaml } => State::Yaml { yaml: yaml.next() },
+            State::Toml { toml } => State::Toml { toml: toml.next() },
+            State::Sas { sas } => State::Sas { sas: sas.next() },
+            State::Spss { spss } => State::Spss { spss: spss.next() },
+            State::Stata { stata } => State::Stata {
+                stata: stata.next(),
+            },
+            State::Delta { delta } => State::Delta {
+                delta: delta.next(),
+            },
+            State::Iceberg { iceberg } => State::Iceberg {
+                iceberg: iceberg.next(),
+            },
+            State::Regex { regex } => State::Regex {
+                regex: regex.next(),
+            },
         }
     }
 
@@ -120,10 +227,20 @@ impl OverlayStep for State {
             State::JsonL { jsonl } => jsonl.responder(),
             State::Parquet { parquet } => parquet.responder(),
             State::Sqlite { sqlite } => sqlite.responder(),
+            #[cfg(feature = "duckdb")]
+            State::DuckDb { duckdb } => duckdb.responder(),
             State::Tsv { tsv } => tsv.responder(),
             State::Logfmt { logfmt } => logfmt.responder(),
             State::Html { html } => html.responder(),
             State::Markdown { markdown } => markdown.responder(),
+            State::Yaml { yaml } => yaml.responder(),
+            State::Toml { toml } => toml.responder(),
+            State::Sas { sas } => sas.responder(),
+            State::Spss { spss } => spss.responder(),
+            State::Stata { stata } => stata.responder(),
+            State::Delta { delta } => delta.responder(),
+            State::Iceberg { iceberg } => iceberg.responder(),
+            State::Regex { regex } => regex.responder(),
         }
     }
 }
@@ -147,10 +264,20 @@ pub enum Format {
     Avro,
     Fwf,
     Sqlite,
+    #[cfg(feature = "duckdb")]
+    DuckDb,
     Excel,
     Logfmt,
     Html,
     Markdown,
+    Yaml,
+    Toml,
+    Sas,
+    Spss,
+    Stata,
+    Delta,
+    Iceberg,
+    Regex,
 }
 
 impl Display for Format {
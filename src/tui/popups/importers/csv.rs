@@ -1,16 +1,25 @@
+use std::ops::ControlFlow;
+
 use super::{
     dismiss_overlay_and_load_data_frame,
+    encoding_picker::EncodingPicker,
     import_source_picker::{ImportSource, ImportSourcePicker},
+    read_options::ReadOptionsStep,
 };
 use crate::{
     handler::message::Message,
-    io::{DataSource, reader::CsvToDataFrame},
+    io::{
+        DataSource,
+        reader::{CsvDialect, CsvToDataFrame, ReaderSource},
+    },
+    misc::encoding::TextEncoding,
     tui::{
         pickers::text_picker::TextPicker,
         popups::{
             file_picker::FilePicker, multi_step_overlay::OverlayStep, url_picker::UrlPicker,
             yes_no_picker::YesNoPicker,
         },
+        widgets::input::InputType,
     },
 };
 
@@ -25,30 +34,53 @@ pub enum State {
     PickUrl {
         picker: UrlPicker,
     },
+    PickEncoding {
+        source: DataSource,
+        picker: EncodingPicker,
+    },
     PickHasHeader {
         source: DataSource,
+        dialect: Option<CsvDialect>,
+        reader: CsvToDataFrame,
         picker: YesNoPicker,
     },
     PickSeparator {
         source: DataSource,
-        has_header: bool,
+        dialect: Option<CsvDialect>,
+        reader: CsvToDataFrame,
         picker: TextPicker,
     },
     PickQuote {
-        separator: char,
         source: DataSource,
-        has_header: bool,
+        dialect: Option<CsvDialect>,
+        reader: CsvToDataFrame,
+        picker: TextPicker,
+    },
+    PickSkipLines {
+        source: DataSource,
+        dialect: Option<CsvDialect>,
+        reader: CsvToDataFrame,
+        picker: TextPicker,
+    },
+    PickCommentPrefix {
+        source: DataSource,
+        reader: CsvToDataFrame,
         picker: TextPicker,
     },
+    PickReadOptions {
+        source: DataSource,
+        reader: CsvToDataFrame,
+        step: ReadOptionsStep,
+    },
 }
 
 impl OverlayStep for State {
     fn next(self) -> Self {
         match self {
             State::PickSource { picker } => match picker.value() {
-                Some(ImportSource::Stdin) => State::PickHasHeader {
+                Some(ImportSource::Stdin) => State::PickEncoding {
                     source: DataSource::Stdin,
-                    picker: YesNoPicker::default().with_title("Has Header"),
+                    picker: EncodingPicker::default(),
                 },
                 Some(ImportSource::File) => State::PickPath {
                     picker: Default::default(),
@@ -60,78 +92,170 @@ impl OverlayStep for State {
                     picker: Default::default(),
                 },
             },
-            State::PickPath { picker } => State::PickHasHeader {
+            State::PickPath { picker } => State::PickEncoding {
                 source: DataSource::File(picker.path()),
-                picker: YesNoPicker::default().with_title("Has Header"),
+                picker: EncodingPicker::default(),
             },
             State::PickUrl { picker } => match picker.url() {
-                Ok(url) => State::PickHasHeader {
+                Ok(url) => State::PickEncoding {
                     source: DataSource::Url(url),
-                    picker: YesNoPicker::default().with_title("Has Header"),
+                    picker: EncodingPicker::default(),
                 },
                 Err(err) => {
                     Message::AppShowToast(err.to_string()).enqueue();
                     State::PickUrl { picker }
                 }
             },
-            State::PickHasHeader { source, picker } => State::PickSeparator {
+            State::PickEncoding { source, picker } => match picker.value() {
+                Some(encoding) => {
+                    let dialect = sniff(&source, encoding);
+                    State::PickHasHeader {
+                        picker: YesNoPicker::default()
+                            .with_title("Has Header")
+                            .with_value(dialect.as_ref().is_none_or(|d| d.has_header)),
+                        source,
+                        dialect,
+                        reader: CsvToDataFrame::default().with_encoding(encoding),
+                    }
+                }
+                None => State::PickEncoding { source, picker },
+            },
+            State::PickHasHeader {
                 source,
-                has_header: picker.value().unwrap_or(true),
+                dialect,
+                reader,
+                picker,
+            } => State::PickSeparator {
+                reader: reader.with_no_header(!picker.value().unwrap_or(true)),
                 picker: TextPicker::default()
                     .with_title("Separator")
-                    .with_max_len(1)
-                    .with_value(",".to_owned()),
+                    .with_hint("Leave empty to detect, \\t for tab")
+                    .with_max_len(2)
+                    .with_value(
+                        dialect
+                            .as_ref()
+                            .map(|d| escape_tab(d.separator))
+                            .unwrap_or_default(),
+                    ),
+                source,
+                dialect,
             },
             State::PickSeparator {
                 source,
-                has_header,
+                dialect,
+                reader,
                 picker,
-            } => {
-                if let Some(separator) = picker.value().chars().next() {
-                    State::PickQuote {
-                        separator,
-                        source,
-                        has_header,
-                        picker: TextPicker::default()
-                            .with_title("Quote")
-                            .with_max_len(1)
-                            .with_value("\"".to_owned()),
-                    }
-                } else {
+            } => match parse_char(picker.value()) {
+                Some(separator) => State::PickQuote {
+                    reader: match separator {
+                        Some(separator) => reader.with_separator(separator),
+                        None => reader,
+                    },
+                    picker: TextPicker::default()
+                        .with_title("Quote")
+                        .with_hint("Leave empty to detect")
+                        .with_max_len(1)
+                        .with_value(
+                            dialect
+                                .as_ref()
+                                .map(|d| d.quote_char.to_string())
+                                .unwrap_or_default(),
+                        ),
+                    source,
+                    dialect,
+                },
+                None => {
                     Message::AppShowToast("Invalid separator".to_owned()).enqueue();
                     State::PickSeparator {
                         source,
-                        has_header,
+                        dialect,
+                        reader,
                         picker,
                     }
                 }
-            }
+            },
             State::PickQuote {
-                separator,
                 source,
-                has_header,
+                dialect,
+                reader,
                 picker,
-            } => {
-                if let Some(quote) = picker.value().chars().next() {
-                    Message::AppDismissOverlay.enqueue();
-                    dismiss_overlay_and_load_data_frame(
-                        source,
-                        CsvToDataFrame::default()
-                            .with_no_header(!has_header)
-                            .with_separator(separator)
-                            .with_quote_char(quote),
-                    );
-                    Default::default()
-                } else {
+            } => match parse_char(picker.value()) {
+                Some(quote) => State::PickSkipLines {
+                    reader: match quote {
+                        Some(quote) => reader.with_quote_char(quote),
+                        None => reader,
+                    },
+                    picker: TextPicker::default()
+                        .with_title("Skip Lines")
+                        .with_hint("Lines before the header, leave empty to detect")
+                        .with_input_type(InputType::Numeric)
+                        .with_value(
+                            dialect
+                                .as_ref()
+                                .map(|d| d.skip_lines.to_string())
+                                .unwrap_or_default(),
+                        ),
+                    source,
+                    dialect,
+                },
+                None => {
                     Message::AppShowToast("Invalid quote char".to_owned()).enqueue();
                     State::PickQuote {
-                        separator,
                         source,
-                        has_header,
+                        dialect,
+                        reader,
                         picker,
                     }
                 }
+            },
+            State::PickSkipLines {
+                source,
+                dialect,
+                reader,
+                picker,
+            } => {
+                let reader = match picker.value().parse() {
+                    Ok(skip_lines) => reader.with_skip_lines(skip_lines),
+                    Err(_) => reader,
+                };
+                State::PickCommentPrefix {
+                    source,
+                    reader,
+                    picker: TextPicker::default()
+                        .with_title("Comment Prefix")
+                        .with_hint("Leave empty for none")
+                        .with_value(dialect.and_then(|d| d.comment_prefix).unwrap_or_default()),
+                }
+            }
+            State::PickCommentPrefix {
+                source,
+                reader,
+                picker,
+            } => {
+                let prefix = Some(picker.value())
+                    .filter(|prefix| !prefix.is_empty())
+                    .map(str::to_owned);
+                State::PickReadOptions {
+                    source,
+                    reader: reader.with_comment_prefix(prefix),
+                    step: ReadOptionsStep::default(),
+                }
             }
+            State::PickReadOptions {
+                source,
+                reader,
+                step,
+            } => match step.next() {
+                ControlFlow::Break(options) => {
+                    dismiss_overlay_and_load_data_frame(source, reader.with_read_options(options));
+                    Default::default()
+                }
+                ControlFlow::Continue(step) => State::PickReadOptions {
+                    source,
+                    reader,
+                    step,
+                },
+            },
         }
     }
 
@@ -140,18 +264,41 @@ impl OverlayStep for State {
             State::PickSource { picker } => picker,
             State::PickPath { picker } => picker,
             State::PickUrl { picker } => picker,
-            State::PickHasHeader { source: _, picker } => picker,
+            State::PickEncoding { source: _, picker } => picker,
+            State::PickHasHeader {
+                source: _,
+                dialect: _,
+                reader: _,
+                picker,
+            } => picker,
             State::PickSeparator {
                 source: _,
-                has_header: _,
+                dialect: _,
+                reader: _,
                 picker,
             } => picker,
             State::PickQuote {
-                separator: _,
                 source: _,
-                has_header: _,
+                dialect: _,
+                reader: _,
                 picker,
             } => picker,
+            State::PickSkipLines {
+                source: _,
+                dialect: _,
+                reader: _,
+                picker,
+            } => picker,
+            State::PickCommentPrefix {
+                source: _,
+                reader: _,
+                picker,
+            } => picker,
+            State::PickReadOptions {
+                source: _,
+                reader: _,
+                step,
+            } => step.responder(),
         }
     }
 }
@@ -163,3 +310,33 @@ impl Default for State {
         }
     }
 }
+
+/// Dialect of local sources, used to pre-fill the pickers. Remote sources are sniffed once
+/// downloaded.
+fn sniff(source: &DataSource, encoding: TextEncoding) -> Option<CsvDialect> {
+    let source = match source {
+        DataSource::File(path) => ReaderSource::File(path.clone()),
+        DataSource::Stdin => ReaderSource::Stdin,
+        DataSource::Url(_) => return None,
+    };
+    CsvDialect::sniff_source(&source, encoding).ok()
+}
+
+fn escape_tab(c: char) -> String {
+    if c == '\t' {
+        "\\t".to_owned()
+    } else {
+        c.to_string()
+    }
+}
+
+/// `Some(None)` for an empty value, meaning the character is detected, `None` if invalid.
+fn parse_char(value: &str) -> Option<Option<char>> {
+    let mut chars = value.chars();
+    match (value, chars.next(), chars.next()) {
+        ("\\t", _, _) => Some(Some('\t')),
+        (_, None, _) => Some(None),
+        (_, Some(c), None) => Some(Some(c)),
+        _ => None,
+    }
+}
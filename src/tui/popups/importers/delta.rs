@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+
+use crate::{
+    handler::message::Message,
+    io::{DataSource, reader::DeltaToDataFrame},
+    tui::{
+        pickers::text_picker::TextPicker,
+        popups::{
+            file_picker::FilePicker, importers::dismiss_overlay_and_load_data_frame,
+            multi_step_overlay::OverlayStep,
+        },
+        widgets::input::InputType,
+    },
+};
+
+#[derive(Debug)]
+pub enum State {
+    PickPath { picker: FilePicker },
+    PickVersion { path: PathBuf, picker: TextPicker },
+}
+
+impl OverlayStep for State {
+    fn next(self) -> Self {
+        match self {
+            State::PickPath { picker } => State::PickVersion {
+                path: picker.path(),
+                picker: TextPicker::default()
+                    .with_title("Version")
+                    .with_hint("Leave empty for the latest version")
+                    .with_input_type(InputType::Numeric),
+            },
+            State::PickVersion { path, picker } => match picker.value().parse::<i64>() {
+                Ok(version) => {
+                    dismiss_overlay_and_load_data_frame(
+                        DataSource::File(path),
+                        DeltaToDataFrame::default().with_version(Some(version)),
+                    );
+                    Default::default()
+                }
+                Err(_) if picker.value().is_empty() => {
+                    dismiss_overlay_and_load_data_frame(
+                        DataSource::File(path),
+                        DeltaToDataFrame::default(),
+                    );
+                    Default::default()
+                }
+                Err(err) => {
+                    Message::AppShowToast(err.to_string()).enqueue();
+                    State::PickVersion { path, picker }
+                }
+            },
+        }
+    }
+
+    fn responder(&mut self) -> &mut dyn crate::tui::component::Component {
+        match self {
+            State::PickPath { picker } => picker,
+            State::PickVersion { path: _, picker } => picker,
+        }
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State::PickPath {
+            picker: FilePicker::default().with_title("Table Directory"),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{
+    handler::message::Message,
+    io::{DataSource, reader::DuckDbToDataFrames},
+    tui::{
+        pickers::text_picker::TextPicker,
+        popups::{
+            file_picker::FilePicker,
+            importers::{
+                dismiss_overlay_and_load_data_frame,
+                import_source_picker::{ImportSource, ImportSourcePicker},
+            },
+            multi_step_overlay::OverlayStep,
+            url_picker::UrlPicker,
+        },
+    },
+};
+
+#[derive(Debug)]
+pub enum State {
+    PickSource {
+        picker: ImportSourcePicker,
+    },
+    PickPath {
+        picker: FilePicker,
+    },
+    PickUrl {
+        picker: UrlPicker,
+    },
+    PickQuery {
+        source: DataSource,
+        picker: TextPicker,
+    },
+}
+
+impl OverlayStep for State {
+    fn next(self) -> Self {
+        match self {
+            State::PickSource { picker } => match picker.value() {
+                Some(ImportSource::Stdin) => State::PickQuery {
+                    source: DataSource::Stdin,
+                    picker: TextPicker::default()
+                        .with_title("Query")
+                        .with_hint("Leave empty to load every table"),
+                },
+                Some(ImportSource::File) => State::PickPath {
+                    picker: FilePicker::default(),
+                },
+                Some(ImportSource::Url) => State::PickUrl {
+                    picker: UrlPicker::default(),
+                },
+                None => State::PickSource { picker },
+            },
+            State::PickPath { picker } => State::PickQuery {
+                source: DataSource::File(picker.path()),
+                picker: TextPicker::default()
+                    .with_title("Query")
+                    .with_hint("Leave empty to load every table"),
+            },
+            State::PickUrl { picker } => match picker.url() {
+                Ok(url) => State::PickQuery {
+                    source: DataSource::Url(url),
+                    picker: TextPicker::default()
+                        .with_title("Query")
+                        .with_hint("Leave empty to load every table"),
+                },
+                Err(err) => {
+                    Message::AppShowToast(err.to_string()).enqueue();
+                    State::PickUrl { picker }
+                }
+            },
+            State::PickQuery { source, picker } => {
+                dismiss_overlay_and_load_data_frame(
+                    source,
+                    if picker.value().is_empty() {
+                        DuckDbToDataFrames::default()
+                    } else {
+                        DuckDbToDataFrames::default().query(picker.value().to_owned())
+                    },
+                );
+                Default::default()
+            }
+        }
+    }
+
+    fn responder(&mut self) -> &mut dyn crate::tui::component::Component {
+        match self {
+            State::PickSource { picker } => picker,
+            State::PickPath { picker } => picker,
+            State::PickQuery { source: _, picker } => picker,
+            State::PickUrl { picker } => picker,
+        }
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State::PickSource {
+            picker: Default::default(),
+        }
+    }
+}
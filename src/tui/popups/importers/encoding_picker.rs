@@ -0,0 +1,38 @@
+use crate::{
+    misc::encoding::TextEncoding,
+    tui::{component::Component, pickers::search_picker::SearchPicker},
+};
+
+#[derive(Debug)]
+pub struct EncodingPicker {
+    search_picker: SearchPicker<TextEncoding>,
+}
+
+impl EncodingPicker {
+    pub fn value(&self) -> Option<TextEncoding> {
+        self.search_picker.selected_item().copied()
+    }
+}
+
+impl Component for EncodingPicker {
+    fn render(
+        &mut self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        focus_state: crate::tui::component::FocusState,
+    ) {
+        self.search_picker.render(area, buf, focus_state);
+    }
+
+    fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        self.search_picker.handle(event)
+    }
+}
+
+impl Default for EncodingPicker {
+    fn default() -> Self {
+        Self {
+            search_picker: SearchPicker::new(TextEncoding::common()).with_title("Encoding"),
+        }
+    }
+}
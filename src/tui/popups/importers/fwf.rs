@@ -1,12 +1,14 @@
 use crate::{
     handler::message::Message,
     io::{DataSource, reader::FwfToDataFrame},
+    misc::encoding::TextEncoding,
     tui::{
         pickers::text_picker::TextPicker,
         popups::{
             file_picker::FilePicker,
             importers::{
                 dismiss_overlay_and_load_data_frame,
+                encoding_picker::EncodingPicker,
                 import_source_picker::{ImportSource, ImportSourcePicker},
             },
             multi_step_overlay::OverlayStep,
@@ -28,19 +30,26 @@ pub enum State {
     PickUrl {
         picker: UrlPicker,
     },
+    PickEncoding {
+        source: DataSource,
+        picker: EncodingPicker,
+    },
     PickWidths {
         source: DataSource,
+        encoding: TextEncoding,
         picker: TextPicker,
     },
     PickHeader {
         widths: Vec<usize>,
         source: DataSource,
+        encoding: TextEncoding,
         picker: YesNoPicker,
     },
     PickSeparatorLength {
         has_header: bool,
         widths: Vec<usize>,
         source: DataSource,
+        encoding: TextEncoding,
         picker: TextPicker,
     },
     PickFlexibleWidth {
@@ -48,6 +57,7 @@ pub enum State {
         has_header: bool,
         widths: Vec<usize>,
         source: DataSource,
+        encoding: TextEncoding,
         picker: YesNoPicker,
     },
 }
@@ -59,39 +69,45 @@ impl OverlayStep for State {
                 Some(ImportSource::File) => State::PickPath {
                     picker: FilePicker::default(),
                 },
-                Some(ImportSource::Stdin) => State::PickWidths {
+                Some(ImportSource::Stdin) => State::PickEncoding {
                     source: DataSource::Stdin,
-                    picker: TextPicker::default()
-                        .with_input_type(InputType::MultiNumeric)
-                        .with_title("Widths")
-                        .with_hint("4 8 12 or leave empty to auto detect"),
+                    picker: EncodingPicker::default(),
                 },
                 Some(ImportSource::Url) => State::PickUrl {
                     picker: UrlPicker::default(),
                 },
                 None => State::PickSource { picker },
             },
-            State::PickPath { picker } => State::PickWidths {
+            State::PickPath { picker } => State::PickEncoding {
                 source: DataSource::File(picker.path()),
-                picker: TextPicker::default()
-                    .with_input_type(InputType::MultiNumeric)
-                    .with_title("Widths")
-                    .with_hint("4 8 12 or leave empty to auto detect"),
+                picker: EncodingPicker::default(),
             },
             State::PickUrl { picker } => match picker.url() {
-                Ok(url) => State::PickWidths {
+                Ok(url) => State::PickEncoding {
                     source: DataSource::Url(url),
-                    picker: TextPicker::default()
-                        .with_input_type(InputType::MultiNumeric)
-                        .with_title("Widths")
-                        .with_hint("4 8 12 or leave empty to auto detect"),
+                    picker: EncodingPicker::default(),
                 },
                 Err(err) => {
                     Message::AppShowToast(err.to_string()).enqueue();
                     State::PickUrl { picker }
                 }
             },
-            State::PickWidths { source, picker } => {
+            State::PickEncoding { source, picker } => match picker.value() {
+                Some(encoding) => State::PickWidths {
+                    source,
+                    encoding,
+                    picker: TextPicker::default()
+                        .with_input_type(InputType::MultiNumeric)
+                        .with_title("Widths")
+                        .with_hint("4 8 12 or leave empty to auto detect"),
+                },
+                None => State::PickEncoding { source, picker },
+            },
+            State::PickWidths {
+                source,
+                encoding,
+                picker,
+            } => {
                 let widths = picker
                     .value()
                     .split(' ')
@@ -101,18 +117,21 @@ impl OverlayStep for State {
                 State::PickHeader {
                     widths,
                     source,
+                    encoding,
                     picker: YesNoPicker::default().with_title("Has Header"),
                 }
             }
             State::PickHeader {
                 widths,
                 source,
+                encoding,
                 picker,
             } => match picker.value() {
                 Some(has_header) => State::PickSeparatorLength {
                     has_header,
                     widths,
                     source,
+                    encoding,
                     picker: TextPicker::default()
                         .with_input_type(InputType::Numeric)
                         .with_title("Separator Legnth"),
@@ -120,6 +139,7 @@ impl OverlayStep for State {
                 None => State::PickHeader {
                     widths,
                     source,
+                    encoding,
                     picker,
                 },
             },
@@ -127,6 +147,7 @@ impl OverlayStep for State {
                 has_header,
                 widths,
                 source,
+                encoding,
                 picker,
             } => {
                 let separator_length = picker.value().parse().unwrap_or(0);
@@ -135,6 +156,7 @@ impl OverlayStep for State {
                     has_header,
                     widths,
                     source,
+                    encoding,
                     picker: YesNoPicker::default().with_title("Flexible Width"),
                 }
             }
@@ -143,6 +165,7 @@ impl OverlayStep for State {
                 has_header,
                 widths,
                 source,
+                encoding,
                 picker,
             } => {
                 let flexible_width = picker.value().unwrap_or(true);
@@ -150,7 +173,8 @@ impl OverlayStep for State {
                     .with_flexible_width(flexible_width)
                     .with_has_header(has_header)
                     .with_separator_length(separator_length)
-                    .with_widths(widths);
+                    .with_widths(widths)
+                    .with_encoding(encoding);
                 dismiss_overlay_and_load_data_frame(source, rtdf);
                 Default::default()
             }
@@ -161,16 +185,23 @@ impl OverlayStep for State {
         match self {
             State::PickSource { picker } => picker,
             State::PickPath { picker } => picker,
-            State::PickWidths { source: _, picker } => picker,
+            State::PickEncoding { source: _, picker } => picker,
+            State::PickWidths {
+                source: _,
+                encoding: _,
+                picker,
+            } => picker,
             State::PickHeader {
                 widths: _,
                 source: _,
+                encoding: _,
                 picker,
             } => picker,
             State::PickSeparatorLength {
                 has_header: _,
                 widths: _,
                 source: _,
+                encoding: _,
                 picker,
             } => picker,
             State::PickFlexibleWidth {
@@ -178,6 +209,7 @@ impl OverlayStep for State {
                 has_header: _,
                 widths: _,
                 source: _,
+                encoding: _,
                 picker,
             } => picker,
             State::PickUrl { picker } => picker,
@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+
+use crate::{
+    handler::message::Message,
+    io::{DataSource, reader::IcebergToDataFrame},
+    tui::{
+        pickers::text_picker::TextPicker,
+        popups::{
+            file_picker::FilePicker, importers::dismiss_overlay_and_load_data_frame,
+            multi_step_overlay::OverlayStep,
+        },
+        widgets::input::InputType,
+    },
+};
+
+#[derive(Debug)]
+pub enum State {
+    PickPath { picker: FilePicker },
+    PickSnapshot { path: PathBuf, picker: TextPicker },
+}
+
+impl OverlayStep for State {
+    fn next(self) -> Self {
+        match self {
+            State::PickPath { picker } => State::PickSnapshot {
+                path: picker.path(),
+                picker: TextPicker::default()
+                    .with_title("Snapshot ID")
+                    .with_hint("Leave empty for the current snapshot")
+                    .with_input_type(InputType::Numeric),
+            },
+            State::PickSnapshot { path, picker } => match picker.value().parse::<i64>() {
+                Ok(snapshot_id) => {
+                    dismiss_overlay_and_load_data_frame(
+                        DataSource::File(path),
+                        IcebergToDataFrame::default().with_snapshot_id(Some(snapshot_id)),
+                    );
+                    Default::default()
+                }
+                Err(_) if picker.value().is_empty() => {
+                    dismiss_overlay_and_load_data_frame(
+                        DataSource::File(path),
+                        IcebergToDataFrame::default(),
+                    );
+                    Default::default()
+                }
+                Err(err) => {
+                    Message::AppShowToast(err.to_string()).enqueue();
+                    State::PickSnapshot { path, picker }
+                }
+            },
+        }
+    }
+
+    fn responder(&mut self) -> &mut dyn crate::tui::component::Component {
+        match self {
+            State::PickPath { picker } => picker,
+            State::PickSnapshot { path: _, picker } => picker,
+        }
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State::PickPath {
+            picker: FilePicker::default().with_title("Table Directory"),
+        }
+    }
+}
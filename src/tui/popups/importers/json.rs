@@ -5,6 +5,7 @@ use crate::{
         file_picker::FilePicker,
         importers::{
             dismiss_overlay_and_load_data_frame,
+            encoding_picker::EncodingPicker,
             import_source_picker::{ImportSource, ImportSourcePicker},
         },
         multi_step_overlay::OverlayStep,
@@ -14,22 +15,29 @@ use crate::{
 
 #[derive(Debug)]
 pub enum State {
-    PickSource { picker: ImportSourcePicker },
-    PickPath { picker: FilePicker },
-    PickUrl { picker: UrlPicker },
+    PickSource {
+        picker: ImportSourcePicker,
+    },
+    PickPath {
+        picker: FilePicker,
+    },
+    PickUrl {
+        picker: UrlPicker,
+    },
+    PickEncoding {
+        source: DataSource,
+        picker: EncodingPicker,
+    },
 }
 
 impl OverlayStep for State {
     fn next(self) -> Self {
         match self {
             State::PickSource { picker } => match picker.value() {
-                Some(ImportSource::Stdin) => {
-                    dismiss_overlay_and_load_data_frame(
-                        DataSource::Stdin,
-                        JsonToDataFrame::default(),
-                    );
-                    State::PickSource { picker }
-                }
+                Some(ImportSource::Stdin) => State::PickEncoding {
+                    source: DataSource::Stdin,
+                    picker: EncodingPicker::default(),
+                },
                 Some(ImportSource::File) => State::PickPath {
                     picker: FilePicker::default(),
                 },
@@ -38,26 +46,30 @@ impl OverlayStep for State {
                 },
                 None => State::PickSource { picker },
             },
-            State::PickPath { picker } => {
-                dismiss_overlay_and_load_data_frame(
-                    DataSource::File(picker.path()),
-                    JsonToDataFrame::default(),
-                );
-                Default::default()
-            }
+            State::PickPath { picker } => State::PickEncoding {
+                source: DataSource::File(picker.path()),
+                picker: EncodingPicker::default(),
+            },
             State::PickUrl { picker } => match picker.url() {
-                Ok(url) => {
-                    dismiss_overlay_and_load_data_frame(
-                        DataSource::Url(url),
-                        JsonToDataFrame::default(),
-                    );
-                    Default::default()
-                }
+                Ok(url) => State::PickEncoding {
+                    source: DataSource::Url(url),
+                    picker: EncodingPicker::default(),
+                },
                 Err(err) => {
                     Message::AppShowToast(err.to_string()).enqueue();
                     State::PickUrl { picker }
                 }
             },
+            State::PickEncoding { source, picker } => match picker.value() {
+                Some(encoding) => {
+                    dismiss_overlay_and_load_data_frame(
+                        source,
+                        JsonToDataFrame::default().with_encoding(encoding),
+                    );
+                    Default::default()
+                }
+                None => State::PickEncoding { source, picker },
+            },
         }
     }
 
@@ -66,6 +78,7 @@ impl OverlayStep for State {
             State::PickSource { picker } => picker,
             State::PickPath { picker } => picker,
             State::PickUrl { picker } => picker,
+            State::PickEncoding { source: _, picker } => picker,
         }
     }
 }
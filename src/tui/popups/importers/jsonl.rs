@@ -1,35 +1,59 @@
+use std::ops::ControlFlow;
+
 use crate::{
     handler::message::Message,
     io::{DataSource, reader::JsonLineToDataFrame},
-    tui::popups::{
-        file_picker::FilePicker,
-        importers::{
-            dismiss_overlay_and_load_data_frame,
-            import_source_picker::{ImportSource, ImportSourcePicker},
+    tui::{
+        pickers::text_picker::TextPicker,
+        popups::{
+            file_picker::FilePicker,
+            importers::{
+                dismiss_overlay_and_load_data_frame,
+                encoding_picker::EncodingPicker,
+                import_source_picker::{ImportSource, ImportSourcePicker},
+                read_options::ReadOptionsStep,
+            },
+            multi_step_overlay::OverlayStep,
+            url_picker::UrlPicker,
         },
-        multi_step_overlay::OverlayStep,
-        url_picker::UrlPicker,
     },
 };
 
 #[derive(Debug)]
 pub enum State {
-    PickSource { picker: ImportSourcePicker },
-    PickPath { picker: FilePicker },
-    PickUrl { picker: UrlPicker },
+    PickSource {
+        picker: ImportSourcePicker,
+    },
+    PickPath {
+        picker: FilePicker,
+    },
+    PickUrl {
+        picker: UrlPicker,
+    },
+    PickEncoding {
+        source: DataSource,
+        picker: EncodingPicker,
+    },
+    PickCommentPrefix {
+        source: DataSource,
+        reader: JsonLineToDataFrame,
+        picker: TextPicker,
+    },
+    PickReadOptions {
+        source: DataSource,
+        reader: JsonLineToDataFrame,
+        step: ReadOptionsStep,
+    },
 }
 
 impl OverlayStep for State {
     fn next(self) -> Self {
         match self {
             State::PickSource { picker } => match picker.value() {
-                Some(ImportSource::Stdin) => {
-                    dismiss_overlay_and_load_data_frame(
-                        DataSource::Stdin,
-                        JsonLineToDataFrame::default(),
-                    );
-                    State::PickSource { picker }
-                }
+                Some(ImportSource::Stdin) => State::PickEncoding {
+                    source: DataSource::Stdin,
+                    picker: EncodingPicker::default(),
+                },
                 Some(ImportSource::File) => State::PickPath {
                     picker: FilePicker::default(),
                 },
@@ -38,26 +62,58 @@ impl OverlayStep for State {
                 },
                 None => State::PickSource { picker },
             },
-            State::PickPath { picker } => {
-                dismiss_overlay_and_load_data_frame(
-                    DataSource::File(picker.path()),
-                    JsonLineToDataFrame::default(),
-                );
-                Default::default()
-            }
+            State::PickPath { picker } => State::PickEncoding {
+                source: DataSource::File(picker.path()),
+                picker: EncodingPicker::default(),
+            },
             State::PickUrl { picker } => match picker.url() {
-                Ok(url) => {
-                    dismiss_overlay_and_load_data_frame(
-                        DataSource::Url(url),
-                        JsonLineToDataFrame::default(),
-                    );
-                    Default::default()
-                }
+                Ok(url) => State::PickEncoding {
+                    source: DataSource::Url(url),
+                    picker: EncodingPicker::default(),
+                },
                 Err(err) => {
                     Message::AppShowToast(err.to_string()).enqueue();
                     State::PickUrl { picker }
                 }
             },
+            State::PickEncoding { source, picker } => match picker.value() {
+                Some(encoding) => State::PickCommentPrefix {
+                    source,
+                    reader: JsonLineToDataFrame::default().with_encoding(encoding),
+                    picker: TextPicker::default()
+                        .with_title("Comment Prefix")
+                        .with_hint("Leave empty for none"),
+                },
+                None => State::PickEncoding { source, picker },
+            },
+            State::PickCommentPrefix {
+                source,
+                reader,
+                picker,
+            } => State::PickReadOptions {
+                source,
+                reader: reader.with_comment_prefix(
+                    Some(picker.value())
+                        .filter(|prefix| !prefix.is_empty())
+                        .map(str::to_owned),
+                ),
+                step: ReadOptionsStep::default(),
+            },
+            State::PickReadOptions {
+                source,
+                reader,
+                step,
+            } => match step.next() {
+                ControlFlow::Break(options) => {
+                    dismiss_overlay_and_load_data_frame(source, reader.with_read_options(options));
+                    Default::default()
+                }
+                ControlFlow::Continue(step) => State::PickReadOptions {
+                    source,
+                    reader,
+                    step,
+                },
+            },
         }
     }
 
@@ -66,6 +122,17 @@ impl OverlayStep for State {
             State::PickSource { picker } => picker,
             State::PickPath { picker } => picker,
             State::PickUrl { picker } => picker,
+            State::PickEncoding { source: _, picker } => picker,
+            State::PickCommentPrefix {
+                source: _,
+                reader: _,
+                picker,
+            } => picker,
+            State::PickReadOptions {
+                source: _,
+                reader: _,
+                step,
+            } => step.responder(),
         }
     }
 }
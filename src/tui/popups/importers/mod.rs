@@ -5,24 +5,37 @@ use crate::{
     io::{DataSource, reader::ReaderSource},
     misc::{
         remote_load,
-        sql::{TableSource, sql},
+        sql::{TableReader, TableSource, sql},
     },
 };
 
 pub mod arrow;
 pub mod avro;
 pub mod csv;
+pub mod delta;
+#[cfg(feature = "duckdb")]
+pub mod duckdb;
+mod encoding_picker;
 pub mod excel;
 pub mod fwf;
 pub mod html;
+pub mod iceberg;
 mod import_source_picker;
 pub mod json;
 pub mod jsonl;
 pub mod logfmt;
 pub mod markdown;
 pub mod parquet;
+mod pattern_picker;
+mod read_options;
+pub mod regex;
+pub mod sas;
+pub mod spss;
 pub mod sqlite;
+pub mod stata;
+pub mod toml;
 pub mod tsv;
+pub mod yaml;
 
 fn dismiss_overlay_and_load_data_frame(source: DataSource, reader: impl remote_load::Reader) {
     Message::AppDismissOverlay.enqueue();
@@ -36,8 +49,10 @@ fn dismiss_overlay_and_load_data_frame(source: DataSource, reader: impl remote_l
                 }
             };
             let count = frames.len();
-            for (name, df) in frames {
+            for frame in frames {
+                let (name, df, metadata) = frame.into_parts();
                 let name = sql().register(&name, df.clone(), TableSource::Stdin);
+                sql().set_metadata(&name, metadata);
                 Message::TabsAddNamePane(df, name).enqueue();
             }
             Message::AppShowToast(format!("{} data frame(s) were imported from Stdin", count,))
@@ -52,8 +67,12 @@ fn dismiss_overlay_and_load_data_frame(source: DataSource, reader: impl remote_l
                 }
             };
             let count = frames.len();
-            for (name, df) in frames {
-                let name = sql().register(&name, df.clone(), TableSource::File(path_buf.clone()));
+            let reader = Arc::new(reader);
+            for frame in frames {
+                let (frame, df, metadata) = frame.into_parts();
+                let name = sql().register(&frame, df.clone(), TableSource::File(path_buf.clone()));
+                sql().set_metadata(&name, metadata);
+                sql().set_reader(&name, TableReader::new(reader.clone(), frame));
                 Message::TabsAddNamePane(df, name).enqueue();
             }
             Message::AppShowToast(format!(
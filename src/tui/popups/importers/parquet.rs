@@ -1,3 +1,5 @@
+use std::ops::ControlFlow;
+
 use crate::{
     handler::message::Message,
     io::{DataSource, reader::ParquetToDataFrame},
@@ -6,6 +8,7 @@ use crate::{
         importers::{
             dismiss_overlay_and_load_data_frame,
             import_source_picker::{ImportSource, ImportSourcePicker},
+            read_options::ReadOptionsStep,
         },
         multi_step_overlay::OverlayStep,
         url_picker::UrlPicker,
@@ -14,44 +17,61 @@ use crate::{
 
 #[derive(Debug)]
 pub enum State {
-    PickSource { picker: ImportSourcePicker },
-    PickPath { picker: FilePicker },
-    PickUrl { picker: UrlPicker },
+    PickSource {
+        picker: ImportSourcePicker,
+    },
+    PickPath {
+        picker: FilePicker,
+    },
+    PickUrl {
+        picker: UrlPicker,
+    },
+    PickReadOptions {
+        source: DataSource,
+        step: ReadOptionsStep,
+    },
 }
 
 impl OverlayStep for State {
     fn next(self) -> Self {
         match self {
             State::PickSource { picker } => match picker.value() {
-                Some(ImportSource::Stdin) => {
-                    dismiss_overlay_and_load_data_frame(DataSource::Stdin, ParquetToDataFrame);
-                    State::PickSource { picker }
-                }
+                Some(ImportSource::Stdin) => State::PickReadOptions {
+                    source: DataSource::Stdin,
+                    step: ReadOptionsStep::default(),
+                },
                 Some(ImportSource::File) => State::PickPath {
-                    picker: Default::default(),
+                    picker: FilePicker::default(),
                 },
                 Some(ImportSource::Url) => State::PickUrl {
                     picker: UrlPicker::default(),
                 },
                 None => State::PickSource { picker },
             },
-            State::PickPath { picker } => {
-                dismiss_overlay_and_load_data_frame(
-                    DataSource::File(picker.path()),
-                    ParquetToDataFrame,
-                );
-                Default::default()
-            }
+            State::PickPath { picker } => State::PickReadOptions {
+                source: DataSource::File(picker.path()),
+                step: ReadOptionsStep::default(),
+            },
             State::PickUrl { picker } => match picker.url() {
-                Ok(url) => {
-                    dismiss_overlay_and_load_data_frame(DataSource::Url(url), ParquetToDataFrame);
-                    Default::default()
-                }
+                Ok(url) => State::PickReadOptions {
+                    source: DataSource::Url(url),
+                    step: ReadOptionsStep::default(),
+                },
                 Err(err) => {
                     Message::AppShowToast(err.to_string()).enqueue();
                     State::PickUrl { picker }
                 }
             },
+            State::PickReadOptions { source, step } => match step.next() {
+                ControlFlow::Break(options) => {
+                    dismiss_overlay_and_load_data_frame(
+                        source,
+                        ParquetToDataFrame::default().with_read_options(options),
+                    );
+                    Default::default()
+                }
+                ControlFlow::Continue(step) => State::PickReadOptions { source, step },
+            },
         }
     }
 
@@ -60,13 +80,14 @@ impl OverlayStep for State {
             State::PickSource { picker } => picker,
             State::PickPath { picker } => picker,
             State::PickUrl { picker } => picker,
+            State::PickReadOptions { source: _, step } => step.responder(),
         }
     }
 }
 
 impl Default for State {
     fn default() -> Self {
-        Self::PickSource {
+        State::PickSource {
             picker: Default::default(),
         }
     }
@@ -0,0 +1,135 @@
+use std::{fs::File, io::Read};
+
+use polars::frame::DataFrame;
+use ratatui::{
+    layout::{Constraint, Flex, Layout},
+    text::Text,
+    widgets::{Clear, Paragraph, Row, Table, Widget, Wrap},
+};
+
+use crate::{
+    AppResult,
+    io::{DataSource, reader::RegexToDataFrame},
+    misc::{config::theme, encoding::TextEncoding},
+    tui::{component::Component, pickers::text_picker::TextPicker, widgets::block::Block},
+};
+
+const PREVIEW_LINES: usize = 5;
+const PREVIEW_BYTES: u64 = 1 << 16;
+
+/// Text picker for a regex pattern that previews how the first lines of the source are parsed.
+#[derive(Debug)]
+pub struct PatternPicker {
+    text_picker: TextPicker,
+    lines: Option<String>,
+    preview: AppResult<DataFrame>,
+}
+
+impl PatternPicker {
+    pub fn new(source: &DataSource, encoding: TextEncoding, pattern: String) -> Self {
+        let lines = match source {
+            DataSource::File(path) => File::open(path).ok().and_then(|file| {
+                let mut bytes = Vec::new();
+                file.take(PREVIEW_BYTES).read_to_end(&mut bytes).ok()?;
+                Some(
+                    String::from_utf8_lossy(&encoding.to_utf8(bytes))
+                        .lines()
+                        .filter(|line| !line.trim().is_empty())
+                        .take(PREVIEW_LINES)
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                )
+            }),
+            DataSource::Stdin | DataSource::Url(_) => None,
+        };
+        let mut picker = Self {
+            text_picker: TextPicker::default()
+                .with_title("Pattern")
+                .with_hint("Named capture groups, e.g., (?P<ip>\\S+) (?P<message>.*)")
+                .with_value(pattern),
+            lines,
+            preview: Ok(DataFrame::empty()),
+        };
+        picker.update_preview();
+        picker
+    }
+
+    pub fn value(&self) -> &str {
+        self.text_picker.value()
+    }
+
+    fn update_preview(&mut self) {
+        if let Some(lines) = &self.lines {
+            self.preview = RegexToDataFrame::default()
+                .with_pattern(self.text_picker.value())
+                .parse(lines);
+        }
+    }
+}
+
+impl Component for PatternPicker {
+    fn render(
+        &mut self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        focus_state: crate::tui::component::FocusState,
+    ) {
+        self.text_picker.render(area, buf, focus_state);
+
+        let [area] = Layout::horizontal([Constraint::Length(120)])
+            .flex(Flex::Center)
+            .areas(buf.area);
+        let [_, area] = Layout::vertical([
+            Constraint::Length(6),
+            Constraint::Length(PREVIEW_LINES as u16 + 3),
+        ])
+        .areas(area);
+        Widget::render(Clear, area, buf);
+        let block = Block::default().title("Preview").into_widget();
+
+        match (&self.lines, &self.preview) {
+            (None, _) => Paragraph::new("Preview is only available for files")
+                .style(theme().subtext())
+                .block(block)
+                .render(area, buf),
+            (Some(_), Err(err)) => Paragraph::new(err.to_string())
+                .style(theme().error())
+                .wrap(Wrap { trim: true })
+                .block(block)
+                .render(area, buf),
+            (Some(_), Ok(df)) => Table::default()
+                .header(
+                    Row::new(
+                        df.get_column_names()
+                            .into_iter()
+                            .enumerate()
+                            .map(|(i, name)| Text::styled(name.as_str(), theme().header(i))),
+                    )
+                    .style(theme().table_header()),
+                )
+                .rows((0..df.height()).map(|idx| {
+                    Row::new(df.columns().iter().map(|column| {
+                        column
+                            .str()
+                            .ok()
+                            .and_then(|values| values.get(idx))
+                            .unwrap_or_default()
+                            .to_owned()
+                    }))
+                    .style(theme().row(idx))
+                }))
+                .widths(vec![Constraint::Fill(1); df.width()])
+                .style(theme().text())
+                .block(block)
+                .render(area, buf),
+        }
+    }
+
+    fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        let handled = self.text_picker.handle(event);
+        if handled {
+            self.update_preview();
+        }
+        handled
+    }
+}
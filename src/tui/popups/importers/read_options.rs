@@ -0,0 +1,105 @@
+use std::ops::ControlFlow;
+
+use crate::{
+    io::reader::ReadOptions,
+    tui::{
+        component::Component, pickers::text_picker::TextPicker, popups::yes_no_picker::YesNoPicker,
+        widgets::input::InputType,
+    },
+};
+
+/// Optional steps narrowing down the rows and columns to load, ending the importers of the
+/// formats that support them.
+#[derive(Debug)]
+pub enum ReadOptionsStep {
+    Customize {
+        picker: YesNoPicker,
+    },
+    SkipRows {
+        picker: TextPicker,
+    },
+    RowLimit {
+        options: ReadOptions,
+        picker: TextPicker,
+    },
+    Columns {
+        options: ReadOptions,
+        picker: TextPicker,
+    },
+    NullValues {
+        options: ReadOptions,
+        picker: TextPicker,
+    },
+}
+
+impl ReadOptionsStep {
+    /// Moves to the next step, or breaks with the options after the last one.
+    pub fn next(self) -> ControlFlow<ReadOptions, Self> {
+        ControlFlow::Continue(match self {
+            ReadOptionsStep::Customize { picker } => match picker.value() {
+                Some(true) => ReadOptionsStep::SkipRows {
+                    picker: TextPicker::default()
+                        .with_title("Skip Rows")
+                        .with_hint("Data rows to skip, leave empty for none")
+                        .with_input_type(InputType::Numeric),
+                },
+                Some(false) => return ControlFlow::Break(ReadOptions::default()),
+                None => ReadOptionsStep::Customize { picker },
+            },
+            ReadOptionsStep::SkipRows { picker } => ReadOptionsStep::RowLimit {
+                options: ReadOptions::default().with_skip_rows(picker.value().parse().unwrap_or(0)),
+                picker: TextPicker::default()
+                    .with_title("Row Limit")
+                    .with_hint("Leave empty to load every row")
+                    .with_input_type(InputType::Numeric),
+            },
+            ReadOptionsStep::RowLimit { options, picker } => ReadOptionsStep::Columns {
+                options: options.with_n_rows(picker.value().parse().ok()),
+                picker: TextPicker::default()
+                    .with_title("Columns")
+                    .with_hint("Comma-separated, leave empty to load every column"),
+            },
+            ReadOptionsStep::Columns { options, picker } => ReadOptionsStep::NullValues {
+                options: options.with_columns(split_list(picker.value())),
+                picker: TextPicker::default()
+                    .with_title("Null Values")
+                    .with_hint("Comma-separated, e.g., NA,\\N"),
+            },
+            ReadOptionsStep::NullValues { options, picker } => {
+                return ControlFlow::Break(
+                    options.with_null_values(split_list(picker.value()).unwrap_or_default()),
+                );
+            }
+        })
+    }
+
+    pub fn responder(&mut self) -> &mut dyn Component {
+        match self {
+            ReadOptionsStep::Customize { picker } => picker,
+            ReadOptionsStep::SkipRows { picker } => picker,
+            ReadOptionsStep::RowLimit { options: _, picker } => picker,
+            ReadOptionsStep::Columns { options: _, picker } => picker,
+            ReadOptionsStep::NullValues { options: _, picker } => picker,
+        }
+    }
+}
+
+impl Default for ReadOptionsStep {
+    fn default() -> Self {
+        ReadOptionsStep::Customize {
+            picker: YesNoPicker::default()
+                .with_title("Customize Read Options")
+                .with_value(false),
+        }
+    }
+}
+
+fn split_list(value: &str) -> Option<Vec<String>> {
+    let items = value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    (!items.is_empty()).then_some(items)
+}
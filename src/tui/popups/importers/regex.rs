@@ -0,0 +1,200 @@
+use std::fmt::Display;
+
+use clap::ValueEnum;
+
+use super::{
+    dismiss_overlay_and_load_data_frame,
+    encoding_picker::EncodingPicker,
+    import_source_picker::{ImportSource, ImportSourcePicker},
+    pattern_picker::PatternPicker,
+};
+use crate::{
+    args::PatternPreset,
+    handler::message::Message,
+    io::{DataSource, reader::RegexToDataFrame},
+    misc::encoding::TextEncoding,
+    tui::{
+        pickers::list_picker::ListPicker,
+        popups::{
+            file_picker::FilePicker, multi_step_overlay::OverlayStep, url_picker::UrlPicker,
+            yes_no_picker::YesNoPicker,
+        },
+    },
+};
+
+#[derive(Debug)]
+pub enum State {
+    PickSource {
+        picker: ImportSourcePicker,
+    },
+    PickPath {
+        picker: FilePicker,
+    },
+    PickUrl {
+        picker: UrlPicker,
+    },
+    PickEncoding {
+        source: DataSource,
+        picker: EncodingPicker,
+    },
+    PickPreset {
+        source: DataSource,
+        encoding: TextEncoding,
+        picker: ListPicker<Preset>,
+    },
+    PickPattern {
+        source: DataSource,
+        encoding: TextEncoding,
+        picker: PatternPicker,
+    },
+    PickSkipUnmatched {
+        source: DataSource,
+        encoding: TextEncoding,
+        pattern: String,
+        picker: YesNoPicker,
+    },
+}
+
+impl OverlayStep for State {
+    fn next(self) -> Self {
+        match self {
+            State::PickSource { picker } => match picker.value() {
+                Some(ImportSource::Stdin) => State::PickEncoding {
+                    source: DataSource::Stdin,
+                    picker: EncodingPicker::default(),
+                },
+                Some(ImportSource::File) => State::PickPath {
+                    picker: FilePicker::default(),
+                },
+                Some(ImportSource::Url) => State::PickUrl {
+                    picker: UrlPicker::default(),
+                },
+                None => State::PickSource { picker },
+            },
+            State::PickPath { picker } => State::PickEncoding {
+                source: DataSource::File(picker.path()),
+                picker: EncodingPicker::default(),
+            },
+            State::PickUrl { picker } => match picker.url() {
+                Ok(url) => State::PickEncoding {
+                    source: DataSource::Url(url),
+                    picker: EncodingPicker::default(),
+                },
+                Err(err) => {
+                    Message::AppShowToast(err.to_string()).enqueue();
+                    State::PickUrl { picker }
+                }
+            },
+            State::PickEncoding { source, picker } => match picker.value() {
+                Some(encoding) => State::PickPreset {
+                    source,
+                    encoding,
+                    picker: preset_picker(),
+                },
+                None => State::PickEncoding { source, picker },
+            },
+            State::PickPreset {
+                source,
+                encoding,
+                picker,
+            } => {
+                let pattern = match picker.selected_item() {
+                    Some(Preset::Builtin(preset)) => preset.pattern().to_owned(),
+                    Some(Preset::Custom) | None => String::default(),
+                };
+                State::PickPattern {
+                    picker: PatternPicker::new(&source, encoding, pattern),
+                    source,
+                    encoding,
+                }
+            }
+            State::PickPattern {
+                source,
+                encoding,
+                picker,
+            } => State::PickSkipUnmatched {
+                source,
+                encoding,
+                pattern: picker.value().to_owned(),
+                picker: YesNoPicker::default().with_title("Skip Unmatched Lines"),
+            },
+            State::PickSkipUnmatched {
+                source,
+                encoding,
+                pattern,
+                picker,
+            } => {
+                dismiss_overlay_and_load_data_frame(
+                    source,
+                    RegexToDataFrame::default()
+                        .with_pattern(pattern)
+                        .with_skip_unmatched(picker.value().unwrap_or(false))
+                        .with_encoding(encoding),
+                );
+                Default::default()
+            }
+        }
+    }
+
+    fn responder(&mut self) -> &mut dyn crate::tui::component::Component {
+        match self {
+            State::PickSource { picker } => picker,
+            State::PickPath { picker } => picker,
+            State::PickUrl { picker } => picker,
+            State::PickEncoding { source: _, picker } => picker,
+            State::PickPreset {
+                source: _,
+                encoding: _,
+                picker,
+            } => picker,
+            State::PickPattern {
+                source: _,
+                encoding: _,
+                picker,
+            } => picker,
+            State::PickSkipUnmatched {
+                source: _,
+                encoding: _,
+                pattern: _,
+                picker,
+            } => picker,
+        }
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State::PickSource {
+            picker: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Preset {
+    Custom,
+    Builtin(PatternPreset),
+}
+
+impl Display for Preset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Preset::Custom => write!(f, "Custom"),
+            Preset::Builtin(preset) => write!(f, "{preset}"),
+        }
+    }
+}
+
+fn preset_picker() -> ListPicker<Preset> {
+    ListPicker::new(
+        std::iter::once(Preset::Custom)
+            .chain(
+                PatternPreset::value_variants()
+                    .iter()
+                    .copied()
+                    .map(Preset::Builtin),
+            )
+            .collect(),
+    )
+    .with_title("Pattern Preset")
+}
@@ -0,0 +1,88 @@
+use super::{
+    dismiss_overlay_and_load_data_frame,
+    import_source_picker::{ImportSource, ImportSourcePicker},
+};
+use crate::{
+    handler::message::Message,
+    io::{DataSource, reader::StataToDataFrame},
+    tui::popups::{
+        file_picker::FilePicker, multi_step_overlay::OverlayStep, url_picker::UrlPicker,
+        yes_no_picker::YesNoPicker,
+    },
+};
+
+#[derive(Debug)]
+pub enum State {
+    PickSource {
+        picker: ImportSourcePicker,
+    },
+    PickPath {
+        picker: FilePicker,
+    },
+    PickUrl {
+        picker: UrlPicker,
+    },
+    PickApplyValueLabels {
+        source: DataSource,
+        picker: YesNoPicker,
+    },
+}
+
+impl OverlayStep for State {
+    fn next(self) -> Self {
+        match self {
+            State::PickSource { picker } => match picker.value() {
+                Some(ImportSource::Stdin) => State::PickApplyValueLabels {
+                    source: DataSource::Stdin,
+                    picker: YesNoPicker::default().with_title("Apply Value Labels"),
+                },
+                Some(ImportSource::File) => State::PickPath {
+                    picker: FilePicker::default(),
+                },
+                Some(ImportSource::Url) => State::PickUrl {
+                    picker: UrlPicker::default(),
+                },
+                None => State::PickSource { picker },
+            },
+            State::PickPath { picker } => State::PickApplyValueLabels {
+                source: DataSource::File(picker.path()),
+                picker: YesNoPicker::default().with_title("Apply Value Labels"),
+            },
+            State::PickUrl { picker } => match picker.url() {
+                Ok(url) => State::PickApplyValueLabels {
+                    source: DataSource::Url(url),
+                    picker: YesNoPicker::default().with_title("Apply Value Labels"),
+                },
+                Err(err) => {
+                    Message::AppShowToast(err.to_string()).enqueue();
+                    State::PickUrl { picker }
+                }
+            },
+            State::PickApplyValueLabels { source, picker } => {
+                dismiss_overlay_and_load_data_frame(
+                    source,
+                    StataToDataFrame::default()
+                        .with_apply_value_labels(picker.value().unwrap_or(true)),
+                );
+                Default::default()
+            }
+        }
+    }
+
+    fn responder(&mut self) -> &mut dyn crate::tui::component::Component {
+        match self {
+            State::PickSource { picker } => picker,
+            State::PickPath { picker } => picker,
+            State::PickUrl { picker } => picker,
+            State::PickApplyValueLabels { source: _, picker } => picker,
+        }
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State::PickSource {
+            picker: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{
+    handler::message::Message,
+    io::{DataSource, reader::TomlToDataFrame},
+    tui::popups::{
+        file_picker::FilePicker,
+        importers::{
+            dismiss_overlay_and_load_data_frame,
+            encoding_picker::EncodingPicker,
+            import_source_picker::{ImportSource, ImportSourcePicker},
+        },
+        multi_step_overlay::OverlayStep,
+        url_picker::UrlPicker,
+    },
+};
+
+#[derive(Debug)]
+pub enum State {
+    PickSource {
+        picker: ImportSourcePicker,
+    },
+    PickPath {
+        picker: FilePicker,
+    },
+    PickUrl {
+        picker: UrlPicker,
+    },
+    PickEncoding {
+        source: DataSource,
+        picker: EncodingPicker,
+    },
+}
+
+impl OverlayStep for State {
+    fn next(self) -> Self {
+        match self {
+            State::PickSource { picker } => match picker.value() {
+                Some(ImportSource::Stdin) => State::PickEncoding {
+                    source: DataSource::Stdin,
+                    picker: EncodingPicker::default(),
+                },
+                Some(ImportSource::File) => State::PickPath {
+                    picker: FilePicker::default(),
+                },
+                Some(ImportSource::Url) => State::PickUrl {
+                    picker: UrlPicker::default(),
+                },
+                None => State::PickSource { picker },
+            },
+            State::PickPath { picker } => State::PickEncoding {
+                source: DataSource::File(picker.path()),
+                picker: EncodingPicker::default(),
+            },
+            State::PickUrl { picker } => match picker.url() {
+                Ok(url) => State::PickEncoding {
+                    source: DataSource::Url(url),
+                    picker: EncodingPicker::default(),
+                },
+                Err(err) => {
+                    Message::AppShowToast(err.to_string()).enqueue();
+                    State::PickUrl { picker }
+                }
+            },
+            State::PickEncoding { source, picker } => match picker.value() {
+                Some(encoding) => {
+                    dismiss_overlay_and_load_data_frame(
+                        source,
+                        TomlToDataFrame::default().with_encoding(encoding),
+                    );
+                    Default::default()
+                }
+                None => State::PickEncoding { source, picker },
+            },
+        }
+    }
+
+    fn responder(&mut self) -> &mut dyn crate::tui::component::Component {
+        match self {
+            State::PickSource { picker } => picker,
+            State::PickPath { picker } => picker,
+            State::PickUrl { picker } => picker,
+            State::PickEncoding { source: _, picker } => picker,
+        }
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State::PickSource {
+            picker: Default::default(),
+        }
+    }
+}
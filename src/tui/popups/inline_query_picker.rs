@@ -1,9 +1,13 @@
 use crossterm::event::{KeyCode, KeyModifiers};
 use polars::frame::DataFrame;
+use ratatui::text::Line;
 
 use crate::{
     handler::message::Message,
-    misc::sql::sql,
+    misc::{
+        history::{HistoryKind, history, remember},
+        query_job::{PlannedQuery, QueryTarget},
+    },
     sql_completion::{self, SqlSuggestion},
     tui::{
         component::Component,
@@ -28,7 +32,8 @@ impl InlineQueryPicker {
             all_columns,
         };
         Self {
-            picker: TextPickerWithSuggestion::new(query_type.title(), provider),
+            picker: TextPickerWithSuggestion::new(query_type.title(), provider)
+                .with_history(history().entries(query_type.history_kind()).to_vec()),
             dataframe,
             query_type,
         }
@@ -36,51 +41,34 @@ impl InlineQueryPicker {
 
     fn submit(&self) {
         let value = self.picker.value();
-        let result = match self.query_type {
-            QueryType::Select => {
-                sql().execute(&format!("SELECT {value} FROM _"), self.dataframe.clone())
-            }
-            QueryType::Filter => sql().execute(
-                &format!("SELECT * FROM _ where {value}"),
-                self.dataframe.clone(),
+        let (query, description, toast) = match self.query_type {
+            QueryType::Select => (
+                format!("SELECT {value} FROM _"),
+                TableDescription::Select(value.to_owned()),
+                format!("Column selection '{value}' occurred"),
+            ),
+            QueryType::Filter => (
+                format!("SELECT * FROM _ where {value}"),
+                TableDescription::Filter(value.to_owned()),
+                format!("Filter '{value}' applied"),
             ),
-            QueryType::Order => sql().execute(
-                &format!("SELECT * FROM _ ORDER BY {value}"),
-                self.dataframe.clone(),
+            QueryType::Order => (
+                format!("SELECT * FROM _ ORDER BY {value}"),
+                TableDescription::Order(value.to_owned()),
+                format!("Data frame ordered by '{value}'"),
             ),
         };
-        match (result, self.query_type) {
-            (Ok(result_dataframe), QueryType::Select) => {
-                Message::PaneDismissModal.enqueue();
-                Message::PanePushDataFrame(
-                    result_dataframe,
-                    TableDescription::Select(value.to_owned()),
-                )
-                .enqueue();
-                Message::AppShowToast(format!("Column selection '{value}' occurred")).enqueue();
-            }
-            (Ok(result_dataframe), QueryType::Order) => {
-                Message::PaneDismissModal.enqueue();
-                Message::PanePushDataFrame(
-                    result_dataframe,
-                    TableDescription::Order(value.to_owned()),
-                )
-                .enqueue();
-                Message::AppShowToast(format!("Data frame ordered by '{value}'")).enqueue();
-            }
-            (Ok(result_dataframe), QueryType::Filter) => {
-                Message::PaneDismissModal.enqueue();
-                Message::PanePushDataFrame(
-                    result_dataframe,
-                    TableDescription::Filter(value.to_owned()),
-                )
-                .enqueue();
-                Message::AppShowToast(format!("Filter '{value}' applied")).enqueue();
-            }
-            (Err(error), _) => {
-                Message::PaneDismissModal.enqueue();
-                Message::AppShowError(error.to_string()).enqueue();
+        Message::PaneDismissModal.enqueue();
+        match PlannedQuery::new(
+            &query,
+            self.dataframe.clone(),
+            QueryTarget::Pane(description, toast),
+        ) {
+            Ok(query) => {
+                remember(self.query_type.history_kind(), value);
+                Message::TabsRunQuery(Box::new(query)).enqueue();
             }
+            Err(error) => Message::AppShowError(error.to_string()).enqueue(),
         }
     }
 }
@@ -138,6 +126,11 @@ impl Provider for InlineQueryProvider {
             Some(&self.dataframe),
         )
     }
+
+    fn hint(&self, value: &str, cursor: usize) -> Option<Line<'static>> {
+        sql_completion::signature_hint(value, cursor, self.query_type.sql_prefix())
+            .map(|hint| hint.line())
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -156,6 +149,14 @@ impl QueryType {
         }
     }
 
+    fn history_kind(&self) -> HistoryKind {
+        match self {
+            QueryType::Select => HistoryKind::Select,
+            QueryType::Filter => HistoryKind::Filter,
+            QueryType::Order => HistoryKind::Order,
+        }
+    }
+
     /// SQL fragment prepended to the user's input so that the tokenizer sees
     /// the full clause context (e.g. a comma after `SELECT col1,` is
     /// recognised as being inside a SELECT clause).
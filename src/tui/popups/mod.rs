@@ -1,4 +1,5 @@
 pub mod column_caster;
+pub mod column_hider;
 pub mod command_palette;
 pub mod data_frame_info;
 pub mod download_notif;
@@ -13,9 +14,14 @@ pub mod importer;
 pub mod importers;
 pub mod inline_query_picker;
 pub mod multi_step_overlay;
+pub mod plan_viewer;
 pub mod scatter_plot_builder;
+pub mod script_runner;
+pub mod snippet_runner;
+pub mod sql_editor;
 pub mod sql_query_picker;
 pub mod table_registerer;
+pub mod table_renamer;
 pub mod theme_selector;
 pub mod url_picker;
 pub mod yes_no_picker;
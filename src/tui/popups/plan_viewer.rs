@@ -0,0 +1,305 @@
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use itertools::Itertools;
+use ratatui::{
+    layout::{Constraint, Layout},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Widget},
+};
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, IntoStaticStr};
+
+use crate::{
+    handler::message::Message,
+    misc::{
+        config::theme,
+        query_plan::{PlanNode, QueryPlan, subtree_end},
+    },
+    tui::{component::Component, widgets::block::Block},
+};
+
+/// Width of the bars comparing the time spent in each node.
+const BAR_WIDTH: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoStaticStr, EnumIter)]
+enum PlanView {
+    Optimized,
+    Unoptimized,
+    Profile,
+}
+
+/// Full screen view of the plans of an `EXPLAIN`, with foldable nodes.
+#[derive(Debug)]
+pub struct PlanViewer {
+    plan: QueryPlan,
+    view: PlanView,
+    folded: HashSet<usize>,
+    selected: usize,
+    scroll: usize,
+    page: usize,
+}
+
+impl PlanViewer {
+    pub fn new(plan: QueryPlan) -> Self {
+        let view = if plan.timings().is_some() {
+            PlanView::Profile
+        } else {
+            PlanView::Optimized
+        };
+        Self {
+            plan,
+            view,
+            folded: HashSet::new(),
+            selected: 0,
+            scroll: 0,
+            page: 1,
+        }
+    }
+
+    fn nodes(&self) -> &[PlanNode] {
+        match self.view {
+            PlanView::Optimized => self.plan.optimized(),
+            PlanView::Unoptimized => self.plan.unoptimized(),
+            PlanView::Profile => &[],
+        }
+    }
+
+    /// Indexes of the nodes outside folded subtrees, or of the timings when profiling.
+    fn rows(&self) -> Vec<usize> {
+        if self.view == PlanView::Profile {
+            return (0..self.plan.timings().map_or(0, <[_]>::len)).collect();
+        }
+        let nodes = self.nodes();
+        let mut rows = Vec::new();
+        let mut idx = 0;
+        while idx < nodes.len() {
+            rows.push(idx);
+            idx = if self.folded.contains(&idx) {
+                subtree_end(nodes, idx)
+            } else {
+                idx + 1
+            };
+        }
+        rows
+    }
+
+    fn has_children(&self, idx: usize) -> bool {
+        subtree_end(self.nodes(), idx) > idx + 1
+    }
+
+    fn switch_view(&mut self, forward: bool) {
+        let views = PlanView::iter().collect_vec();
+        let idx = views
+            .iter()
+            .position(|view| *view == self.view)
+            .unwrap_or(0);
+        self.view = if forward {
+            views[(idx + 1) % views.len()]
+        } else {
+            views[(idx + views.len() - 1) % views.len()]
+        };
+        self.folded.clear();
+        self.selected = 0;
+        self.scroll = 0;
+    }
+
+    fn select_row(&mut self, row: usize) {
+        let rows = self.rows();
+        if let Some(idx) = rows.get(row.min(rows.len().saturating_sub(1))) {
+            self.selected = *idx;
+        }
+    }
+
+    fn selected_row(&self) -> usize {
+        self.rows()
+            .iter()
+            .position(|idx| *idx == self.selected)
+            .unwrap_or_default()
+    }
+
+    /// Folds the selected node, or selects its parent when it is already folded or a leaf.
+    fn fold(&mut self) {
+        if self.view == PlanView::Profile {
+            return;
+        }
+        if self.has_children(self.selected) && self.folded.insert(self.selected) {
+            return;
+        }
+        let depth = self.nodes()[self.selected].depth();
+        if let Some(parent) = self.nodes()[..self.selected]
+            .iter()
+            .rposition(|node| node.depth() < depth)
+        {
+            self.selected = parent;
+        }
+    }
+
+    fn toggle_fold(&mut self) {
+        if self.view != PlanView::Profile
+            && self.has_children(self.selected)
+            && !self.folded.remove(&self.selected)
+        {
+            self.folded.insert(self.selected);
+        }
+    }
+
+    fn plan_line(&self, idx: usize) -> Line<'_> {
+        let node = &self.nodes()[idx];
+        let marker = match (self.has_children(idx), self.folded.contains(&idx)) {
+            (false, _) => "  ",
+            (true, false) => "▾ ",
+            (true, true) => "▸ ",
+        };
+        let (head, tail) = node
+            .text()
+            .split_once(' ')
+            .map_or((node.text(), ""), |(head, tail)| (head, tail));
+        Line::from(vec![
+            Span::raw("  ".repeat(node.depth())),
+            Span::styled(marker, theme().subtext()),
+            Span::styled(head, theme().header(node.depth() % 6)),
+            Span::raw(if tail.is_empty() { "" } else { " " }),
+            Span::styled(tail, theme().text()),
+        ])
+    }
+
+    fn timing_line(&self, idx: usize) -> Line<'_> {
+        let timings = self.plan.timings().unwrap_or_default();
+        let longest = timings
+            .iter()
+            .map(|timing| timing.duration())
+            .max()
+            .unwrap_or_default()
+            .as_secs_f64()
+            .max(f64::EPSILON);
+        let timing = &timings[idx];
+        let filled =
+            (timing.duration().as_secs_f64() / longest * BAR_WIDTH as f64).round() as usize;
+        Line::from(vec![
+            Span::styled(
+                format!("{:>11.3} ms ", timing.duration().as_secs_f64() * 1000.0),
+                theme().text(),
+            ),
+            Span::styled("█".repeat(filled), theme().header(0)),
+            Span::styled(
+                format!("{} ", "░".repeat(BAR_WIDTH - filled)),
+                theme().subtext(),
+            ),
+            Span::styled(
+                format!(
+                    "{:>9.3} → {:<9.3} ",
+                    timing.start().as_secs_f64() * 1000.0,
+                    timing.end().as_secs_f64() * 1000.0
+                ),
+                theme().subtext(),
+            ),
+            Span::styled(timing.node(), theme().text()),
+        ])
+    }
+}
+
+impl Component for PlanViewer {
+    fn render(
+        &mut self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        _focus_state: crate::tui::component::FocusState,
+    ) {
+        Clear.render(area, buf);
+        let block = Block::default()
+            .title("Query Plan")
+            .bottom(" Tab View │ Enter Fold │ Esc Close ");
+        let inner = block.inner(area);
+        block.into_widget().render(area, buf);
+        let [tabs_area, rows_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+
+        Line::from(
+            PlanView::iter()
+                .map(|view| {
+                    let name: &'static str = view.into();
+                    if view == self.view {
+                        Span::styled(format!(" {name} "), theme().row_highlighted())
+                    } else {
+                        Span::styled(format!(" {name} "), theme().subtext())
+                    }
+                })
+                .collect_vec(),
+        )
+        .render(tabs_area, buf);
+
+        if self.view == PlanView::Profile && self.plan.timings().is_none() {
+            Paragraph::new("Run the query with EXPLAIN ANALYZE to time each node")
+                .style(theme().subtext())
+                .render(rows_area, buf);
+            return;
+        }
+
+        let rows = self.rows();
+        let height = (rows_area.height as usize).max(1);
+        let selected = self.selected_row();
+        self.page = height;
+        if selected < self.scroll {
+            self.scroll = selected;
+        } else if selected >= self.scroll + height {
+            self.scroll = selected + 1 - height;
+        }
+        let lines = rows
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(height)
+            .map(|(row, idx)| {
+                let line = match self.view {
+                    PlanView::Profile => self.timing_line(*idx),
+                    _ => self.plan_line(*idx),
+                };
+                if row == selected {
+                    line.style(theme().row_highlighted())
+                } else {
+                    line
+                }
+            })
+            .collect_vec();
+        Paragraph::new(lines)
+            .style(theme().text())
+            .render(rows_area, buf);
+    }
+
+    fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        let row = self.selected_row();
+        match (event.code, event.modifiers) {
+            (KeyCode::Esc, KeyModifiers::NONE) | (KeyCode::Char('q'), KeyModifiers::NONE) => {
+                Message::AppDismissOverlay.enqueue()
+            }
+            (KeyCode::Tab, KeyModifiers::NONE) => self.switch_view(true),
+            (KeyCode::BackTab, _) => self.switch_view(false),
+            (KeyCode::Up, KeyModifiers::NONE) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
+                self.select_row(row.saturating_sub(1))
+            }
+            (KeyCode::Down, KeyModifiers::NONE) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
+                self.select_row(row + 1)
+            }
+            (KeyCode::PageUp, KeyModifiers::NONE) => self.select_row(row.saturating_sub(self.page)),
+            (KeyCode::PageDown, KeyModifiers::NONE) => self.select_row(row + self.page),
+            (KeyCode::Home, KeyModifiers::NONE) | (KeyCode::Char('g'), KeyModifiers::NONE) => {
+                self.select_row(0)
+            }
+            (KeyCode::End, KeyModifiers::NONE) | (KeyCode::Char('G'), KeyModifiers::SHIFT) => {
+                self.select_row(usize::MAX)
+            }
+            (KeyCode::Enter, KeyModifiers::NONE) | (KeyCode::Char(' '), KeyModifiers::NONE) => {
+                self.toggle_fold()
+            }
+            (KeyCode::Left, KeyModifiers::NONE) | (KeyCode::Char('h'), KeyModifiers::NONE) => {
+                self.fold()
+            }
+            (KeyCode::Right, KeyModifiers::NONE) | (KeyCode::Char('l'), KeyModifiers::NONE) => {
+                self.folded.remove(&self.selected);
+            }
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,68 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use polars::frame::DataFrame;
+
+use crate::{
+    handler::message::Message,
+    misc::sql_script::SqlScript,
+    tui::{component::Component, popups::file_picker::FilePicker},
+};
+
+#[derive(Debug)]
+pub struct ScriptRunner {
+    df: Option<DataFrame>,
+    picker: FilePicker,
+}
+
+impl ScriptRunner {
+    pub fn new(df: Option<DataFrame>) -> Self {
+        Self {
+            df,
+            picker: FilePicker::default().with_title("SQL Script"),
+        }
+    }
+
+    fn run(&self) {
+        let path = self.picker.path();
+        let script = match std::fs::read_to_string(&path) {
+            Ok(script) => script,
+            Err(err) => {
+                Message::AppShowError(format!("Unable to read '{}': {err}", path.display()))
+                    .enqueue();
+                return;
+            }
+        };
+        Message::TabsRunScript(Box::new(SqlScript::new(
+            path.file_name().unwrap_or_default().to_string_lossy(),
+            &script,
+            self.df.clone(),
+        )))
+        .enqueue();
+    }
+}
+
+impl Component for ScriptRunner {
+    fn render(
+        &mut self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        focus_state: crate::tui::component::FocusState,
+    ) {
+        self.picker.render(area, buf, focus_state);
+    }
+
+    fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        self.picker.handle(event)
+            || match (event.code, event.modifiers) {
+                (KeyCode::Enter, KeyModifiers::NONE) => {
+                    Message::AppDismissOverlay.enqueue();
+                    self.run();
+                    true
+                }
+                (KeyCode::Esc, KeyModifiers::NONE) => {
+                    Message::AppDismissOverlay.enqueue();
+                    true
+                }
+                _ => false,
+            }
+    }
+}
@@ -0,0 +1,108 @@
+use polars::frame::DataFrame;
+
+use crate::{
+    handler::message::Message,
+    misc::{
+        query_job::{PlannedQuery, QueryTarget},
+        snippets::Snippet,
+    },
+    tui::{
+        component::Component,
+        pickers::{search_picker::SearchPicker, text_picker::TextPicker},
+        popups::multi_step_overlay::{MultiStepOverlay, OverlayStep},
+    },
+};
+
+pub type SnippetRunner = MultiStepOverlay<State>;
+
+#[derive(Debug)]
+pub enum State {
+    PickSnippet {
+        df: Option<DataFrame>,
+        picker: SearchPicker<Snippet>,
+    },
+    PickParam {
+        df: Option<DataFrame>,
+        snippet: Snippet,
+        values: Vec<String>,
+        picker: TextPicker,
+    },
+}
+
+impl State {
+    pub fn new(snippets: Vec<Snippet>, df: Option<DataFrame>) -> Self {
+        State::PickSnippet {
+            df,
+            picker: SearchPicker::new(snippets).with_title("Snippet"),
+        }
+    }
+
+    /// Asks for the next parameter, or runs the snippet once every parameter has a value.
+    fn pick_param_or_run(df: Option<DataFrame>, snippet: Snippet, values: Vec<String>) -> Self {
+        match snippet.params().get(values.len()) {
+            Some(param) => State::PickParam {
+                picker: TextPicker::default().with_title(format!(":{param}")),
+                df,
+                snippet,
+                values,
+            },
+            None => {
+                let params = snippet.params();
+                let sql = snippet.render(|name| {
+                    params
+                        .iter()
+                        .position(|param| *param == name)
+                        .and_then(|idx| values.get(idx))
+                        .map(String::as_str)
+                });
+                match PlannedQuery::new(&sql, df.clone(), QueryTarget::Tab(sql.clone())) {
+                    Ok(query) => {
+                        Message::AppDismissOverlay.enqueue();
+                        Message::TabsRunQuery(Box::new(query)).enqueue();
+                    }
+                    Err(err) => {
+                        Message::AppDismissOverlay.enqueue();
+                        Message::AppShowError(err.to_string()).enqueue();
+                    }
+                }
+                // the overlay is dismissed, this state is never shown
+                State::PickSnippet {
+                    picker: SearchPicker::new(vec![snippet]),
+                    df,
+                }
+            }
+        }
+    }
+}
+
+impl OverlayStep for State {
+    fn next(self) -> Self {
+        match self {
+            State::PickSnippet { df, picker } => match picker.selected_item().cloned() {
+                Some(snippet) => State::pick_param_or_run(df, snippet, Vec::new()),
+                None => State::PickSnippet { df, picker },
+            },
+            State::PickParam {
+                df,
+                snippet,
+                mut values,
+                picker,
+            } => {
+                values.push(picker.value().to_owned());
+                State::pick_param_or_run(df, snippet, values)
+            }
+        }
+    }
+
+    fn responder(&mut self) -> &mut dyn Component {
+        match self {
+            State::PickSnippet { df: _, picker } => picker,
+            State::PickParam {
+                df: _,
+                snippet: _,
+                values: _,
+                picker,
+            } => picker,
+        }
+    }
+}
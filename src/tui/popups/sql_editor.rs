@@ -0,0 +1,548 @@
+use std::{iter::once, sync::LazyLock};
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use itertools::Itertools;
+use polars::frame::DataFrame;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Clear, List, ListItem, ListState, Paragraph, StatefulWidget, Widget, Wrap},
+};
+use regex::Regex;
+use sqlparser::{
+    dialect::GenericDialect,
+    keywords::Keyword,
+    tokenizer::{Location, Token, Tokenizer, Whitespace},
+};
+
+use crate::{
+    handler::message::Message,
+    misc::{
+        config::theme,
+        history::{HistoryKind, remember},
+        query_job::{PlannedQuery, QueryTarget},
+    },
+    sql_completion::{self, SqlSuggestion},
+    tui::{
+        component::Component,
+        pickers::text_picker_with_suggestion::Suggestion,
+        widgets::{block::Block, text_area::TextArea},
+    },
+};
+
+/// Rows of the completion popup.
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Full screen editor for queries spanning several lines.
+#[derive(Debug)]
+pub struct SqlEditor {
+    text: TextArea,
+    dataframe: Option<DataFrame>,
+    all_columns: Vec<String>,
+    suggestions: Vec<SqlSuggestion>,
+    list: ListState,
+    error: Option<QueryError>,
+    scroll: (usize, usize),
+    page: usize,
+}
+
+/// Error of the last run, with the character it points at when known.
+#[derive(Debug)]
+struct QueryError {
+    message: String,
+    offset: Option<usize>,
+}
+
+impl SqlEditor {
+    pub fn new(query: &str, dataframe: Option<DataFrame>) -> Self {
+        Self {
+            text: TextArea::new(query),
+            all_columns: sql_completion::collect_all_columns(dataframe.as_ref()),
+            dataframe,
+            suggestions: Vec::new(),
+            list: ListState::default().with_selected(Some(0)),
+            error: None,
+            scroll: (0, 0),
+            page: 1,
+        }
+    }
+
+    fn edited(&mut self) {
+        self.error = None;
+        self.suggestions = sql_completion::suggestions(
+            &self.text.value(),
+            self.text.cursor(),
+            "",
+            &self.all_columns,
+            self.dataframe.as_ref(),
+        );
+        self.list.select(Some(0));
+    }
+
+    fn complete(&mut self) {
+        if let Some(suggestion) = self
+            .list
+            .selected()
+            .and_then(|idx| self.suggestions.get(idx))
+        {
+            suggestion.complete(&mut self.text);
+        }
+        self.suggestions.clear();
+    }
+
+    fn run(&mut self) {
+        let query = self.text.value();
+        if query.trim().is_empty() {
+            return;
+        }
+        match PlannedQuery::new(
+            &query,
+            self.dataframe.clone(),
+            QueryTarget::Tab(query.split_whitespace().join(" ")),
+        ) {
+            Ok(planned) => {
+                remember(HistoryKind::Sql, &query);
+                Message::AppDismissOverlay.enqueue();
+                Message::TabsRunQuery(Box::new(planned)).enqueue();
+            }
+            Err(err) => {
+                let message = err.to_string();
+                self.error = Some(QueryError {
+                    offset: error_offset(&query, &message),
+                    message,
+                });
+                self.suggestions.clear();
+            }
+        }
+    }
+
+    /// Scrolls so that the cursor stays within the given text area.
+    fn scroll_to_cursor(&mut self, width: usize, height: usize) {
+        let (row, col) = self.text.position();
+        let (top, left) = &mut self.scroll;
+        if row < *top {
+            *top = row;
+        } else if row >= *top + height {
+            *top = row + 1 - height;
+        }
+        if col < *left {
+            *left = col;
+        } else if col >= *left + width {
+            *left = col + 1 - width;
+        }
+    }
+
+    /// Per character styles, from the tokens, the matching brackets and the error.
+    fn styles(&self, value: &str) -> Vec<Style> {
+        let mut styles = highlight(value);
+        if let Some((bracket, other)) = self.text.matching_brackets() {
+            for idx in [bracket, other] {
+                if let Some(style) = styles.get_mut(idx) {
+                    *style = theme().row_highlighted();
+                }
+            }
+        }
+        if let Some(offset) = self.error.as_ref().and_then(|error| error.offset) {
+            let len = value
+                .chars()
+                .skip(offset)
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .count()
+                .max(1);
+            for style in styles.iter_mut().skip(offset).take(len) {
+                *style = style.patch(error_marker());
+            }
+        }
+        styles
+    }
+}
+
+impl Component for SqlEditor {
+    fn render(
+        &mut self,
+        area: Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        focus_state: crate::tui::component::FocusState,
+    ) {
+        Clear.render(area, buf);
+        let block = Block::default()
+            .title("SQL Editor")
+            .bottom(Line::from(" Ctrl+S Run │ Ctrl+Space Suggest │ Esc Close "));
+        let inner = block.inner(area);
+        block.into_widget().render(area, buf);
+
+        let error_height = if self.error.is_some() { 3 } else { 0 };
+        let [text_area, error_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(error_height)]).areas(inner);
+
+        let gutter = self.text.lines().len().to_string().len() + 1;
+        let width = (text_area.width as usize).saturating_sub(gutter + 1).max(1);
+        let height = (text_area.height as usize).max(1);
+        self.page = height;
+        self.scroll_to_cursor(width, height);
+
+        let value = self.text.value();
+        let mut styles = self.styles(&value).into_iter();
+        let mut rows = Vec::new();
+        for (row, line) in self.text.lines().iter().enumerate() {
+            let line_styles = styles.by_ref().take(line.chars().count()).collect_vec();
+            styles.next(); // the line break
+            if row < self.scroll.0 || row >= self.scroll.0 + height {
+                continue;
+            }
+            let spans = line
+                .chars()
+                .zip(line_styles)
+                .skip(self.scroll.1)
+                .chunk_by(|(_, style)| *style)
+                .into_iter()
+                .map(|(style, chars)| {
+                    Span::styled(chars.map(|(c, _)| c).collect::<String>(), style)
+                })
+                .collect_vec();
+            rows.push(Line::from(
+                once(Span::styled(
+                    format!("{:>width$} ", row + 1, width = gutter - 1),
+                    theme().subtext(),
+                ))
+                .chain(spans)
+                .collect_vec(),
+            ));
+        }
+        Paragraph::new(rows)
+            .style(theme().text())
+            .render(text_area, buf);
+
+        let cell = |(row, col): (usize, usize)| {
+            (row >= self.scroll.0 && row < self.scroll.0 + height && col >= self.scroll.1).then(
+                || Rect {
+                    x: text_area.x + (gutter + col - self.scroll.1) as u16,
+                    y: text_area.y + (row - self.scroll.0) as u16,
+                    width: 1,
+                    height: 1,
+                },
+            )
+        };
+
+        // the error may point past the end of a line, where no character carries the marker
+        if let Some(rect) = self
+            .error
+            .as_ref()
+            .and_then(|error| error.offset)
+            .map(|offset| self.text.offset_position(offset))
+            .and_then(cell)
+            .filter(|rect| text_area.contains(rect.as_position()))
+        {
+            buf.set_style(rect, error_marker());
+        }
+
+        let cursor =
+            cell(self.text.position()).filter(|rect| text_area.contains(rect.as_position()));
+        if let Some(cursor) = cursor
+            && focus_state.is_focused()
+        {
+            buf.set_style(cursor, theme().text().add_modifier(Modifier::REVERSED));
+        }
+
+        if let Some(error) = &self.error {
+            Paragraph::new(error.message.as_str())
+                .style(theme().error())
+                .wrap(Wrap { trim: true })
+                .render(error_area, buf);
+        }
+
+        if let Some(cursor) = cursor {
+            // the signature hint sits right next to the cursor, with the suggestions beyond it
+            let hint = sql_completion::signature_hint(&value, self.text.cursor(), "")
+                .map(|hint| hint.line());
+            let hint_height = hint.is_some() as u16;
+            let space_below = text_area.bottom().saturating_sub(cursor.y + 1);
+            let space_above = cursor.y.saturating_sub(text_area.y);
+            let wanted = match self.suggestions.len() {
+                0 => 0,
+                len => len.min(MAX_SUGGESTIONS) as u16 + 2,
+            };
+            let below = hint_height + wanted <= space_below || space_below >= space_above;
+            let height = wanted
+                .min(if below { space_below } else { space_above }.saturating_sub(hint_height));
+            let top = if below {
+                cursor.y + 1
+            } else {
+                cursor.y.saturating_sub(hint_height + height)
+            };
+
+            if let Some(hint) = hint {
+                let width = (hint.width() as u16).min(text_area.width);
+                let rect = Rect {
+                    x: cursor.x.min(text_area.right().saturating_sub(width)),
+                    y: if below { top } else { top + height },
+                    width,
+                    height: 1,
+                }
+                .intersection(area);
+                Clear.render(rect, buf);
+                hint.style(theme().text()).render(rect, buf);
+            }
+
+            if height > 2 {
+                let width = self
+                    .suggestions
+                    .iter()
+                    .map(|suggestion| suggestion.title().chars().count())
+                    .max()
+                    .unwrap_or_default()
+                    .clamp(12, 60) as u16
+                    + 2;
+                let popup = Rect {
+                    x: cursor.x.min(area.right().saturating_sub(width)),
+                    y: if below { top + hint_height } else { top },
+                    width,
+                    height,
+                }
+                .intersection(area);
+                Clear.render(popup, buf);
+                StatefulWidget::render(
+                    List::new(
+                        self.suggestions
+                            .iter()
+                            .map(|suggestion| ListItem::new(suggestion.title())),
+                    )
+                    .style(theme().text())
+                    .highlight_style(theme().row_highlighted())
+                    .block(Block::default().into_widget()),
+                    popup,
+                    buf,
+                    &mut self.list,
+                );
+            }
+        }
+    }
+
+    fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        let popup = !self.suggestions.is_empty();
+        match (event.code, event.modifiers) {
+            (KeyCode::Esc, KeyModifiers::NONE) if popup => self.suggestions.clear(),
+            (KeyCode::Esc, KeyModifiers::NONE) => Message::AppDismissOverlay.enqueue(),
+            (KeyCode::Up, KeyModifiers::NONE) if popup => {
+                if self.list.selected() == Some(0) {
+                    self.list.select(Some(self.suggestions.len() - 1));
+                } else {
+                    self.list.select_previous();
+                }
+            }
+            (KeyCode::Down, KeyModifiers::NONE) if popup => {
+                if self.list.selected() == Some(self.suggestions.len() - 1) {
+                    self.list.select_first();
+                } else {
+                    self.list.select_next();
+                }
+            }
+            (KeyCode::Tab, KeyModifiers::NONE) | (KeyCode::Enter, KeyModifiers::NONE) if popup => {
+                self.complete()
+            }
+            (KeyCode::Char('s'), KeyModifiers::CONTROL)
+            | (KeyCode::F(5), KeyModifiers::NONE)
+            | (KeyCode::Enter, KeyModifiers::ALT) => self.run(),
+            (KeyCode::Char(' '), KeyModifiers::CONTROL) => self.edited(),
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                self.text.newline();
+                self.edited();
+            }
+            (KeyCode::Tab, KeyModifiers::NONE) => {
+                self.text.indent();
+                self.edited();
+            }
+            (KeyCode::BackTab, _) => {
+                self.text.dedent();
+                self.edited();
+            }
+            (KeyCode::Backspace, KeyModifiers::NONE) => {
+                self.text.delete_prev();
+                self.edited();
+            }
+            (KeyCode::Delete, KeyModifiers::NONE) => {
+                self.text.delete_next();
+                self.edited();
+            }
+            (KeyCode::Char(c), KeyModifiers::NONE) | (KeyCode::Char(c), KeyModifiers::SHIFT) => {
+                self.text.insert(c);
+                self.edited();
+            }
+            (code, KeyModifiers::NONE) => {
+                match code {
+                    KeyCode::Left => self.text.move_left(),
+                    KeyCode::Right => self.text.move_right(),
+                    KeyCode::Up => self.text.move_up(1),
+                    KeyCode::Down => self.text.move_down(1),
+                    KeyCode::Home => self.text.move_home(),
+                    KeyCode::End => self.text.move_end(),
+                    KeyCode::PageUp => self.text.move_up(self.page),
+                    KeyCode::PageDown => self.text.move_down(self.page),
+                    _ => return false,
+                }
+                self.suggestions.clear();
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+fn error_marker() -> Style {
+    theme()
+        .error()
+        .add_modifier(Modifier::UNDERLINED | Modifier::BOLD)
+}
+
+/// Styles of each character of the query, following the sqlparser tokens.
+fn highlight(value: &str) -> Vec<Style> {
+    let len = value.chars().count();
+    let mut styles = vec![theme().text(); len];
+    let mut tokens = Vec::new();
+    let _ = Tokenizer::new(&GenericDialect {}, value).tokenize_with_location_into_buf(&mut tokens);
+    let line_starts = once(0)
+        .chain(
+            value
+                .chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '\n')
+                .map(|(idx, _)| idx + 1),
+        )
+        .collect_vec();
+    let offset = |location: Location| {
+        line_starts
+            .get((location.line as usize).checked_sub(1)?)
+            .map(|start| start + (location.column as usize).saturating_sub(1))
+    };
+    for (idx, token) in tokens.iter().enumerate() {
+        let Some(start) = offset(token.span.start) else {
+            continue;
+        };
+        let end = tokens
+            .get(idx + 1)
+            .and_then(|next| offset(next.span.start))
+            .unwrap_or(len);
+        let style = token_style(&token.token);
+        for char_style in styles.iter_mut().take(end.min(len)).skip(start) {
+            *char_style = style;
+        }
+    }
+    styles
+}
+
+fn token_style(token: &Token) -> Style {
+    match token {
+        Token::Word(word) if word.quote_style.is_some() => theme().header(4).not_bold(),
+        Token::Word(word) if word.keyword != Keyword::NoKeyword => theme().header(0),
+        Token::Number(_, _) => theme().header(3).not_bold(),
+        Token::SingleQuotedString(_)
+        | Token::DoubleQuotedString(_)
+        | Token::NationalStringLiteral(_)
+        | Token::EscapedStringLiteral(_)
+        | Token::HexStringLiteral(_) => theme().header(2).not_bold(),
+        Token::Placeholder(_) => theme().header(5).not_bold(),
+        Token::Whitespace(
+            Whitespace::SingleLineComment { .. } | Whitespace::MultiLineComment(_),
+        ) => theme().subtext(),
+        _ => theme().text(),
+    }
+}
+
+/// Character the error points at, from the line and column of parser errors or the first
+/// quoted name found exactly once in the query as a whole identifier.
+fn error_offset(query: &str, message: &str) -> Option<usize> {
+    static LOCATION: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"Line: (\d+), Column: (\d+)").unwrap());
+    static QUOTED: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"["'`]([^"'`]+)["'`]"#).unwrap());
+
+    if let Some(captures) = LOCATION.captures(message) {
+        let line = captures[1].parse::<usize>().ok()?.checked_sub(1)?;
+        let column = captures[2].parse::<usize>().ok()?.saturating_sub(1);
+        let start = query
+            .split('\n')
+            .take(line)
+            .map(|line| line.chars().count() + 1)
+            .sum::<usize>();
+        return Some(start + column);
+    }
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+    QUOTED.captures_iter(message).find_map(|captures| {
+        let name = &captures[1];
+        let mut matches = query.match_indices(name).filter(|(idx, _)| {
+            !query[..*idx].chars().next_back().is_some_and(is_identifier)
+                && !query[idx + name.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(is_identifier)
+        });
+        let (idx, _) = matches.next()?;
+        matches
+            .next()
+            .is_none()
+            .then(|| query[..idx].chars().count())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight() {
+        let query = "SELECT a, 'x' -- note\nFROM \"t\" WHERE b > 10";
+        let styles = highlight(query);
+        assert_eq!(styles.len(), query.chars().count());
+        let style_at = |text: &str| styles[query.find(text).unwrap()];
+        assert_eq!(style_at("SELECT"), theme().header(0));
+        assert_eq!(style_at("FROM"), theme().header(0));
+        assert_eq!(style_at("a,"), theme().text());
+        assert_eq!(style_at("'x'"), theme().header(2).not_bold());
+        assert_eq!(style_at("-- note"), theme().subtext());
+        assert_eq!(style_at("\"t\""), theme().header(4).not_bold());
+        assert_eq!(style_at("10"), theme().header(3).not_bold());
+        assert!(highlight("").is_empty());
+    }
+
+    #[test]
+    fn test_error_offset() {
+        let query = "SELECT name,\n  a FROM t";
+        assert_eq!(
+            error_offset(
+                query,
+                "sql parser error: Expected end at Line: 2, Column: 3"
+            ),
+            Some(15)
+        );
+        // not within 'name' or 'SELECT'
+        assert_eq!(error_offset(query, "column \"a\" not found"), Some(15));
+        assert_eq!(error_offset(query, "relation 't' was not found"), Some(22));
+        assert_eq!(
+            error_offset("SELECT x, x FROM t", "column 'x' is ambiguous"),
+            None
+        );
+        assert_eq!(error_offset(query, "column 'c' not found"), None);
+    }
+
+    #[test]
+    fn test_scroll_to_cursor() {
+        let lines = (0..20).map(|idx| format!("{idx:>3}")).join("\n");
+        let mut editor = SqlEditor::new(&lines, None);
+        editor.scroll_to_cursor(10, 5);
+        assert_eq!(editor.scroll, (15, 0));
+
+        editor.text.move_up(17);
+        editor.scroll_to_cursor(10, 5);
+        assert_eq!(editor.scroll, (2, 0));
+
+        editor.text.insert_str(&"x".repeat(12));
+        editor.scroll_to_cursor(10, 5);
+        assert_eq!(editor.text.position(), (2, 15));
+        assert_eq!(editor.scroll, (2, 6));
+
+        editor.text.move_home();
+        editor.scroll_to_cursor(10, 5);
+        assert_eq!(editor.scroll, (2, 0));
+    }
+}
@@ -1,9 +1,13 @@
 use crossterm::event::{KeyCode, KeyModifiers};
 use polars::frame::DataFrame;
+use ratatui::text::Line;
 
 use crate::{
     handler::message::Message,
-    misc::{history::History, sql::sql},
+    misc::{
+        history::{HistoryKind, history, remember},
+        query_job::{PlannedQuery, QueryTarget},
+    },
     sql_completion::{self, SqlSuggestion},
     tui::{
         component::Component,
@@ -11,8 +15,6 @@ use crate::{
     },
 };
 
-static HISTORY: History<HistoryOrSqlSuggestion> = History::<HistoryOrSqlSuggestion>::new(24);
-
 #[derive(Debug)]
 pub struct SqlQueryPicker {
     picker: TextPickerWithSuggestion<SqlQueryProvider>,
@@ -22,13 +24,17 @@ pub struct SqlQueryPicker {
 impl SqlQueryPicker {
     pub fn new(dataframe: Option<DataFrame>) -> Self {
         let all_columns = sql_completion::collect_all_columns(dataframe.as_ref());
+        let history = history().entries(HistoryKind::Sql).to_vec();
         let provider = SqlQueryProvider {
             dataframe: dataframe.clone(),
             all_columns,
-            history: HISTORY.to_vec(),
+            history: history
+                .iter()
+                .map(|text| HistoryOrSqlSuggestion::History { text: text.clone() })
+                .collect(),
         };
         Self {
-            picker: TextPickerWithSuggestion::new("SQL", provider),
+            picker: TextPickerWithSuggestion::new("SQL", provider).with_history(history),
             dataframe,
         }
     }
@@ -56,26 +62,26 @@ impl Component for SqlQueryPicker {
                         self.picker.apply_selected_suggestion();
                     } else {
                         let value = self.picker.value();
-                        match sql().execute(value, self.dataframe.clone()) {
-                            Ok(df) => {
-                                if df.columns().is_empty() {
-                                    Message::AppShowToast(
-                                        "The query results in an empty data frame".to_owned(),
-                                    )
-                                    .enqueue()
-                                } else {
-                                    Message::AppDismissOverlay.enqueue();
-                                    Message::TabsAddQueryPane(df, value.to_owned()).enqueue();
-                                    HISTORY.push(HistoryOrSqlSuggestion::History {
-                                        text: value.to_owned(),
-                                    });
-                                }
+                        match PlannedQuery::new(
+                            value,
+                            self.dataframe.clone(),
+                            QueryTarget::Tab(value.to_owned()),
+                        ) {
+                            Ok(query) => {
+                                Message::AppDismissOverlay.enqueue();
+                                Message::TabsRunQuery(Box::new(query)).enqueue();
+                                remember(HistoryKind::Sql, value);
                             }
                             Err(error) => Message::AppShowToast(error.to_string()).enqueue(),
                         }
                     }
                     true
                 }
+                (KeyCode::Char('o'), KeyModifiers::CONTROL) => {
+                    Message::AppDismissOverlay.enqueue();
+                    Message::AppShowSqlEditor(self.picker.value().to_owned()).enqueue();
+                    true
+                }
                 (KeyCode::Esc, KeyModifiers::NONE) => {
                     Message::AppDismissOverlay.enqueue();
                     true
@@ -111,6 +117,10 @@ impl Provider for SqlQueryProvider {
             .collect()
         }
     }
+
+    fn hint(&self, value: &str, cursor: usize) -> Option<Line<'static>> {
+        sql_completion::signature_hint(value, cursor, "").map(|hint| hint.line())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,63 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::{
+    handler::message::Message,
+    misc::sql::sql,
+    tui::{component::Component, pickers::text_picker::TextPicker},
+};
+
+#[derive(Debug)]
+pub struct TableRenamer {
+    name: String,
+    picker: TextPicker,
+}
+
+impl TableRenamer {
+    pub fn new(name: String) -> Self {
+        Self {
+            picker: TextPicker::default()
+                .with_title(format!("Rename '{name}'"))
+                .with_value(name.clone()),
+            name,
+        }
+    }
+
+    fn rename(&self) {
+        let new = self.picker.value().trim();
+        if new == self.name {
+            return;
+        }
+        match sql().rename(&self.name, new) {
+            Ok(()) => Message::AppShowToast(format!("Renamed '{}' to '{new}'", self.name)),
+            Err(err) => Message::AppShowError(err.to_string()),
+        }
+        .enqueue();
+    }
+}
+
+impl Component for TableRenamer {
+    fn render(
+        &mut self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        focus_state: crate::tui::component::FocusState,
+    ) {
+        self.picker.render(area, buf, focus_state);
+    }
+
+    fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
+        self.picker.handle(event)
+            || match (event.code, event.modifiers) {
+                (KeyCode::Enter, KeyModifiers::NONE) => {
+                    Message::AppDismissOverlay.enqueue();
+                    self.rename();
+                    true
+                }
+                (KeyCode::Esc, KeyModifiers::NONE) => {
+                    Message::AppDismissOverlay.enqueue();
+                    true
+                }
+                _ => false,
+            }
+    }
+}
@@ -17,6 +17,11 @@ impl YesNoPicker {
         }
     }
 
+    pub fn with_value(mut self, value: bool) -> Self {
+        self.list_picker.select(if value { 0 } else { 1 });
+        self
+    }
+
     pub fn value(&self) -> Option<bool> {
         self.list_picker.selected_item().map(|yn| match yn {
             YesNo::Yes => true,
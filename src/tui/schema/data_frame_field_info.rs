@@ -1,4 +1,5 @@
 use crossterm::event::{KeyCode, KeyModifiers};
+use itertools::Itertools;
 use ratatui::{
     layout::{Alignment, Constraint},
     text::Text,
@@ -57,12 +58,21 @@ impl Component for DataFrameFieldInfo {
                 .len()
                 .saturating_sub(area.height.saturating_sub(2).into()),
         );
+        let labeled = self
+            .table_schema
+            .iter()
+            .any(|(_, info)| !info.metadata().is_empty());
+        let mut header = vec!["Name", "Type", "Estimated Size", "Null Count", "Min", "Max"];
+        if labeled {
+            header.extend(["Label", "Value Labels"]);
+        }
+        let widths = vec![Constraint::Fill(1); header.len()];
         Widget::render(Clear, area, buf);
         StatefulWidget::render(
             Table::default()
                 .header(
                     Row::new(
-                        ["Name", "Type", "Estimated Size", "Null Count", "Min", "Max"]
+                        header
                             .into_iter()
                             .enumerate()
                             .map(|(i, s)| Text::styled(s, theme().header(i))),
@@ -74,25 +84,28 @@ impl Component for DataFrameFieldInfo {
                         .iter()
                         .enumerate()
                         .map(|(idx, (name, info))| {
-                            Row::new([
+                            let mut cells = vec![
                                 name.to_owned(),
                                 format!("{}", info.dtype()),
                                 human_readable_size(info.estimated_size() as u64),
                                 format!("{}", info.null_count()),
                                 info.min().to_string(),
                                 info.max().to_string(),
-                            ])
-                            .style(theme().row(idx))
+                            ];
+                            if labeled {
+                                cells.push(info.metadata().label().unwrap_or_default().to_owned());
+                                cells.push(
+                                    info.metadata()
+                                        .value_labels()
+                                        .iter()
+                                        .map(|(value, label)| format!("{value}: {label}"))
+                                        .join(", "),
+                                );
+                            }
+                            Row::new(cells).style(theme().row(idx))
                         }),
                 )
-                .widths([
-                    Constraint::Fill(1),
-                    Constraint::Fill(1),
-                    Constraint::Fill(1),
-                    Constraint::Fill(1),
-                    Constraint::Fill(1),
-                    Constraint::Fill(1),
-                ])
+                .widths(widths)
                 .style(theme().text())
                 .block(
                     Block::default()
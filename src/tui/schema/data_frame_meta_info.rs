@@ -14,7 +14,11 @@ use crate::{
         sql::{self, TableInfo},
         type_ext::human_readable_size,
     },
-    tui::{component::Component, widgets::block::Block},
+    tui::{
+        component::Component,
+        tag_line::{Tag, TagLine},
+        widgets::block::Block,
+    },
 };
 
 #[derive(Debug)]
@@ -30,6 +34,12 @@ impl DataFrameMetaInfo {
     pub fn table_info(&self) -> &TableInfo {
         &self.info
     }
+
+    pub fn height(&self) -> u16 {
+        6 + self.info.metadata().version().is_some() as u16
+            + self.info.sql().is_some() as u16
+            + !self.info.is_available() as u16
+    }
 }
 
 impl Component for DataFrameMetaInfo {
@@ -40,31 +50,67 @@ impl Component for DataFrameMetaInfo {
         _focus_state: crate::tui::component::FocusState,
     ) {
         Widget::render(Clear, area, buf);
-        Table::default()
-            .rows([
-                Row::new([
-                    Span::styled("Path", theme().header(0)),
-                    Span::styled(self.info.source().display_path(), theme().text()),
-                ]),
-                Row::new([
-                    Span::styled("Shape", theme().header(1)),
-                    Span::styled(
-                        format!("{} x {}", self.info.height(), self.info.width()),
-                        theme().text(),
-                    ),
-                ]),
-                Row::new([
-                    Span::styled("Total Estimated Memory", theme().header(2)),
-                    Span::styled(
-                        human_readable_size(self.info.total_est_size() as u64),
-                        theme().text(),
-                    ),
-                ]),
-                Row::new([
-                    Span::styled("Total Null Count", theme().header(3)),
-                    Span::styled(self.info.total_null().to_string(), theme().text()),
-                ]),
+        let version = self.info.metadata().version().map(|version| {
+            Row::new([
+                Span::styled("Version", theme().header(4)),
+                Span::styled(version, theme().text()),
             ])
+        });
+        let sql = self.info.sql().map(|sql| {
+            Row::new([
+                Span::styled("SQL", theme().header(5)),
+                Span::styled(sql, theme().text()),
+            ])
+        });
+        let status = (!self.info.is_available()).then(|| {
+            Row::new([
+                Span::styled("Status", theme().error()),
+                Span::styled("Unavailable, a table it reads from is gone", theme().text()),
+            ])
+        });
+        // the rows of a view are only known once it is queried
+        let unknown = |value: String| {
+            if self.info.is_view() {
+                "-".to_owned()
+            } else {
+                value
+            }
+        };
+        Table::default()
+            .rows(
+                [
+                    Row::new([
+                        Span::styled("Path", theme().header(0)),
+                        Span::styled(self.info.source().display_path(), theme().text()),
+                    ]),
+                    Row::new([
+                        Span::styled("Shape", theme().header(1)),
+                        Span::styled(
+                            format!(
+                                "{} x {}",
+                                unknown(self.info.height().to_string()),
+                                self.info.width()
+                            ),
+                            theme().text(),
+                        ),
+                    ]),
+                    Row::new([
+                        Span::styled("Total Estimated Memory", theme().header(2)),
+                        Span::styled(
+                            unknown(human_readable_size(self.info.total_est_size() as u64)),
+                            theme().text(),
+                        ),
+                    ]),
+                    Row::new([
+                        Span::styled("Total Null Count", theme().header(3)),
+                        Span::styled(unknown(self.info.total_null().to_string()), theme().text()),
+                    ]),
+                ]
+                .into_iter()
+                .chain(version)
+                .chain(sql)
+                .chain(status),
+            )
             .widths([Constraint::Max(23), Constraint::Fill(1)])
             .block(
                 Block::default()
@@ -75,6 +121,14 @@ impl Component for DataFrameMetaInfo {
                     })
                     .title_alignment(Alignment::Center)
                     .title("Info")
+                    .bottom(
+                        TagLine::new()
+                            .mono_color()
+                            .centered()
+                            .tag(Tag::new(" Rename ", " r "))
+                            .tag(Tag::new(" Reload ", " Shift+R "))
+                            .tag(Tag::new(" Copy Schema ", " y ")),
+                    )
                     .into_widget(),
             )
             .render(area, buf);
@@ -6,7 +6,9 @@ use ratatui::{
 };
 
 use crate::{
-    misc::{config::theme, sql::sql},
+    handler::message::Message,
+    io::writer::SqlDialect,
+    misc::{config::theme, osc52::CopyToClipboardOsc52, sql::sql},
     tui::{
         component::Component,
         tag_line::{Tag, TagLine},
@@ -24,6 +26,27 @@ impl DataFrameNames {
         self.table.selected()
     }
 
+    fn selected_name(&self) -> Option<String> {
+        self.selected()
+            .and_then(|idx| sql().schema().get_by_index(idx).map(|(s, _)| s.to_owned()))
+    }
+
+    /// Copies a `CREATE TABLE` statement describing the columns of the table.
+    fn copy_schema(&self, name: &str) {
+        let Some(statement) = sql().schema().get(name).map(|info| {
+            SqlDialect::PostgreSql.create_table(
+                name,
+                info.schema()
+                    .iter()
+                    .map(|(column, field)| (column.as_str(), field.dtype())),
+            )
+        }) else {
+            return;
+        };
+        statement.copy_to_clipboard_via_osc52();
+        Message::AppShowToast(format!("Schema of '{name}' copied to clipboard")).enqueue();
+    }
+
     fn select_up(&mut self) {
         self.table.select_previous();
     }
@@ -59,11 +82,15 @@ impl Component for DataFrameNames {
         let num_width = sql().schema().len().to_string().len();
 
         let table = Table::default()
-            .rows(sql().schema().iter().enumerate().map(|(i, (s, _))| {
+            .rows(sql().schema().iter().enumerate().map(|(i, (s, info))| {
                 Row::new([
                     Span::raw(format!(" {:>width$}", i + 1, width = num_width))
                         .style(theme().subtext()),
-                    Span::raw(s.to_owned()).style(theme().text()),
+                    if info.is_available() {
+                        Span::raw(s.to_owned()).style(theme().text())
+                    } else {
+                        Span::raw(format!("{s} (unavailable)")).style(theme().subtext())
+                    },
                 ])
             }))
             .row_highlight_style(theme().row_highlighted())
@@ -115,14 +142,29 @@ impl Component for DataFrameNames {
                 true
             }
             (KeyCode::Delete, KeyModifiers::NONE) => {
-                if let Some(name) = self
-                    .selected()
-                    .and_then(|idx| sql().schema().get_by_index(idx).map(|(s, _)| s.to_owned()))
-                {
+                if let Some(name) = self.selected_name() {
                     sql().unregister(&name);
                 }
                 true
             }
+            (KeyCode::Char('r'), KeyModifiers::NONE) => {
+                if let Some(name) = self.selected_name() {
+                    Message::AppShowTableRenamer(name).enqueue();
+                }
+                true
+            }
+            (KeyCode::Char('R'), KeyModifiers::SHIFT) => {
+                if let Some(name) = self.selected_name() {
+                    Message::AppReloadTable(name).enqueue();
+                }
+                true
+            }
+            (KeyCode::Char('y'), KeyModifiers::NONE) => {
+                if let Some(name) = self.selected_name() {
+                    self.copy_schema(&name);
+                }
+                true
+            }
 
             _ => false,
         }
@@ -1,16 +1,22 @@
-use std::ops::{Add, Div};
+use std::{
+    collections::BTreeSet,
+    ops::{Add, Div},
+};
 
+use anyhow::anyhow;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use itertools::Itertools;
 use polars::frame::DataFrame;
 use ratatui::{
     layout::{Constraint, Layout, Position, Rect},
+    style::Style,
     text::Text,
     widgets::{Cell, List, ListItem, ListState, Row, StatefulWidget, TableState},
 };
 use tui_scrollview::{ScrollView, ScrollViewState, ScrollbarVisibility};
 
 use crate::{
+    AppResult,
     misc::{
         config::theme,
         iter_ext::ZipItersExt,
@@ -32,6 +38,8 @@ pub struct Table {
     offset: usize,
     rendered_rows: usize,
     rendered_width: u16,
+    marked: BTreeSet<usize>,
+    hidden: BTreeSet<usize>,
     column_mode: ColumnMode,
     gutter_mode: GutterMode,
 }
@@ -53,6 +61,8 @@ impl Table {
             offset: 0,
             rendered_rows: 0,
             rendered_width: 0,
+            marked: BTreeSet::new(),
+            hidden: BTreeSet::new(),
             column_mode: ColumnMode::Compact,
             striped: false,
             show_header: false,
@@ -68,7 +78,16 @@ impl Table {
             .into_iter()
             .map(|u| Constraint::Length(u as u16))
             .collect_vec();
-        let col_offsets = col_offsets(&col_widths, self.col_space);
+        let hidden = self
+            .hidden
+            .iter()
+            .filter_map(|idx| self.df.get_column_names().get(*idx).copied())
+            .filter_map(|name| df.get_column_index(name))
+            .collect::<BTreeSet<_>>();
+        let col_offsets = col_offsets(
+            &shown_widths(&col_widths, &hidden).collect_vec(),
+            self.col_space,
+        );
         let gutter_width = df.height().to_string().len() as u16;
         Self {
             df,
@@ -78,6 +97,8 @@ impl Table {
             selected: self.selected,
             rendered_rows: self.rendered_rows,
             rendered_width: self.rendered_width,
+            marked: BTreeSet::new(),
+            hidden,
             column_mode: self.column_mode,
             striped: self.striped,
             show_header: self.show_header,
@@ -150,6 +171,73 @@ impl Table {
     pub fn set_data_frame(&mut self, df: DataFrame) {
         if self.df.schema_equal(&df).is_ok() {
             self.df = df;
+            self.marked.clear();
+        }
+    }
+
+    /// The data frame without its hidden columns.
+    pub fn shown_data_frame(&self) -> DataFrame {
+        if self.hidden.is_empty() {
+            self.df.clone()
+        } else {
+            self.df
+                .select(self.shown_column_names())
+                .expect("shown columns are taken from the data frame")
+        }
+    }
+
+    pub fn shown_column_names(&self) -> Vec<String> {
+        self.df
+            .get_column_names()
+            .into_iter()
+            .enumerate()
+            .filter(|(idx, _)| !self.hidden.contains(idx))
+            .map(|(_, name)| name.to_string())
+            .collect()
+    }
+
+    pub fn hide_column(&mut self, name: &str) -> AppResult<()> {
+        let idx = self
+            .df
+            .get_column_index(name)
+            .ok_or_else(|| anyhow!("Column '{name}' not found"))?;
+        if self.hidden.len() + 1 >= self.df.width() {
+            return Err(anyhow!("The last shown column can not be hidden"));
+        }
+        self.hidden.insert(idx);
+        self.update_col_offsets();
+        Ok(())
+    }
+
+    pub fn unhide_columns(&mut self) {
+        self.hidden.clear();
+        self.update_col_offsets();
+    }
+
+    pub fn marked_rows(&self) -> &BTreeSet<usize> {
+        &self.marked
+    }
+
+    pub fn unmark_rows(&mut self) {
+        self.marked.clear();
+    }
+
+    fn toggle_mark(&mut self) {
+        if let Some(selected) = self.selected {
+            if !self.marked.remove(&selected) {
+                self.marked.insert(selected);
+            }
+            self.select_down();
+        }
+    }
+
+    fn update_col_offsets(&mut self) {
+        self.col_offsets = col_offsets(
+            &shown_widths(&self.col_widths, &self.hidden).collect_vec(),
+            self.col_space,
+        );
+        if let ColumnMode::Expanded(offset) = &mut self.column_mode {
+            *offset = 0;
         }
     }
 
@@ -291,7 +379,8 @@ impl Table {
 
     fn gutter_item(&self, idx: usize) -> ListItem<'_> {
         ListItem::new(Text::raw(format!(
-            "  {:>w$}  ",
+            " {}{:>w$}  ",
+            if self.marked.contains(&idx) { '*' } else { ' ' },
             idx + 1,
             w = self.gutter_mode.width().into()
         )))
@@ -317,7 +406,7 @@ impl Table {
     }
 
     fn minimum_compact_width(&self) -> u16 {
-        let col_count = self.df.width() as u16;
+        let col_count = (self.df.width() - self.hidden.len()) as u16;
         col_count + (col_count.saturating_sub(1) * self.col_space)
     }
 }
@@ -376,15 +465,29 @@ impl Component for Table {
             self.column_mode = ColumnMode::Expanded(0);
         }
 
+        let shown = self.shown_data_frame();
+        let widths = shown_widths(&self.col_widths, &self.hidden).collect_vec();
+        let row_style = |idx: usize| {
+            let style = if self.striped {
+                theme().row(idx)
+            } else {
+                theme().row(0)
+            };
+            if self.marked.contains(&idx) {
+                style.underlined()
+            } else {
+                style
+            }
+        };
         match &mut self.column_mode {
             ColumnMode::Compact => {
-                let df = self.df.slice(self.offset as i64, height);
+                let df = shown.slice(self.offset as i64, height);
                 let table = build_table(
                     &df,
-                    &self.col_widths,
+                    &widths,
                     self.col_space,
                     self.show_header,
-                    self.striped,
+                    row_style,
                     self.offset,
                     0,
                 );
@@ -399,7 +502,7 @@ impl Component for Table {
                 );
             }
             ColumnMode::Expanded(x) => {
-                if self.df.columns().is_empty() {
+                if shown.columns().is_empty() {
                     return;
                 }
                 let total_width = self
@@ -411,17 +514,16 @@ impl Component for Table {
                 *x = (*x).min(total_width.saturating_sub(table_area.width as usize));
                 let col_start = column_index(&self.col_offsets, x);
                 let col_end = column_index(&self.col_offsets, &x.add(table_area.width as usize));
-                let df = self
-                    .df
-                    .select(&self.df.get_column_names()[col_start..=col_end])
+                let df = shown
+                    .select(&shown.get_column_names()[col_start..=col_end])
                     .unwrap()
                     .slice(self.offset as i64, height);
                 let table = build_table(
                     &df,
-                    &self.col_widths[col_start..=col_end],
+                    &widths[col_start..=col_end],
                     self.col_space,
                     self.show_header,
-                    self.striped,
+                    row_style,
                     self.offset,
                     col_start,
                 );
@@ -519,6 +621,10 @@ impl Component for Table {
                 self.scroll_to_last_column();
                 true
             }
+            (KeyCode::Char(' '), KeyModifiers::NONE) => {
+                self.toggle_mark();
+                true
+            }
             _ => false,
         }
     }
@@ -566,6 +672,17 @@ fn col_offsets(col_widths: &[Constraint], col_space: u16) -> Vec<usize> {
         .collect_vec()
 }
 
+fn shown_widths<'a>(
+    col_widths: &'a [Constraint],
+    hidden: &'a BTreeSet<usize>,
+) -> impl Iterator<Item = Constraint> + 'a {
+    col_widths
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !hidden.contains(idx))
+        .map(|(_, width)| *width)
+}
+
 fn column_index(col_offsets: &[usize], offset: &usize) -> usize {
     // col_offsets index: 0    1    2    3    4
     // col_offsets      : 0---10---20---30---40
@@ -595,7 +712,7 @@ fn build_table<'a>(
     col_widths: &[Constraint],
     col_space: u16,
     show_header: bool,
-    striped: bool,
+    row_style: impl Fn(usize) -> Style,
     offset_row: usize,
     offset_col: usize,
 ) -> ratatui::widgets::Table<'a> {
@@ -614,11 +731,7 @@ fn build_table<'a>(
                     let cells = vals
                         .into_iter()
                         .map(|val| Cell::new(val.into_single_line()));
-                    Row::new(cells).style(if striped {
-                        theme().row(offset_row + idx)
-                    } else {
-                        theme().row(0)
-                    })
+                    Row::new(cells).style(row_style(offset_row + idx))
                 }),
         );
 
@@ -633,3 +746,51 @@ fn build_table<'a>(
     }
     table
 }
+
+#[cfg(test)]
+mod tests {
+    use polars::df;
+
+    use super::*;
+
+    #[test]
+    fn test_hidden_columns() {
+        let df = df! {
+            "a" => [1, 2],
+            "b" => ["x", "y"],
+            "c" => [true, false],
+        }
+        .unwrap();
+        let mut table = Table::new(df.clone());
+        table.hide_column("b").unwrap();
+        assert_eq!(table.shown_column_names(), ["a", "c"]);
+        assert_eq!(table.shown_data_frame().get_column_names(), ["a", "c"]);
+        assert!(table.hide_column("d").is_err());
+
+        table.hide_column("a").unwrap();
+        assert!(table.hide_column("c").is_err());
+
+        let pushed = table.clone_with_data_frame(df.select(["b", "c"]).unwrap());
+        assert_eq!(pushed.shown_column_names(), ["c"]);
+
+        table.unhide_columns();
+        assert_eq!(table.shown_column_names(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_marked_rows() {
+        let df = df! { "a" => [1, 2, 3] }.unwrap();
+        let mut table = Table::new(df.clone()).with_selected(0);
+        table.handle(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        table.handle(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert_eq!(table.marked_rows().iter().copied().collect_vec(), [0, 1]);
+        assert_eq!(table.selected(), Some(2));
+
+        table.select(1);
+        table.handle(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert_eq!(table.marked_rows().iter().copied().collect_vec(), [0]);
+
+        table.set_data_frame(df);
+        assert!(table.marked_rows().is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 use crossterm::event::{KeyCode, KeyModifiers};
 
 use ratatui::{
@@ -7,7 +9,14 @@ use ratatui::{
 
 use crate::{
     handler::message::Message,
-    misc::config::config,
+    io::writer::{Destination, WriteToXlsx},
+    misc::{
+        config::config,
+        query_job::{PlannedQuery, QueryJob, QueryTarget},
+        sql::sql,
+        sql_script::{ScriptJob, SqlScript},
+        type_ext::UnwrapOrEnqueueError,
+    },
     tui::{
         component::{Component, FocusState},
         pane::TableDescription,
@@ -22,6 +31,9 @@ pub struct Tabs {
     panes: Vec<Pane>,
     switcher: Option<TabSwitcher>,
     idx: usize,
+    /// Running query, along with the index of the pane it was started from.
+    query: Option<(usize, QueryJob)>,
+    script: Option<ScriptJob>,
 }
 
 impl Tabs {
@@ -32,6 +44,7 @@ impl Tabs {
     fn add(&mut self, tabular: Pane) {
         self.panes.push(tabular);
         self.idx = self.panes.len().saturating_sub(1);
+        self.sync_sql();
     }
 
     fn len(&self) -> usize {
@@ -56,6 +69,17 @@ impl Tabs {
     fn remove_selected(&mut self) {
         if self.idx < self.panes.len() {
             self.panes.remove(self.idx);
+            self.query = self.query.take().and_then(|(origin, job)| {
+                match (origin.cmp(&self.idx), job.target()) {
+                    (Ordering::Greater, _) => Some((origin - 1, job)),
+                    (Ordering::Equal, QueryTarget::Pane(_, _)) => {
+                        job.cancel();
+                        None
+                    }
+                    _ => Some((origin, job)),
+                }
+            });
+            self.sync_sql();
         }
         if self.switcher.is_some() {
             self.show_tab_switcher();
@@ -87,6 +111,119 @@ impl Tabs {
     fn dismiss_tab_switcher(&mut self) {
         self.switcher.take();
     }
+
+    fn run_query(&mut self, query: PlannedQuery) {
+        if let Some((_, job)) = self.query.take() {
+            job.cancel();
+        }
+        self.query = Some((self.idx, query.start()));
+    }
+
+    fn cancel_query(&mut self) {
+        if let Some((_, job)) = self.query.take() {
+            job.cancel();
+            Message::AppShowToast("Query cancelled".to_owned()).enqueue();
+        }
+    }
+
+    fn finish_query(&mut self) {
+        if self.query.as_ref().is_none_or(|(_, job)| job.is_running()) {
+            return;
+        }
+        let Some((origin, (target, result))) = self
+            .query
+            .take()
+            .and_then(|(origin, job)| job.finish().map(|finished| (origin, finished)))
+        else {
+            return;
+        };
+        match (target, result) {
+            (QueryTarget::Tab(_), Ok(df)) if df.columns().is_empty() => {
+                Message::AppShowToast("The query results in an empty data frame".to_owned())
+                    .enqueue();
+            }
+            (QueryTarget::Tab(query), Ok(df)) => {
+                Message::TabsAddQueryPane(df, query).enqueue();
+            }
+            (QueryTarget::Pane(description, toast), Ok(df)) => {
+                if let Some(pane) = self.panes.get_mut(origin) {
+                    pane.push_data_frame(df, description);
+                    Message::AppShowToast(toast).enqueue();
+                }
+                self.sync_sql();
+            }
+            (QueryTarget::Define(definition), Ok(df)) => {
+                Message::AppShowToast(sql().define(definition, df)).enqueue();
+            }
+            (QueryTarget::Explain(plan), Ok(timings)) => {
+                Message::AppShowPlanViewer(Box::new(plan.with_timings(&timings))).enqueue();
+            }
+            (QueryTarget::Applied(summary), Ok(_)) => {
+                Message::AppShowToast(summary).enqueue();
+            }
+            (_, Err(err)) => Message::AppShowError(err.to_string()).enqueue(),
+        }
+    }
+
+    fn run_script(&mut self, script: SqlScript) {
+        if let Some(job) = self.script.take() {
+            job.cancel();
+        }
+        self.script = Some(script.start());
+    }
+
+    fn cancel_script(&mut self) {
+        if let Some(job) = self.script.take() {
+            job.cancel();
+            let (statement, statements) = job.progress();
+            Message::AppShowToast(format!(
+                "Script cancelled at statement {statement} of {statements}"
+            ))
+            .enqueue();
+        }
+    }
+
+    fn finish_script(&mut self) {
+        if self.script.as_ref().is_none_or(ScriptJob::is_running) {
+            return;
+        }
+        let Some(job) = self.script.take() else {
+            return;
+        };
+        let name = job.name().to_owned();
+        match job.finish() {
+            Some(Ok(outcome)) => match outcome.result {
+                Some((query, df)) => Message::TabsAddQueryPane(df, query).enqueue(),
+                None => Message::AppShowToast(format!(
+                    "Ran {} statement(s) from '{name}'",
+                    outcome.statements
+                ))
+                .enqueue(),
+            },
+            Some(Err(err)) => Message::AppShowError(err.to_string()).enqueue(),
+            None => (),
+        }
+    }
+
+    /// Keeps the top frame of every tab queryable by name, called whenever tabs are added or
+    /// removed and panes push or pop frames.
+    fn sync_sql(&self) {
+        sql().set_tabs(
+            self.panes
+                .iter()
+                .map(|pane| (pane.title(), pane.table().data_frame().clone())),
+        );
+    }
+
+    fn status_bar<'a>(&self, pane: &'a Pane) -> StatusBar<'a> {
+        StatusBar::new(pane, self.idx, self.len())
+            .with_query_elapsed(self.query.as_ref().map(|(_, job)| job.elapsed()))
+            .with_script_progress(
+                self.script
+                    .as_ref()
+                    .map(|job| (job.progress(), job.elapsed())),
+            )
+    }
 }
 
 impl Component for Tabs {
@@ -104,8 +241,7 @@ impl Component for Tabs {
                 let new = blk.inner(area);
                 blk.render(area, buf);
                 if let Some(pane) = self.panes.get(self.idx) {
-                    let status_bar = StatusBar::new(pane, self.idx, self.len());
-                    status_bar.render(
+                    self.status_bar(pane).render(
                         Rect {
                             x: area.x + 1,
                             y: area.height.saturating_sub(1),
@@ -121,8 +257,7 @@ impl Component for Tabs {
                     Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
 
                 if let Some(pane) = self.panes.get(self.idx) {
-                    let status_bar = StatusBar::new(pane, self.idx, self.len());
-                    status_bar.render(statusbar_area, buf);
+                    self.status_bar(pane).render(statusbar_area, buf);
                 }
                 pane_area
             }
@@ -147,6 +282,16 @@ impl Component for Tabs {
     fn handle(&mut self, event: crossterm::event::KeyEvent) -> bool {
         if let Some(switcher) = self.switcher.as_mut() {
             switcher.handle(event)
+        } else if self.query.is_some()
+            && (event.code, event.modifiers) == (KeyCode::Esc, KeyModifiers::NONE)
+        {
+            self.cancel_query();
+            true
+        } else if self.script.is_some()
+            && (event.code, event.modifiers) == (KeyCode::Esc, KeyModifiers::NONE)
+        {
+            self.cancel_script();
+            true
         } else {
             self.panes
                 .get_mut(self.idx)
@@ -193,6 +338,27 @@ impl Component for Tabs {
                     TableDescription::Query(query.to_owned()),
                 ));
             }
+            Message::TabsRunQuery(query) => self.run_query(query.as_ref().clone()),
+            Message::TabsRunScript(script) => self.run_script(script.as_ref().clone()),
+            Message::TabsExportXlsx(path) => {
+                WriteToXlsx
+                    .write_sheets(
+                        Destination::File(path.clone()),
+                        self.panes
+                            .iter()
+                            .map(|pane| (pane.title(), pane.table().data_frame())),
+                    )
+                    .unwrap_or_enqueue_error()
+                    .then(|| {
+                        Message::PaneDismissModal.enqueue();
+                        Message::AppShowToast(format!(
+                            "{} tabs exported to '{}' in Excel format",
+                            self.panes.len(),
+                            path.file_name().unwrap_or_default().to_string_lossy()
+                        ))
+                        .enqueue();
+                    });
+            }
             Message::TabsSelect(idx) if focus_state.is_focused() => self.select(*idx),
             Message::TabsDismissSwitcher if focus_state.is_focused() => self.dismiss_tab_switcher(),
             _ => (),
@@ -214,9 +380,21 @@ impl Component for Tabs {
                 );
             }
         }
+        if matches!(
+            action,
+            Message::PanePushDataFrame(..)
+                | Message::PanePopDataFrame
+                | Message::PaneEditInExternalEditor
+                // a search shows its matches on the frame it pushed until it is dismissed
+                | Message::PaneDismissModal
+        ) {
+            self.sync_sql();
+        }
     }
 
     fn tick(&mut self) {
+        self.finish_query();
+        self.finish_script();
         if let Some(switcher) = self.switcher.as_mut() {
             switcher.tick();
         }
@@ -228,10 +406,14 @@ impl Component for Tabs {
 
 impl FromIterator<Pane> for Tabs {
     fn from_iter<T: IntoIterator<Item = Pane>>(iter: T) -> Self {
-        Self {
+        let tabs = Self {
             panes: iter.into_iter().collect(),
             idx: 0,
             switcher: None,
-        }
+            query: None,
+            script: None,
+        };
+        tabs.sync_sql();
+        tabs
     }
 }
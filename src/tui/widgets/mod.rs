@@ -2,3 +2,4 @@ pub mod block;
 pub mod highlighted_line;
 pub mod input;
 pub mod status_bar;
+pub mod text_area;
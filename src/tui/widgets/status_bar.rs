@@ -1,5 +1,5 @@
 use std::default::Default;
-use std::{borrow::Cow, ops::Add};
+use std::{borrow::Cow, ops::Add, time::Duration};
 
 use ratatui::{
     layout::{Constraint, Layout},
@@ -18,6 +18,8 @@ pub struct StatusBar<'a> {
     pane: &'a Pane,
     sel_tab: usize,
     tot_tab: usize,
+    query_elapsed: Option<Duration>,
+    script_progress: Option<((usize, usize), Duration)>,
 }
 
 impl<'a> StatusBar<'a> {
@@ -26,8 +28,22 @@ impl<'a> StatusBar<'a> {
             pane,
             sel_tab: selected_tab,
             tot_tab: total_tabs,
+            query_elapsed: None,
+            script_progress: None,
         }
     }
+
+    /// Shows a spinner with the time spent on a running query.
+    pub fn with_query_elapsed(mut self, elapsed: Option<Duration>) -> Self {
+        self.query_elapsed = elapsed;
+        self
+    }
+
+    /// Shows a spinner with the statement a running script is at and the time spent on it.
+    pub fn with_script_progress(mut self, progress: Option<((usize, usize), Duration)>) -> Self {
+        self.script_progress = progress;
+        self
+    }
 }
 
 impl<'a> Widget for StatusBar<'a> {
@@ -63,8 +79,41 @@ impl<'a> Widget for StatusBar<'a> {
             ),
             3,
         );
-        let [history_area, tab_area, row_area, shp_area] = Layout::horizontal([
+        let query_tag = self.query_elapsed.map(|elapsed| {
+            Tag::new(
+                "Query",
+                format!(
+                    "{} {:.1}s",
+                    SPINNER[(elapsed.as_millis() / 100) as usize % SPINNER.len()],
+                    elapsed.as_secs_f64()
+                ),
+                4,
+            )
+        });
+        let script_tag = self
+            .script_progress
+            .map(|((statement, statements), elapsed)| {
+                Tag::new(
+                    "Script",
+                    format!(
+                        "{} {statement} / {statements} {:.1}s",
+                        SPINNER[(elapsed.as_millis() / 100) as usize % SPINNER.len()],
+                        elapsed.as_secs_f64()
+                    ),
+                    4,
+                )
+            });
+        let [
+            history_area,
+            script_area,
+            query_area,
+            tab_area,
+            row_area,
+            shp_area,
+        ] = Layout::horizontal([
             Constraint::Fill(3),
+            Constraint::Length(script_tag.as_ref().map(Tag::width).unwrap_or_default()),
+            Constraint::Length(query_tag.as_ref().map(Tag::width).unwrap_or_default()),
             Constraint::Length(tab_tag.width()),
             Constraint::Length(row_tag.width()),
             Constraint::Length(shp_tag.width()),
@@ -72,6 +121,12 @@ impl<'a> Widget for StatusBar<'a> {
         .spacing(1)
         .areas(area);
 
+        if let Some(script_tag) = script_tag {
+            script_tag.line().render(script_area, buf);
+        }
+        if let Some(query_tag) = query_tag {
+            query_tag.line().render(query_area, buf);
+        }
         tab_tag.line().render(tab_area, buf);
         row_tag.line().render(row_area, buf);
         shp_tag.line().render(shp_area, buf);
@@ -86,6 +141,7 @@ impl<'a> Widget for StatusBar<'a> {
 }
 
 const SEPARATOR: &str = "\u{25B6}";
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
 const SUMMARIZED: &str = " ... ";
 
 struct History<'a> {
@@ -0,0 +1,259 @@
+const INDENT: usize = 4;
+
+/// Multi-line text with a cursor, addressed in characters rather than bytes.
+#[derive(Debug, Clone)]
+pub struct TextArea {
+    lines: Vec<String>,
+    row: usize,
+    col: usize,
+}
+
+impl TextArea {
+    /// Text with the cursor at its end.
+    pub fn new(text: &str) -> Self {
+        let lines = text.split('\n').map(str::to_owned).collect::<Vec<_>>();
+        let row = lines.len() - 1;
+        let col = lines[row].chars().count();
+        Self { lines, row, col }
+    }
+
+    pub fn value(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Row and column of the cursor.
+    pub fn position(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    /// Cursor as a character index into [`TextArea::value`].
+    pub fn cursor(&self) -> usize {
+        self.lines[..self.row]
+            .iter()
+            .map(|line| line.chars().count() + 1)
+            .sum::<usize>()
+            + self.col
+    }
+
+    /// Row and column of a character index into [`TextArea::value`].
+    pub fn offset_position(&self, mut offset: usize) -> (usize, usize) {
+        for (row, line) in self.lines.iter().enumerate() {
+            let len = line.chars().count();
+            if offset <= len {
+                return (row, offset);
+            }
+            offset -= len + 1;
+        }
+        let row = self.lines.len() - 1;
+        (row, self.lines[row].chars().count())
+    }
+
+    pub fn insert(&mut self, c: char) {
+        if c == '\n' {
+            let rest = {
+                let line = &mut self.lines[self.row];
+                line.split_off(byte_index(line, self.col))
+            };
+            self.lines.insert(self.row + 1, rest);
+            self.row += 1;
+            self.col = 0;
+        } else {
+            let line = &mut self.lines[self.row];
+            line.insert(byte_index(line, self.col), c);
+            self.col += 1;
+        }
+    }
+
+    pub fn insert_str(&mut self, text: &str) {
+        for c in text.chars() {
+            self.insert(c);
+        }
+    }
+
+    /// Breaks the line, keeping its indentation and indenting once more after an opening
+    /// bracket.
+    pub fn newline(&mut self) {
+        let line = &self.lines[self.row];
+        let mut indent = line.chars().take_while(|c| *c == ' ').count();
+        if line[..byte_index(line, self.col)].trim_end().ends_with('(') {
+            indent += INDENT;
+        }
+        self.insert('\n');
+        self.insert_str(&" ".repeat(indent));
+    }
+
+    pub fn delete_prev(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            let line = &mut self.lines[self.row];
+            line.remove(byte_index(line, self.col));
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.lines[self.row].chars().count();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    pub fn delete_next(&mut self) {
+        let line = &mut self.lines[self.row];
+        if self.col < line.chars().count() {
+            line.remove(byte_index(line, self.col));
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    /// Inserts spaces up to the next indentation stop.
+    pub fn indent(&mut self) {
+        self.insert_str(&" ".repeat(INDENT - self.col % INDENT));
+    }
+
+    /// Removes up to one indentation level from the start of the line.
+    pub fn dedent(&mut self) {
+        let line = &mut self.lines[self.row];
+        let spaces = line.chars().take_while(|c| *c == ' ').count().min(INDENT);
+        line.drain(..spaces);
+        self.col = self.col.saturating_sub(spaces);
+    }
+
+    pub fn move_left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.lines[self.row].chars().count();
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if self.col < self.lines[self.row].chars().count() {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    pub fn move_up(&mut self, rows: usize) {
+        self.row = self.row.saturating_sub(rows);
+        self.col = self.col.min(self.lines[self.row].chars().count());
+    }
+
+    pub fn move_down(&mut self, rows: usize) {
+        self.row = (self.row + rows).min(self.lines.len() - 1);
+        self.col = self.col.min(self.lines[self.row].chars().count());
+    }
+
+    pub fn move_home(&mut self) {
+        self.col = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.col = self.lines[self.row].chars().count();
+    }
+
+    /// Character indexes of the bracket at or just before the cursor and of its match.
+    pub fn matching_brackets(&self) -> Option<(usize, usize)> {
+        let chars = self.value().chars().collect::<Vec<_>>();
+        let cursor = self.cursor();
+        [Some(cursor), cursor.checked_sub(1)]
+            .into_iter()
+            .flatten()
+            .find_map(|idx| {
+                let (open, close, forward) = match chars.get(idx)? {
+                    '(' => ('(', ')', true),
+                    ')' => ('(', ')', false),
+                    '[' => ('[', ']', true),
+                    ']' => ('[', ']', false),
+                    _ => return None,
+                };
+                let mut depth = 0_usize;
+                let mut scan = |other: usize| {
+                    match chars[other] {
+                        c if c == open && forward || c == close && !forward => depth += 1,
+                        c if c == close || c == open => depth -= 1,
+                        _ => (),
+                    }
+                    depth == 0
+                };
+                let found = if forward {
+                    (idx..chars.len()).find(|other| scan(*other))
+                } else {
+                    (0..=idx).rev().find(|other| scan(*other))
+                };
+                found.map(|other| (idx, other))
+            })
+    }
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+fn byte_index(line: &str, col: usize) -> usize {
+    line.char_indices()
+        .nth(col)
+        .map(|(idx, _)| idx)
+        .unwrap_or(line.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_delete_across_lines() {
+        let mut text = TextArea::new("SELECT é");
+        text.insert('\n');
+        text.insert_str("FROM t");
+        assert_eq!(text.value(), "SELECT é\nFROM t");
+        assert_eq!(text.cursor(), 15);
+        text.move_home();
+        text.delete_prev();
+        assert_eq!(text.value(), "SELECT éFROM t");
+        assert_eq!(text.position(), (0, 8));
+        text.delete_next();
+        assert_eq!(text.value(), "SELECT éROM t");
+    }
+
+    #[test]
+    fn test_newline_keeps_and_increases_indent() {
+        let mut text = TextArea::new("    WHERE a IN (");
+        text.newline();
+        text.insert('1');
+        text.newline();
+        assert_eq!(text.value(), "    WHERE a IN (\n        1\n        ");
+        text.dedent();
+        assert_eq!(text.position(), (2, 4));
+    }
+
+    #[test]
+    fn test_offset_position() {
+        let text = TextArea::new("ab\n\ncde");
+        assert_eq!(text.offset_position(0), (0, 0));
+        assert_eq!(text.offset_position(3), (1, 0));
+        assert_eq!(text.offset_position(6), (2, 2));
+        assert_eq!(text.offset_position(99), (2, 3));
+    }
+
+    #[test]
+    fn test_matching_brackets() {
+        let mut text = TextArea::new("f(a, (b))");
+        assert_eq!(text.matching_brackets(), Some((8, 1)));
+        text.move_left();
+        text.move_left();
+        assert_eq!(text.matching_brackets(), Some((7, 5)));
+        text.move_home();
+        assert_eq!(text.matching_brackets(), None);
+        text.move_right();
+        assert_eq!(text.matching_brackets(), Some((1, 8)));
+    }
+}